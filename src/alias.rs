@@ -0,0 +1,290 @@
+//! Saved pipelines (aliases) for the alias and run subcommands.
+//!
+//! An alias stores the arguments of a multi-subcommand pipeline under a name,
+//! in `aliases.json` in the frencli config directory:
+//!
+//! ```json
+//! {
+//!   "aliases": {
+//!     "photos": ["list", "*.jpg", "rename", "%N_%C3.%E", "apply", "--yes"]
+//!   }
+//! }
+//! ```
+//!
+//! `frencli run photos` parses the stored arguments like a command line and
+//! runs them; extra arguments to `run` are appended to the alias's `list`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::executor::validate_subcommand_combinations;
+use crate::subcommands::{ParsedSubcommand, parse_multi_subcommand, get_flag_value, has_flag};
+use crate::suggest::did_you_mean;
+use crate::template_store::config_dir;
+
+/// File name of the alias file inside the frencli config directory
+pub const ALIASES_FILE: &str = "aliases.json";
+
+/// Subcommands that cannot be stored in an alias
+const NOT_IN_ALIAS: [&str; 2] = ["alias", "run"];
+
+/// Layout of the alias file
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AliasFile {
+    aliases: BTreeMap<String, Vec<String>>,
+}
+
+/// Returns the path of the alias file, if a config directory is known.
+pub fn aliases_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(ALIASES_FILE))
+}
+
+/// Reads the aliases from an alias file.
+///
+/// A missing file yields no aliases, so the first saved alias creates it.
+///
+/// # Returns
+///
+/// * `Ok(BTreeMap)` - Alias names and their stored arguments, sorted by name
+/// * `Err(String)` - If the file cannot be read or is malformed
+pub fn read_aliases(path: &Path) -> Result<BTreeMap<String, Vec<String>>, String> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read alias file '{}': {}", path.display(), e))?;
+    let file: AliasFile = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid alias file '{}': {}", path.display(), e))?;
+    Ok(file.aliases)
+}
+
+/// Writes aliases to an alias file, creating its directory if needed.
+pub fn write_aliases(path: &Path, aliases: BTreeMap<String, Vec<String>>) -> Result<(), String> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory '{}': {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(&AliasFile { aliases })
+        .map_err(|e| format!("Failed to serialize aliases: {}", e))?;
+    fs::write(path, json + "\n")
+        .map_err(|e| format!("Failed to write alias file '{}': {}", path.display(), e))
+}
+
+/// Checks that an alias name can be stored and typed after `run`.
+fn validate_alias_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("alias name cannot be empty".to_string());
+    }
+    if name.chars().any(|c| c.is_whitespace()) {
+        return Err(format!("alias name '{}' cannot contain whitespace", name));
+    }
+    if name.starts_with('-') {
+        return Err(format!("alias name '{}' cannot start with '-'", name));
+    }
+    Ok(())
+}
+
+/// Parses the stored arguments of an alias into subcommands.
+///
+/// # Returns
+///
+/// * `Ok(Vec<ParsedSubcommand>)` - The pipeline's subcommands
+/// * `Err(String)` - If the arguments do not parse, are empty, contain `alias`
+///   or `run`, or combine subcommands that cannot be used together
+pub fn parse_alias_pipeline(args: &[String]) -> Result<Vec<ParsedSubcommand>, String> {
+    let subcommands = parse_multi_subcommand(args.to_vec())?;
+    if subcommands.is_empty() {
+        return Err("An alias needs at least one subcommand.".to_string());
+    }
+    if let Some(subcmd) = subcommands.iter().find(|s| NOT_IN_ALIAS.contains(&s.name.as_str())) {
+        return Err(format!("An alias cannot contain '{}'.", subcmd.name));
+    }
+    if subcommands.iter().any(|s| has_flag(&s.flags, "help")) {
+        return Err("An alias cannot contain '--help'.".to_string());
+    }
+    validate_subcommand_combinations(&subcommands)?;
+    Ok(subcommands)
+}
+
+/// Expands a stored alias into subcommands, appending extra arguments to its `list`.
+///
+/// # Arguments
+///
+/// * `aliases` - The saved aliases
+/// * `name` - Name of the alias to expand
+/// * `extra` - Extra search patterns for the alias's `list` subcommand
+///
+/// # Returns
+///
+/// * `Ok(Vec<ParsedSubcommand>)` - The subcommands to run
+/// * `Err(String)` - If the alias is unknown or invalid, or extra patterns are
+///   given for an alias without a `list` that takes patterns
+pub fn expand_alias(
+    aliases: &BTreeMap<String, Vec<String>>,
+    name: &str,
+    extra: &[String],
+) -> Result<Vec<ParsedSubcommand>, String> {
+    let args = aliases.get(name).ok_or_else(|| format!(
+        "Unknown alias '{}'.{} Run 'frencli alias --list' to see the saved aliases.",
+        name,
+        did_you_mean(name, aliases.keys().map(String::as_str))
+    ))?;
+    let mut subcommands = parse_alias_pipeline(args)
+        .map_err(|e| format!("Alias '{}' is invalid: {}", name, e))?;
+
+    if !extra.is_empty() {
+        let list = subcommands.iter_mut().find(|s| s.name == "list")
+            .ok_or_else(|| format!("Alias '{}' has no 'list' subcommand to add '{}' to.", name, extra.join(" ")))?;
+        if has_flag(&list.flags, "files-from") {
+            return Err(format!("Alias '{}' reads its files with '--files-from' and does not take patterns.", name));
+        }
+        list.args.extend(extra.iter().cloned());
+    }
+    Ok(subcommands)
+}
+
+/// Replaces a `run NAME [PATTERN]...` command line with the alias's subcommands.
+///
+/// Command lines without `run` are returned unchanged.
+///
+/// # Returns
+///
+/// * `Ok(Vec<ParsedSubcommand>)` - The subcommands to run
+/// * `Err(String)` - If `run` is combined with other subcommands, has no alias
+///   name, or the alias cannot be expanded
+pub fn expand_run_command(subcommands: Vec<ParsedSubcommand>) -> Result<Vec<ParsedSubcommand>, String> {
+    let Some(run) = subcommands.iter().find(|s| s.name == "run") else {
+        return Ok(subcommands);
+    };
+    if subcommands.len() > 1 {
+        return Err("'run' cannot be used with other subcommands; save them in the alias instead.\n\nExample:\n  frencli run photos \"*.png\"".to_string());
+    }
+    let Some((name, extra)) = run.args.split_first() else {
+        return Err("'run' requires an alias name.\nExample:\n  frencli run photos".to_string());
+    };
+    let path = aliases_path()
+        .ok_or("Cannot determine the user config directory. Set XDG_CONFIG_HOME or HOME.")?;
+    expand_alias(&read_aliases(&path)?, name, extra)
+}
+
+/// Handles the alias subcommand (`--save`, `--list`, `--delete`)
+///
+/// # Arguments
+///
+/// * `subcmd` - The parsed `alias` subcommand
+/// * `store_path` - Path of the alias file
+///
+/// # Returns
+///
+/// * `Ok(())` - The alias was saved or deleted, or the aliases were listed
+/// * `Err(String)` - If the action is missing or invalid, or the file cannot be read or written
+pub fn handle_alias_command(subcmd: &ParsedSubcommand, store_path: &Path) -> Result<(), String> {
+    let actions: Vec<&str> = ["save", "list", "delete"].into_iter()
+        .filter(|action| has_flag(&subcmd.flags, action))
+        .collect();
+    if actions.len() > 1 {
+        return Err(format!("Cannot use '--{}' and '--{}' together.", actions[0], actions[1]));
+    }
+    if actions.first() != Some(&"save") && !subcmd.args.is_empty() {
+        return Err("Subcommands after '--' can only be used with 'alias --save'.".to_string());
+    }
+    if has_flag(&subcmd.flags, "force") && actions.first() != Some(&"save") {
+        return Err("'--force' can only be used with 'alias --save'.".to_string());
+    }
+
+    match actions.first() {
+        Some(&"save") => {
+            let name = get_flag_value(&subcmd.flags, "save").unwrap_or_default();
+            handle_alias_save(store_path, &name, &subcmd.args, has_flag(&subcmd.flags, "force"))
+        }
+        Some(&"delete") => handle_alias_delete(store_path, &get_flag_value(&subcmd.flags, "delete").unwrap_or_default()),
+        Some(_) => handle_alias_list(store_path),
+        None => Err("'alias' requires '--save', '--list' or '--delete'.\n\nExamples:\n  frencli alias --save photos -- list \"*.jpg\" rename \"%N_%C3.%E\"\n  frencli alias --list".to_string()),
+    }
+}
+
+/// Handles `alias --save` - stores a pipeline under a name.
+///
+/// # Arguments
+///
+/// * `store_path` - Path of the alias file
+/// * `name` - Name of the alias
+/// * `args` - The pipeline's arguments, as given after `--`
+/// * `force` - Replace an existing alias with the same name
+///
+/// # Returns
+///
+/// * `Ok(())` - Alias saved
+/// * `Err(String)` - If the name or pipeline is invalid, the alias exists and
+///   `force` is not set, or the file cannot be written
+pub fn handle_alias_save(store_path: &Path, name: &str, args: &[String], force: bool) -> Result<(), String> {
+    validate_alias_name(name)?;
+    if args.is_empty() {
+        return Err(format!("'alias --save' requires the subcommands to save after '--'.\nExample:\n  frencli alias --save {} -- list \"*.jpg\" rename \"%N_%C3.%E\"", name));
+    }
+    parse_alias_pipeline(args).map_err(|e| format!("Cannot save alias '{}': {}", name, e))?;
+
+    let mut aliases = read_aliases(store_path)?;
+    if !force && aliases.contains_key(name) {
+        return Err(format!("Alias '{}' already exists. Use '--force' to replace it.", name));
+    }
+    aliases.insert(name.to_string(), args.to_vec());
+    write_aliases(store_path, aliases)?;
+
+    info!("Saved alias '{}' -> frencli {}", name, format_command(args));
+    Ok(())
+}
+
+/// Handles `alias --delete` - removes an alias.
+///
+/// # Returns
+///
+/// * `Ok(())` - Alias deleted
+/// * `Err(String)` - If no alias has that name or the file cannot be written
+pub fn handle_alias_delete(store_path: &Path, name: &str) -> Result<(), String> {
+    let mut aliases = read_aliases(store_path)?;
+    if aliases.remove(name).is_none() {
+        return Err(format!(
+            "No alias named '{}'.{}",
+            name,
+            did_you_mean(name, aliases.keys().map(String::as_str))
+        ));
+    }
+    write_aliases(store_path, aliases)?;
+
+    info!("Deleted alias '{}'", name);
+    Ok(())
+}
+
+/// Handles `alias --list` - prints every alias with its command line.
+pub fn handle_alias_list(store_path: &Path) -> Result<(), String> {
+    let aliases = read_aliases(store_path)?;
+    if aliases.is_empty() {
+        info!("No aliases saved. Save one with 'frencli alias --save NAME -- <SUBCOMMAND>...'.");
+        return Ok(());
+    }
+    let width = aliases.keys().map(|name| name.chars().count()).max().unwrap_or(0);
+    for (name, args) in &aliases {
+        info!("{:<width$}    frencli {}", name, format_command(args), width = width);
+    }
+    Ok(())
+}
+
+/// Formats stored arguments as a command line, quoting arguments the shell would change.
+pub fn format_command(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            let plain = !arg.is_empty() && arg.chars()
+                .all(|c| c.is_ascii_alphanumeric() || "%_-./:=,+@".contains(c));
+            if plain {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', "'\\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -3,22 +3,63 @@
 //! This module handles the `frencli apply` command which performs the actual file renaming
 //! operations based on a preview generated by the rename command. All operations are async.
 
-use freneng::{perform_renames, FrenError, EnginePreviewResult, FileRename, log_audit_from_result};
-use freneng::history::save_history;
+use freneng::{perform_renames, EnginePreviewResult, FileRename, RenameExecutionResult, RenamingEngine};
+use freneng::history::RenameAction;
 use crate::ui::interactive_edit;
+use crate::error::FrencliError;
+use crate::validate::describe_issue;
+use crate::workdir::{log_audit, resolve, save_history, AUDIT_FILE, HISTORY_FILE};
+use crate::style;
+use std::collections::HashMap;
 use std::io::{self, Write};
-use std::env;
-use std::path::PathBuf;
+use std::path::Path;
+use std::time::Instant;
 use serde::Serialize;
+use tokio::fs;
+
+/// Error message when a file would get an empty name
+const EMPTY_NAMES: &str = "One or more files would have an empty name. Renaming aborted.\n\
+    Please check your pattern and ensure it generates valid filenames.";
 
 #[derive(Serialize)]
 struct ApplyJsonOutput {
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    dry_run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issues: Option<Vec<ErrorJsonItem>>,
     successful: Vec<ApplyJsonItem>,
     skipped: Vec<SkippedJsonItem>,
     errors: Vec<ErrorJsonItem>,
     count: usize,
 }
 
+impl ApplyJsonOutput {
+    /// Builds the JSON report of an execution (real or simulated).
+    fn new(execution: &RenameExecutionResult) -> Self {
+        ApplyJsonOutput {
+            dry_run: false,
+            issues: None,
+            successful: execution.successful.iter().map(|action| ApplyJsonItem {
+                old_path: action.old_path.to_string_lossy().to_string(),
+                new_path: action.new_path.to_string_lossy().to_string(),
+                new_name: action.new_path.file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "?".to_string()),
+            }).collect(),
+            skipped: execution.skipped.iter().map(|(path, reason)| SkippedJsonItem {
+                path: path.to_string_lossy().to_string(),
+                reason: reason.clone(),
+            }).collect(),
+            errors: execution.errors.iter().map(|(path, err)| ErrorJsonItem {
+                path: path.to_string_lossy().to_string(),
+                error: err.clone(),
+            }).collect(),
+            count: execution.successful.len(),
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct ApplyJsonItem {
     old_path: String,
@@ -42,6 +83,7 @@ struct ErrorJsonItem {
 /// 
 /// # Arguments
 /// 
+/// * `base_dir` - Directory the undo history and audit log are written to
 /// * `preview_result` - The preview result from the rename command (contains the mapping)
 /// * `overwrite` - Whether to overwrite existing files
 /// * `yes` - Skip confirmation prompt (rename all)
@@ -54,8 +96,13 @@ struct ErrorJsonItem {
 /// # Returns
 /// 
 /// * `Ok(())` - Command completed successfully
-/// * `Err(FrenError)` - If renaming fails
+/// * `Err(FrencliError)` - [`FrencliError::PartialApply`] if some renames failed,
+///   [`FrencliError::Aborted`] if the user aborted at a prompt,
+///   [`FrencliError::ValidationFailed`] if a file would get an empty name or two
+///   files the same name, or the engine's error if renaming could not start
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_apply_command(
+    base_dir: &Path,
     preview_result: EnginePreviewResult,
     overwrite: bool,
     yes: bool,
@@ -64,22 +111,20 @@ pub async fn handle_apply_command(
     pattern: Option<String>,
     enable_audit: bool,
     json: bool,
-) -> Result<(), FrenError> {
+) -> Result<(), FrencliError> {
     // Preview was already shown by rename/template --use, so we don't show it again
 
     // Show warnings
     if !preview_result.warnings.is_empty() {
-        println!("\nWARNINGS:");
+        info!("\n{}", style::warning("WARNINGS:"));
         for warning in &preview_result.warnings {
-            println!("  - {}", warning);
+            info!("  - {}", style::warning(warning));
         }
     }
 
     // Block if empty names
     if preview_result.has_empty_names {
-        eprintln!("\nERROR: One or more files would have an empty name. Renaming aborted.");
-        eprintln!("Please check your pattern and ensure it generates valid filenames.");
-        std::process::exit(1);
+        return Err(FrencliError::ValidationFailed(EMPTY_NAMES.to_string()));
     }
 
     let mut renames = preview_result.renames;
@@ -87,97 +132,89 @@ pub async fn handle_apply_command(
     // Interactive mode: edit filenames individually
     if interactive {
         if !interactive_edit(&mut renames) {
-            println!("Interactive editing cancelled.");
-            return Ok(());
+            return Err(FrencliError::Aborted("Interactive editing cancelled.".to_string()));
         }
 
         // Re-validate after interactive editing
         let has_empty = renames.iter().any(|r| r.new_name.trim().is_empty());
         if has_empty {
-            eprintln!("\nERROR: One or more files would have an empty name. Renaming aborted.");
-            eprintln!("Please check your pattern and ensure it generates valid filenames.");
-            std::process::exit(1);
+            return Err(FrencliError::ValidationFailed(EMPTY_NAMES.to_string()));
         }
     }
 
+    // perform_renames would silently replace a file renamed earlier in the batch
+    check_duplicate_targets(&renames)?;
+
     // If not --yes and not --interactive, prompt for each file
     if !yes && !interactive {
         renames = prompt_each_rename(&renames)?;
         if renames.is_empty() {
-            println!("No files to rename.");
+            info!("No files to rename.");
             return Ok(());
         }
     }
 
     // Apply renames
+    let started = Instant::now();
     match perform_renames(&renames, overwrite).await {
         Ok(execution) => {
+            for action in &execution.successful {
+                verbose!("Renamed {} -> {}", action.old_path.display(), action.new_path.display());
+            }
+            verbose!("Renamed {} of {} file(s) in {:.2?}", execution.successful.len(), renames.len(), started.elapsed());
+            
             if json {
                 // Output as JSON
-                let json_output = ApplyJsonOutput {
-                    successful: execution.successful.iter().map(|action| ApplyJsonItem {
-                        old_path: action.old_path.to_string_lossy().to_string(),
-                        new_path: action.new_path.to_string_lossy().to_string(),
-                        new_name: action.new_path.file_name()
-                            .and_then(|n| n.to_str())
-                            .map(|s| s.to_string())
-                            .unwrap_or_else(|| "?".to_string()),
-                    }).collect(),
-                    skipped: execution.skipped.iter().map(|(path, reason)| SkippedJsonItem {
-                        path: path.to_string_lossy().to_string(),
-                        reason: reason.clone(),
-                    }).collect(),
-                    errors: execution.errors.iter().map(|(path, err)| ErrorJsonItem {
-                        path: path.to_string_lossy().to_string(),
-                        error: err.clone(),
-                    }).collect(),
-                    count: execution.successful.len(),
-                };
+                let json_output = ApplyJsonOutput::new(&execution);
                 let json_str = serde_json::to_string_pretty(&json_output)
-                    .map_err(|e| FrenError::Pattern(format!("Failed to serialize JSON: {}", e)))?;
+                    .map_err(|e| FrencliError::Io(format!("Failed to serialize JSON: {}", e)))?;
                 println!("{}", json_str);
             } else {
                 // Report results
                 if !execution.skipped.is_empty() {
                     for (path, reason) in &execution.skipped {
-                        println!("Skipping {}: {}", path.display(), reason);
+                        info!("{}", style::warning(&format!("Skipping {}: {}", path.display(), reason)));
                     }
                 }
                 
                 if !execution.errors.is_empty() {
-                    eprintln!("\nErrors:");
+                    eprintln!("\n{}", style::error("Errors:"));
                     for (path, err) in &execution.errors {
-                        eprintln!("  {}: {}", path.display(), err);
+                        eprintln!("  {}: {}", path.display(), style::error(err));
                     }
                 }
                 
-                println!("\nSuccessfully renamed {} file(s).", execution.successful.len());
+                info!("\n{}", style::success(&format!("Successfully renamed {} file(s).", execution.successful.len())));
             }
             
             // Save history
-            if let Err(e) = save_history(execution.successful.clone()).await {
-                if !json {
-                    eprintln!("Warning: Failed to save rename history: {}", e);
+            match save_history(base_dir, execution.successful.clone()).await {
+                Ok(()) if !execution.successful.is_empty() => {
+                    verbose!("Saved undo history to '{}'", resolve(base_dir, HISTORY_FILE).display())
                 }
+                Ok(()) => {}
+                Err(e) if !json => warning!("{}", style::warning(&format!("Warning: Failed to save rename history: {}", e))),
+                Err(_) => {}
             }
             
             // Log to audit file (if enabled)
             if enable_audit {
-                let working_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-                
-                if let Err(e) = log_audit_from_result(
-                    &command,
-                    pattern,
-                    working_dir,
-                    &execution,
-                ).await {
-                    if !json {
-                        eprintln!("Warning: Failed to write audit log: {}", e);
-                    }
+                match log_audit(base_dir, &command, pattern, &execution).await {
+                    Ok(()) => verbose!("Wrote audit log entry to '{}'", resolve(base_dir, AUDIT_FILE).display()),
+                    Err(e) if !json => warning!("{}", style::warning(&format!("Warning: Failed to write audit log: {}", e))),
+                    Err(_) => {}
                 }
             }
             
-            Ok(())
+            if execution.errors.is_empty() {
+                Ok(())
+            } else {
+                Err(FrencliError::PartialApply(format!(
+                    "{} of {} file(s) could not be renamed.",
+                    execution.errors.len(),
+                    execution.successful.len() + execution.skipped.len() + execution.errors.len()
+                )))
+            }
         }
         Err(e) => {
             if json {
@@ -189,18 +226,192 @@ pub async fn handle_apply_command(
                     "count": 0
                 });
                 println!("{}", serde_json::to_string_pretty(&error_json).unwrap());
-            } else {
-                eprintln!("Error performing renames: {}", e);
             }
-            Err(e)
+            Err(e.into())
         }
     }
 }
 
+/// Handles `apply --dry-run` - validates the renames and reports what `apply` would do.
+///
+/// Nothing is renamed, and no history or audit entry is written.
+///
+/// # Arguments
+///
+/// * `engine` - The renaming engine
+/// * `preview_result` - The preview result from the rename command (contains the mapping)
+/// * `overwrite` - Whether `apply` would overwrite existing files
+/// * `json` - If true, output as JSON; if false, output as human-readable
+///
+/// # Returns
+///
+/// * `Ok(RenameExecutionResult)` - The simulated renamed, skipped and failed files
+/// * `Err(FrencliError)` - [`FrencliError::PartialApply`] if some renames would fail, or
+///   [`FrencliError::ValidationFailed`] if a file would get an empty name or two files
+///   the same name
+pub async fn handle_apply_dry_run(
+    engine: &RenamingEngine,
+    preview_result: &EnginePreviewResult,
+    overwrite: bool,
+    json: bool,
+) -> Result<RenameExecutionResult, FrencliError> {
+    if preview_result.has_empty_names {
+        return Err(FrencliError::ValidationFailed(EMPTY_NAMES.to_string()));
+    }
+    check_duplicate_targets(&preview_result.renames)?;
+
+    let validation = engine.validate(&preview_result.renames, overwrite).await;
+    let execution = simulate_renames(&preview_result.renames, overwrite).await;
+
+    if json {
+        let json_output = ApplyJsonOutput {
+            dry_run: true,
+            issues: Some(validation.issues.iter().map(|(path, issue)| ErrorJsonItem {
+                path: path.to_string_lossy().to_string(),
+                error: describe_issue(issue),
+            }).collect()),
+            ..ApplyJsonOutput::new(&execution)
+        };
+        let json_str = serde_json::to_string_pretty(&json_output)
+            .map_err(|e| FrencliError::Io(format!("Failed to serialize JSON: {}", e)))?;
+        println!("{}", json_str);
+    } else {
+        info!("\nDry run: no files will be renamed.");
+
+        if !validation.issues.is_empty() {
+            info!("\n{}", style::error("Validation issues:"));
+            for (path, issue) in &validation.issues {
+                info!("  {}: {}", path.display(), style::error(&describe_issue(issue)));
+            }
+        }
+        if !execution.successful.is_empty() {
+            info!("\n{}", style::success("Would rename:"));
+            for action in &execution.successful {
+                info!("  {} -> {}", action.old_path.display(), action.new_path.display());
+            }
+        }
+        if !execution.skipped.is_empty() {
+            info!("\n{}", style::warning("Would skip:"));
+            for (path, reason) in &execution.skipped {
+                info!("  {}: {}", path.display(), style::warning(reason));
+            }
+        }
+        if !execution.errors.is_empty() {
+            info!("\n{}", style::error("Would fail:"));
+            for (path, err) in &execution.errors {
+                info!("  {}: {}", path.display(), style::error(err));
+            }
+        }
+
+        info!(
+            "\nWould rename {} file(s), skip {} and fail {}.",
+            execution.successful.len(),
+            execution.skipped.len(),
+            execution.errors.len()
+        );
+    }
+
+    if execution.errors.is_empty() {
+        Ok(execution)
+    } else {
+        Err(FrencliError::PartialApply(format!(
+            "{} of {} file(s) would fail to rename.",
+            execution.errors.len(),
+            preview_result.renames.len()
+        )))
+    }
+}
+
+/// Errors if two renames have the same target.
+///
+/// `perform_renames` runs the renames concurrently, so the later rename would
+/// silently replace the file moved by the earlier one. `apply`,
+/// `apply --dry-run` and [`crate::pipeline::Pipeline::apply`] refuse such a
+/// batch before anything is renamed.
+///
+/// # Returns
+///
+/// * `Ok(())` - Every rename has a different target
+/// * `Err(FrencliError)` - [`FrencliError::ValidationFailed`] listing the renames that share a target
+pub fn check_duplicate_targets(renames: &[FileRename]) -> Result<(), FrencliError> {
+    let mut seen: HashMap<&Path, &Path> = HashMap::new();
+    let mut duplicates = Vec::new();
+    for rename in renames {
+        if let Some(first) = seen.insert(&rename.new_path, &rename.old_path) {
+            duplicates.push(format!(
+                "  {} and {} -> {}",
+                first.display(), rename.old_path.display(), rename.new_path.display()
+            ));
+        }
+    }
+    if duplicates.is_empty() {
+        return Ok(());
+    }
+    Err(FrencliError::ValidationFailed(format!(
+        "Several files would be renamed to the same name. Renaming aborted.\n{}",
+        duplicates.join("\n")
+    )))
+}
+
+/// Simulates `perform_renames` against the current file system without renaming anything.
+///
+/// `perform_renames` runs the renames concurrently and checks every target
+/// before the files are moved, so each rename is checked against the files as
+/// they are now, not against the result of the renames before it. Targets are
+/// checked with `fs::metadata` like the engine does, so a dangling symlink
+/// does not count as an existing target. Skip reasons match those of
+/// `perform_renames`. Duplicate targets are rejected before this is called.
+///
+/// # Arguments
+///
+/// * `renames` - The renames to simulate
+/// * `overwrite` - Whether existing targets would be overwritten
+///
+/// # Returns
+///
+/// The renames that would succeed, be skipped or fail, like `perform_renames`
+pub async fn simulate_renames(renames: &[FileRename], overwrite: bool) -> RenameExecutionResult {
+    let mut result = RenameExecutionResult {
+        successful: Vec::new(),
+        skipped: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for rename in renames {
+        if rename.old_path == rename.new_path {
+            result.skipped.push((rename.old_path.clone(), "New name is identical to old name".to_string()));
+            continue;
+        }
+        if !overwrite && fs::metadata(&rename.new_path).await.is_ok() {
+            result.skipped.push((rename.old_path.clone(), "Target file already exists".to_string()));
+            continue;
+        }
+        // fs::rename moves a symlink itself, so the source only has to exist as a link
+        if fs::symlink_metadata(&rename.old_path).await.is_err() {
+            result.errors.push((rename.old_path.clone(), "Source file does not exist".to_string()));
+            continue;
+        }
+        if let Some(parent) = rename.new_path.parent()
+            && !parent.as_os_str().is_empty()
+            && !fs::metadata(parent).await.is_ok_and(|m| m.is_dir()) {
+            result.errors.push((rename.old_path.clone(), format!("Target directory '{}' does not exist", parent.display())));
+            continue;
+        }
+
+        result.successful.push(RenameAction {
+            old_path: rename.old_path.clone(),
+            new_path: rename.new_path.clone(),
+        });
+    }
+
+    result
+}
+
 /// Prompts the user for each rename operation.
 /// 
-/// Returns a filtered list of renames to apply based on user choices.
-fn prompt_each_rename(renames: &[FileRename]) -> Result<Vec<FileRename>, FrenError> {
+/// Returns a filtered list of renames to apply based on user choices,
+/// or [`FrencliError::Aborted`] if the user aborts.
+fn prompt_each_rename(renames: &[FileRename]) -> Result<Vec<FileRename>, FrencliError> {
     println!("\nConfirm each rename (y=yes, s=skip, a=apply all remaining, q=abort):");
     println!("{:-<80}", "");
     
@@ -218,10 +429,10 @@ fn prompt_each_rename(renames: &[FileRename]) -> Result<Vec<FileRename>, FrenErr
         
         loop {
             print!("\n[{}] {} -> {} (y/s/a/q): ", i + 1, old, new);
-            io::stdout().flush().map_err(|e| FrenError::Pattern(format!("IO error: {}", e)))?;
+            io::stdout().flush().map_err(|e| FrencliError::Io(format!("IO error: {}", e)))?;
             
             let mut input = String::new();
-            io::stdin().read_line(&mut input).map_err(|e| FrenError::Pattern(format!("IO error: {}", e)))?;
+            io::stdin().read_line(&mut input).map_err(|e| FrencliError::Io(format!("IO error: {}", e)))?;
             let input = input.trim().to_lowercase();
             
             match input.as_str() {
@@ -240,8 +451,7 @@ fn prompt_each_rename(renames: &[FileRename]) -> Result<Vec<FileRename>, FrenErr
                     break;
                 }
                 "q" | "quit" | "abort" => {
-                    println!("Aborted.");
-                    return Ok(Vec::new());
+                    return Err(FrencliError::Aborted("Renaming aborted; no files were renamed.".to_string()));
                 }
                 _ => {
                     println!("Invalid choice. Use: y (yes), s (skip), a (all), q (abort)");
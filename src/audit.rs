@@ -3,12 +3,15 @@
 //! This module handles the `fren audit` command which displays audit log entries
 //! from previous rename operations.
 
-use freneng::{read_audit_log, AuditEntry};
+use std::path::Path;
+use freneng::AuditEntry;
+use crate::workdir::read_audit_log;
 
 /// Handles the audit subcommand - displays audit log entries.
 /// 
 /// # Arguments
 /// 
+/// * `base_dir` - Directory whose audit log is shown
 /// * `limit` - Maximum number of entries to display (None = all)
 /// * `json` - If true, output as JSON; if false, output as human-readable table
 /// 
@@ -16,11 +19,11 @@ use freneng::{read_audit_log, AuditEntry};
 /// 
 /// * `Ok(())` - Command completed successfully
 /// * `Err(String)` - If audit log reading fails
-pub async fn handle_audit_command(limit: Option<usize>, json: bool) -> Result<(), String> {
-    let entries = read_audit_log().await.map_err(|e| format!("Failed to read audit log: {}", e))?;
+pub async fn handle_audit_command(base_dir: &Path, limit: Option<usize>, json: bool) -> Result<(), String> {
+    let entries = read_audit_log(base_dir).await.map_err(|e| format!("Failed to read audit log: {}", e))?;
     
     if entries.is_empty() {
-        println!("No audit entries found.");
+        info!("No audit entries found.");
         return Ok(());
     }
     
@@ -45,52 +48,52 @@ pub async fn handle_audit_command(limit: Option<usize>, json: bool) -> Result<()
 
 /// Displays audit entries in a human-readable format.
 fn display_audit_entries(entries: &[&AuditEntry]) {
-    println!("Audit Log Entries (showing {} of {}):\n", entries.len(), entries.len());
-    println!("{:-<120}", "");
+    info!("Audit Log Entries (showing {} of {}):\n", entries.len(), entries.len());
+    info!("{:-<120}", "");
     
     for (i, entry) in entries.iter().enumerate() {
-        println!("\nEntry #{}", i + 1);
-        println!("  Timestamp:      {}", entry.timestamp.format("%Y-%m-%d %H:%M:%S"));
+        info!("\nEntry #{}", i + 1);
+        info!("  Timestamp:      {}", entry.timestamp.format("%Y-%m-%d %H:%M:%S"));
         if let Some(user) = &entry.user {
-            println!("  User:           {}", user);
+            info!("  User:           {}", user);
         }
-        println!("  Directory:      {}", entry.working_directory.display());
-        println!("  Command:        {}", entry.command);
+        info!("  Directory:      {}", entry.working_directory.display());
+        info!("  Command:        {}", entry.command);
         if let Some(pattern) = &entry.pattern {
-            println!("  Pattern:        {}", pattern);
+            info!("  Pattern:        {}", pattern);
         }
-        println!("  Results:        {} successful, {} skipped, {} errors",
+        info!("  Results:        {} successful, {} skipped, {} errors",
             entry.successful_count, entry.skipped_count, entry.error_count);
         
         if !entry.successful.is_empty() {
-            println!("  Successful renames:");
+            info!("  Successful renames:");
             for (old, new) in &entry.successful {
-                println!("    {} -> {}", 
+                info!("    {} -> {}", 
                     old.file_name().and_then(|n| n.to_str()).unwrap_or("?"),
                     new.file_name().and_then(|n| n.to_str()).unwrap_or("?"));
             }
         }
         
         if !entry.skipped.is_empty() {
-            println!("  Skipped files:");
+            info!("  Skipped files:");
             for (path, reason) in &entry.skipped {
-                println!("    {}: {}", 
+                info!("    {}: {}", 
                     path.file_name().and_then(|n| n.to_str()).unwrap_or("?"),
                     reason);
             }
         }
         
         if !entry.errors.is_empty() {
-            println!("  Errors:");
+            info!("  Errors:");
             for (path, error) in &entry.errors {
-                println!("    {}: {}", 
+                info!("    {}: {}", 
                     path.file_name().and_then(|n| n.to_str()).unwrap_or("?"),
                     error);
             }
         }
         
         if i < entries.len() - 1 {
-            println!("{:-<120}", "");
+            info!("{:-<120}", "");
         }
     }
 }
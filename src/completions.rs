@@ -0,0 +1,295 @@
+//! Shell completion scripts for the completions subcommand.
+//!
+//! The scripts are generated from the command spec in `spec.rs`, so they know
+//! every subcommand and its flags. Since subcommands can be combined in one
+//! command line, a flag is completed for the last subcommand typed before the
+//! cursor. Template names are not baked into the scripts: they call
+//! `frencli completions --template-names` when completing, so user and project
+//! templates are always current.
+
+use std::fmt::Write;
+use crate::spec::{FlagSpec, SubcommandSpec, ValueHint, GLOBAL_FLAGS, HELP_FLAG, SHELLS, SUBCOMMANDS, TOP_LEVEL_OPTIONS};
+use crate::subcommands::{ParsedSubcommand, has_flag};
+use crate::templates::TemplateRegistry;
+
+/// Command the scripts run to list template names
+const TEMPLATE_NAMES_COMMAND: &str = "frencli completions --template-names 2>/dev/null";
+
+/// Handles the completions subcommand
+///
+/// # Arguments
+///
+/// * `subcmd` - The parsed `completions` subcommand
+/// * `template_registry` - The template registry, for `--template-names`
+///
+/// # Returns
+///
+/// * `Ok(())` - The script or the template names were printed
+/// * `Err(String)` - If the shell is missing or not supported
+pub fn handle_completions_command(subcmd: &ParsedSubcommand, template_registry: &TemplateRegistry) -> Result<(), String> {
+    if has_flag(&subcmd.flags, "template-names") {
+        for (name, _) in template_registry.templates() {
+            println!("{}", name);
+        }
+        return Ok(());
+    }
+    match subcmd.args.as_slice() {
+        [shell] => {
+            print!("{}", generate_completions(shell)?);
+            Ok(())
+        }
+        _ => Err(format!(
+            "'completions' requires one shell name: {}.\nExample:\n  frencli completions bash",
+            SHELLS.join(", ")
+        )),
+    }
+}
+
+/// Generates the completion script for a shell
+///
+/// # Arguments
+///
+/// * `shell` - `bash`, `zsh` or `fish`
+///
+/// # Returns
+///
+/// * `Ok(String)` - The completion script
+/// * `Err(String)` - If the shell is not supported
+pub fn generate_completions(shell: &str) -> Result<String, String> {
+    match shell {
+        "bash" => Ok(bash_script()),
+        "zsh" => Ok(zsh_script()),
+        "fish" => Ok(fish_script()),
+        _ => Err(format!("Unsupported shell '{}'. Use one of: {}.", shell, SHELLS.join(", "))),
+    }
+}
+
+/// Flags of a subcommand including `--help`, as `--name` words
+fn flag_words(spec: &SubcommandSpec) -> String {
+    all_flags(spec).map(|flag| format!("--{}", flag.name)).collect::<Vec<_>>().join(" ")
+}
+
+/// Flags of a subcommand including `--help`
+fn all_flags(spec: &SubcommandSpec) -> impl Iterator<Item = &FlagSpec> {
+    spec.flags.iter().chain(std::iter::once(&HELP_FLAG))
+}
+
+/// Options accepted before the first subcommand, as `--name` words
+fn top_level_words() -> String {
+    GLOBAL_FLAGS.iter()
+        .map(|flag| format!("--{}", flag.name))
+        .chain(TOP_LEVEL_OPTIONS.iter().map(|(name, _)| name.to_string()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Global options that take a file name
+fn global_file_flags() -> impl Iterator<Item = &'static FlagSpec> {
+    GLOBAL_FLAGS.iter().filter(|flag| flag.hint == ValueHint::File)
+}
+
+/// Names of all subcommands separated by `separator`
+fn subcommand_list(separator: &str) -> String {
+    SUBCOMMANDS.iter().map(|spec| spec.name).collect::<Vec<_>>().join(separator)
+}
+
+fn bash_script() -> String {
+    let mut out = String::new();
+    out.push_str("# bash completion for frencli\n");
+    out.push_str("_frencli() {\n");
+    out.push_str("    local cur prev subcmd i\n");
+    out.push_str("    cur=\"${COMP_WORDS[COMP_CWORD]}\"\n");
+    out.push_str("    prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n");
+    let _ = writeln!(out, "    local subcommands=\"{}\"", subcommand_list(" "));
+    out.push('\n');
+    out.push_str("    # Flags belong to the last subcommand before the cursor\n");
+    out.push_str("    subcmd=\"\"\n");
+    out.push_str("    for ((i = 1; i < COMP_CWORD; i++)); do\n");
+    let _ = writeln!(out, "        case \"${{COMP_WORDS[i]}}\" in\n            {}) subcmd=\"${{COMP_WORDS[i]}}\" ;;\n        esac", subcommand_list("|"));
+    out.push_str("    done\n\n");
+
+    out.push_str("    case \"$subcmd:$prev\" in\n");
+    for flag in global_file_flags() {
+        let _ = writeln!(out, "        :--{})\n            return ;;", flag.name);
+    }
+    for spec in SUBCOMMANDS {
+        for flag in spec.flags {
+            match flag.hint {
+                ValueHint::TemplateName => {
+                    let _ = writeln!(out, "        {}:--{})\n            COMPREPLY=($(compgen -W \"$({})\" -- \"$cur\"))\n            return ;;",
+                        spec.name, flag.name, TEMPLATE_NAMES_COMMAND);
+                }
+                ValueHint::Choices(choices) => {
+                    let _ = writeln!(out, "        {}:--{})\n            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            return ;;",
+                        spec.name, flag.name, choices.join(" "));
+                }
+                // Empty replies fall back to file names (complete -o default)
+                ValueHint::File => {
+                    let _ = writeln!(out, "        {}:--{})\n            return ;;", spec.name, flag.name);
+                }
+                ValueHint::None | ValueHint::Subcommand => {}
+            }
+        }
+    }
+    out.push_str("    esac\n\n");
+
+    out.push_str("    if [[ \"$cur\" == --* ]]; then\n");
+    out.push_str("        case \"$subcmd\" in\n");
+    for spec in SUBCOMMANDS {
+        let _ = writeln!(out, "            {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;", spec.name, flag_words(spec));
+    }
+    let _ = writeln!(out, "            *) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;", top_level_words());
+    out.push_str("        esac\n");
+    out.push_str("        return\n");
+    out.push_str("    fi\n\n");
+
+    out.push_str("    case \"$subcmd\" in\n");
+    for spec in SUBCOMMANDS {
+        match spec.args.first().map(|arg| arg.hint) {
+            Some(ValueHint::Choices(choices)) => {
+                let _ = writeln!(out, "        {})\n            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            return ;;",
+                    spec.name, choices.join(" "));
+            }
+            Some(ValueHint::File) => {
+                let _ = writeln!(out, "        {})\n            COMPREPLY=($(compgen -W \"$subcommands\" -- \"$cur\") $(compgen -f -- \"$cur\"))\n            return ;;",
+                    spec.name);
+            }
+            _ => {}
+        }
+    }
+    out.push_str("    esac\n");
+    out.push_str("    COMPREPLY=($(compgen -W \"$subcommands\" -- \"$cur\"))\n");
+    out.push_str("}\n");
+    out.push_str("complete -o default -F _frencli frencli\n");
+    out
+}
+
+fn zsh_script() -> String {
+    let mut out = String::new();
+    out.push_str("#compdef frencli\n\n");
+    out.push_str("_frencli() {\n");
+    let _ = writeln!(out, "    local -a subcommands\n    subcommands=({})", subcommand_list(" "));
+    out.push_str("    local subcmd=\"\" prev=\"${words[CURRENT-1]}\" cur=\"${words[CURRENT]}\" i\n\n");
+    out.push_str("    # Flags belong to the last subcommand before the cursor\n");
+    out.push_str("    for (( i = 2; i < CURRENT; i++ )); do\n");
+    out.push_str("        if (( ${subcommands[(Ie)${words[i]}]} )); then\n");
+    out.push_str("            subcmd=\"${words[i]}\"\n");
+    out.push_str("        fi\n");
+    out.push_str("    done\n\n");
+
+    out.push_str("    case \"$subcmd:$prev\" in\n");
+    for flag in global_file_flags() {
+        let _ = writeln!(out, "        :--{})\n            _files\n            return ;;", flag.name);
+    }
+    for spec in SUBCOMMANDS {
+        for flag in spec.flags {
+            match flag.hint {
+                ValueHint::TemplateName => {
+                    let _ = writeln!(out, "        {}:--{})\n            compadd -- ${{(f)\"$({})\"}}\n            return ;;",
+                        spec.name, flag.name, TEMPLATE_NAMES_COMMAND);
+                }
+                ValueHint::Choices(choices) => {
+                    let _ = writeln!(out, "        {}:--{})\n            compadd -- {}\n            return ;;",
+                        spec.name, flag.name, choices.join(" "));
+                }
+                ValueHint::File => {
+                    let _ = writeln!(out, "        {}:--{})\n            _files\n            return ;;", spec.name, flag.name);
+                }
+                ValueHint::None | ValueHint::Subcommand => {}
+            }
+        }
+    }
+    out.push_str("    esac\n\n");
+
+    out.push_str("    if [[ \"$cur\" == --* ]]; then\n");
+    out.push_str("        case \"$subcmd\" in\n");
+    for spec in SUBCOMMANDS {
+        let _ = writeln!(out, "            {}) compadd -- {} ;;", spec.name, flag_words(spec));
+    }
+    let _ = writeln!(out, "            *) compadd -- {} ;;", top_level_words());
+    out.push_str("        esac\n");
+    out.push_str("        return\n");
+    out.push_str("    fi\n\n");
+
+    out.push_str("    case \"$subcmd\" in\n");
+    for spec in SUBCOMMANDS {
+        match spec.args.first().map(|arg| arg.hint) {
+            Some(ValueHint::Choices(choices)) => {
+                let _ = writeln!(out, "        {})\n            compadd -- {}\n            return ;;", spec.name, choices.join(" "));
+            }
+            Some(ValueHint::File) => {
+                let _ = writeln!(out, "        {})\n            compadd -- $subcommands\n            _files\n            return ;;", spec.name);
+            }
+            _ => {}
+        }
+    }
+    out.push_str("    esac\n");
+    out.push_str("    compadd -- $subcommands\n");
+    out.push_str("}\n\n");
+    out.push_str("if [[ \"${funcstack[1]}\" == \"_frencli\" ]]; then\n");
+    out.push_str("    _frencli \"$@\"\n");
+    out.push_str("else\n");
+    out.push_str("    compdef _frencli frencli\n");
+    out.push_str("fi\n");
+    out
+}
+
+/// Quotes text for a single-quoted fish string
+fn fish_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn fish_script() -> String {
+    let mut out = String::new();
+    out.push_str("# fish completion for frencli\n\n");
+    out.push_str("# Prints the last subcommand on the command line; flags belong to it\n");
+    out.push_str("function __frencli_subcommand\n");
+    out.push_str("    set -l current \"\"\n");
+    out.push_str("    for word in (commandline -opc)[2..-1]\n");
+    let _ = writeln!(out, "        if contains -- $word {}\n            set current $word\n        end", subcommand_list(" "));
+    out.push_str("    end\n");
+    out.push_str("    echo $current\n");
+    out.push_str("end\n\n");
+    out.push_str("function __frencli_using\n");
+    out.push_str("    test (__frencli_subcommand) = $argv[1]\n");
+    out.push_str("end\n\n");
+
+    out.push_str("complete -c frencli -f\n");
+    for flag in GLOBAL_FLAGS {
+        let value = if flag.hint == ValueHint::File { " -r -F" } else { "" };
+        let description = fish_quote(flag.help.first().copied().unwrap_or_default());
+        let _ = writeln!(out, "complete -c frencli -n 'test -z (__frencli_subcommand)' -l {}{} -d {}", flag.name, value, description);
+    }
+    out.push_str("complete -c frencli -n 'test -z (__frencli_subcommand)' -l help -d 'Print help'\n");
+    out.push_str("complete -c frencli -n 'test -z (__frencli_subcommand)' -l version -d 'Print version'\n");
+    for spec in SUBCOMMANDS {
+        let _ = writeln!(out, "complete -c frencli -a {} -d {}", spec.name, fish_quote(spec.summary));
+    }
+
+    for spec in SUBCOMMANDS {
+        out.push('\n');
+        let condition = fish_quote(&format!("__frencli_using {}", spec.name));
+        for flag in all_flags(spec) {
+            let description = fish_quote(flag.help.first().copied().unwrap_or_default());
+            let value = match flag.hint {
+                ValueHint::TemplateName => format!(" -x -a {}", fish_quote(&format!("({})", TEMPLATE_NAMES_COMMAND))),
+                ValueHint::Choices(choices) => format!(" -x -a {}", fish_quote(&choices.join(" "))),
+                ValueHint::Subcommand => format!(" -x -a {}", fish_quote(&subcommand_list(" "))),
+                ValueHint::File => " -r -F".to_string(),
+                ValueHint::None if flag.value_name.is_empty() => String::new(),
+                ValueHint::None => " -x".to_string(),
+            };
+            let _ = writeln!(out, "complete -c frencli -n {} -l {}{} -d {}", condition, flag.name, value, description);
+        }
+        match spec.args.first().map(|arg| arg.hint) {
+            Some(ValueHint::Choices(choices)) => {
+                let _ = writeln!(out, "complete -c frencli -n {} -a {}", condition, fish_quote(&choices.join(" ")));
+            }
+            Some(ValueHint::File) => {
+                let _ = writeln!(out, "complete -c frencli -n {} -F", condition);
+            }
+            _ => {}
+        }
+    }
+    out
+}
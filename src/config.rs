@@ -0,0 +1,130 @@
+//! Global configuration file with default options.
+//!
+//! The config file (`config.toml` in the frencli config directory) supplies
+//! defaults for options that would otherwise be repeated on every invocation,
+//! such as list excludes or turning off the audit log. Options given on the
+//! command line take precedence over the config file.
+//!
+//! ```toml
+//! [list]
+//! recursive = true
+//! exclude = [".git", "node_modules"]
+//!
+//! [apply]
+//! audit = false
+//! ```
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use crate::template_store::config_dir;
+
+/// Name of the config file in the frencli config directory
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// Environment variable that overrides the config file location
+pub const CONFIG_ENV: &str = "FRENCLI_CONFIG";
+
+/// Default options for `list`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ListDefaults {
+    pub recursive: bool,
+    pub exclude: Vec<String>,
+    pub fullpath: bool,
+    pub json: bool,
+}
+
+/// Default options for `rename` and `template --use`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RenameDefaults {
+    pub json: bool,
+}
+
+/// Default options for `apply`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApplyDefaults {
+    pub overwrite: bool,
+    /// Record renames in the audit log; `false` is the same as always passing `--no-audit`
+    pub audit: bool,
+    pub json: bool,
+}
+
+impl Default for ApplyDefaults {
+    fn default() -> Self {
+        ApplyDefaults { overwrite: false, audit: true, json: false }
+    }
+}
+
+/// Contents of the config file; every section and field is optional.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub list: ListDefaults,
+    pub rename: RenameDefaults,
+    pub apply: ApplyDefaults,
+}
+
+impl Config {
+    /// Parses the contents of a config file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Config)` - The parsed config
+    /// * `Err(String)` - If the content is not valid TOML or has unknown or mistyped fields
+    pub fn parse(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| match e.span() {
+            Some(span) => {
+                let line = content[..span.start].matches('\n').count() + 1;
+                format!("line {}: {}", line, e.message().trim_end())
+            }
+            None => e.message().trim_end().to_string(),
+        })
+    }
+
+    /// Loads the config file, if there is one.
+    ///
+    /// The file is taken from `explicit` (the `--config` option) if given, then
+    /// from the `FRENCLI_CONFIG` environment variable, then from `config.toml`
+    /// in the config directory. A file named by `--config` or `FRENCLI_CONFIG`
+    /// must exist; the default file is optional.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Config))` - The loaded config
+    /// * `Ok(None)` - If there is no config file
+    /// * `Err(String)` - If the file cannot be read or is invalid
+    pub fn load(explicit: Option<&str>) -> Result<Option<Self>, String> {
+        match Self::path(explicit) {
+            Some(path) => {
+                verbose!("Using config file '{}'", path.display());
+                Self::read(&path).map(Some)
+            }
+            None => {
+                verbose!("No config file found");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns the config file [`Config::load`] reads, or `None` if there is none.
+    ///
+    /// A file named by `explicit` or `FRENCLI_CONFIG` is returned even if it
+    /// does not exist, so that loading it reports the error.
+    pub fn path(explicit: Option<&str>) -> Option<PathBuf> {
+        explicit.map(PathBuf::from)
+            .or_else(|| env::var_os(CONFIG_ENV).filter(|v| !v.is_empty()).map(PathBuf::from))
+            .or_else(|| config_dir().map(|dir| dir.join(CONFIG_FILE)).filter(|path| path.exists()))
+    }
+
+    /// Reads and parses a config file.
+    fn read(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read config file '{}': {}", path.display(), e))?;
+        Self::parse(&content)
+            .map_err(|e| format!("Invalid config file '{}': {}", path.display(), e))
+    }
+}
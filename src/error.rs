@@ -0,0 +1,95 @@
+//! Error type for frencli commands, with a distinct process exit code per kind of failure.
+//!
+//! Scripts can branch on the exit code instead of parsing messages:
+//!
+//! | Code | Meaning                                                        |
+//! |------|----------------------------------------------------------------|
+//! | 0    | Success                                                        |
+//! | 2    | Usage error: invalid command line, config file or alias        |
+//! | 3    | No files matched the `list` patterns                           |
+//! | 4    | Validation failed (`validate`, empty names, `template --check`) |
+//! | 5    | `apply` could not rename some of the files                     |
+//! | 6    | Aborted at a confirmation prompt                               |
+//! | 7    | I/O error reading or writing files                             |
+
+use std::fmt;
+use freneng::FrenError;
+
+/// Exit code for a usage error
+pub const EXIT_USAGE: i32 = 2;
+
+/// Exit code when no files matched the `list` patterns
+pub const EXIT_NO_FILES_MATCHED: i32 = 3;
+
+/// Exit code when validation failed
+pub const EXIT_VALIDATION_FAILED: i32 = 4;
+
+/// Exit code when `apply` could not rename some of the files
+pub const EXIT_PARTIAL_APPLY: i32 = 5;
+
+/// Exit code when the user aborted at a prompt
+pub const EXIT_ABORTED: i32 = 6;
+
+/// Exit code for an I/O error
+pub const EXIT_IO: i32 = 7;
+
+/// A failed frencli command; each variant holds the message shown to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrencliError {
+    /// The command line, a config file, a template or an alias is invalid
+    Usage(String),
+    /// No files matched the `list` patterns, so there is nothing to rename
+    NoFilesMatched(String),
+    /// Validation found problems with the renames or templates
+    ValidationFailed(String),
+    /// `apply` renamed some files but could not rename others
+    PartialApply(String),
+    /// The user aborted at a prompt
+    Aborted(String),
+    /// Reading or writing a file failed
+    Io(String),
+}
+
+impl FrencliError {
+    /// Returns the process exit code for this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            FrencliError::Usage(_) => EXIT_USAGE,
+            FrencliError::NoFilesMatched(_) => EXIT_NO_FILES_MATCHED,
+            FrencliError::ValidationFailed(_) => EXIT_VALIDATION_FAILED,
+            FrencliError::PartialApply(_) => EXIT_PARTIAL_APPLY,
+            FrencliError::Aborted(_) => EXIT_ABORTED,
+            FrencliError::Io(_) => EXIT_IO,
+        }
+    }
+
+    /// Returns the message shown to the user.
+    pub fn message(&self) -> &str {
+        match self {
+            FrencliError::Usage(message)
+            | FrencliError::NoFilesMatched(message)
+            | FrencliError::ValidationFailed(message)
+            | FrencliError::PartialApply(message)
+            | FrencliError::Aborted(message)
+            | FrencliError::Io(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for FrencliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for FrencliError {}
+
+impl From<FrenError> for FrencliError {
+    /// File system errors from the engine are I/O errors; invalid patterns are usage errors.
+    fn from(error: FrenError) -> Self {
+        match error {
+            FrenError::Io(_) => FrencliError::Io(error.to_string()),
+            _ => FrencliError::Usage(error.to_string()),
+        }
+    }
+}
@@ -2,21 +2,33 @@
 //! 
 //! This module handles the execution of parsed subcommands, including:
 //! - Standalone commands (undo, audit, interactive, template --list)
+//! - Template previews (list -> template --try)
 //! - Subcommand argument extraction
 //! - Execution orchestration (list -> rename -> validate -> apply)
 
 use freneng::RenamingEngine;
 use crate::subcommands::{ParsedSubcommand, get_flag_value, has_flag, get_flag_values};
-use crate::templates::TemplateRegistry;
-use crate::list::find_files;
-use crate::rename::handle_rename_command;
-use crate::apply::handle_apply_command;
-use crate::template::handle_template_command;
+use crate::templates::{TemplateRegistry, parse_param_assignments};
+use crate::list::{find_files_filtered, parse_size, parse_time, FileType, ListFilters};
+use crate::rename::handle_rename_chain_command;
+use crate::apply::{handle_apply_command, handle_apply_dry_run};
+use crate::template::{
+    handle_template_list, handle_template_try, handle_template_check, handle_template_save, handle_template_delete,
+    handle_template_rename, handle_template_export, handle_template_import, ConflictPolicy,
+};
+use crate::template_store::user_templates_path;
 use crate::validate::handle_validate_command;
 use crate::undo::{handle_undo_check, handle_undo_apply};
 use crate::audit::handle_audit_command;
 use crate::interactive::handle_interactive_command;
-use std::path::PathBuf;
+use crate::completions::handle_completions_command;
+use crate::manpage::handle_manpage_command;
+use crate::config::Config;
+use crate::error::FrencliError;
+use crate::alias::{aliases_path, handle_alias_command};
+use crate::workdir::{resolve, resolve_pattern};
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime};
 use std::fs;
 use std::io::{self, BufRead};
 
@@ -29,14 +41,32 @@ pub struct CommandConfig {
     pub list_exclude: Vec<String>,
     pub list_fullpath: bool,
     pub list_json: bool,
+    pub list_filters: ListFilters,
     pub rename_pattern: Option<String>,
+    pub rename_chain: Vec<String>,  // Patterns applied after rename_pattern, in order
     pub rename_json: bool,
+    pub show_stages: bool,
     pub template_use: Option<String>,
+    pub template_set: Vec<(String, String)>,
+    pub template_try: Option<Vec<String>>,  // Templates to preview; empty for all
+    pub template_category: Option<String>,
     pub validate_skip_invalid: bool,
     pub apply_overwrite: bool,
     pub apply_yes: bool,
     pub apply_interactive: bool,
     pub apply_json: bool,
+    pub apply_no_audit: bool,
+    pub apply_dry_run: bool,
+}
+
+impl CommandConfig {
+    /// Returns all rename patterns given to `rename`, in the order they are applied.
+    pub fn rename_patterns(&self) -> Vec<String> {
+        self.rename_pattern.iter()
+            .chain(self.rename_chain.iter())
+            .cloned()
+            .collect()
+    }
 }
 
 /// Reads file paths from a file or stdin
@@ -44,18 +74,19 @@ pub struct CommandConfig {
 /// # Arguments
 /// 
 /// * `source` - File path, or "-" for stdin
+/// * `base_dir` - Directory the file and the relative paths it lists are resolved against
 /// 
 /// # Returns
 /// 
 /// * `Ok(Vec<PathBuf>)` - List of file paths
 /// * `Err(String)` - Error message
-fn read_files_from_source(source: &str) -> Result<Vec<PathBuf>, String> {
+fn read_files_from_source(source: &str, base_dir: &Path) -> Result<Vec<PathBuf>, String> {
     let reader: Box<dyn BufRead> = if source == "-" {
         // Read from stdin
         Box::new(io::BufReader::new(io::stdin()))
     } else {
         // Read from file
-        let file = fs::File::open(source)
+        let file = fs::File::open(resolve(base_dir, source))
             .map_err(|e| format!("Failed to open file '{}': {}", source, e))?;
         Box::new(io::BufReader::new(file))
     };
@@ -68,32 +99,141 @@ fn read_files_from_source(source: &str) -> Result<Vec<PathBuf>, String> {
         if trimmed.is_empty() || trimmed.starts_with('#') {
             continue;
         }
-        files.push(PathBuf::from(trimmed));
+        files.push(resolve(base_dir, trimmed));
     }
     
     Ok(files)
 }
 
-/// Handles standalone commands that must be used alone (undo, audit, interactive, template --list)
+/// Builds the metadata filters from `list --min-size`, `--max-size`, `--newer`, `--older` and `--type`.
+fn extract_list_filters(subcmd: &ParsedSubcommand) -> Result<ListFilters, String> {
+    let now = SystemTime::now();
+    let size = |flag| get_flag_value(&subcmd.flags, flag).map(|value| parse_size(&value)).transpose();
+    let time = |flag| get_flag_value(&subcmd.flags, flag).map(|value| parse_time(&value, now)).transpose();
+    let filters = ListFilters {
+        min_size: size("min-size")?,
+        max_size: size("max-size")?,
+        newer: time("newer")?,
+        older: time("older")?,
+        file_type: get_flag_value(&subcmd.flags, "type").map(|value| FileType::parse(&value)).transpose()?,
+    };
+    if let (Some(min), Some(max)) = (filters.min_size, filters.max_size)
+        && min > max {
+        return Err("'--min-size' is larger than '--max-size', so no file can match.".to_string());
+    }
+    Ok(filters)
+}
+
+/// Resolves a switch that has a negating flag, such as `--overwrite`/`--no-overwrite`.
+///
+/// Either flag overrides `default` (the config file value); giving both is an error.
+fn switch_setting(subcmd: &ParsedSubcommand, on: &str, off: &str, default: bool) -> Result<bool, String> {
+    match (has_flag(&subcmd.flags, on), has_flag(&subcmd.flags, off)) {
+        (true, true) => Err(format!("Cannot use both '--{}' and '--{}'.", on, off)),
+        (true, false) => Ok(true),
+        (false, true) => Ok(false),
+        (false, false) => Ok(default),
+    }
+}
+
+/// Template actions that read or edit template files and must be used alone
+const TEMPLATE_EDIT_ACTIONS: [&str; 5] = ["save", "delete", "rename", "export", "import"];
+
+/// Resolves a `template --export`/`--import` bundle file against the base directory,
+/// keeping `-` (stdout or stdin) as is.
+fn resolve_bundle_path(base_dir: &Path, bundle_path: &str) -> String {
+    if bundle_path == "-" {
+        bundle_path.to_string()
+    } else {
+        resolve(base_dir, bundle_path).to_string_lossy().to_string()
+    }
+}
+
+/// Handles `template --save`, `--delete`, `--rename`, `--export` and `--import`,
+/// which read or edit template files. Bundle files are relative to `base_dir`.
+fn handle_template_edit(subcmd: &ParsedSubcommand, template_registry: &TemplateRegistry, base_dir: &Path) -> Result<(), String> {
+    let actions: Vec<&str> = TEMPLATE_EDIT_ACTIONS.into_iter()
+        .filter(|action| has_flag(&subcmd.flags, action))
+        .collect();
+    if actions.len() > 1 {
+        return Err(format!("Cannot use '--{}' and '--{}' together.", actions[0], actions[1]));
+    }
+
+    let store_path = user_templates_path()
+        .ok_or("Cannot determine the user config directory. Set XDG_CONFIG_HOME or HOME.")?;
+    let force = has_flag(&subcmd.flags, "force");
+    let values = get_flag_values(&subcmd.flags, actions[0]);
+    let params = parse_param_assignments(&get_flag_values(&subcmd.flags, "set"))?;
+    if !params.is_empty() && actions[0] != "save" {
+        return Err("'--set' can only be used with 'template --use' or 'template --save'.".to_string());
+    }
+    let on_conflict = if has_flag(&subcmd.flags, "on-conflict") {
+        if actions[0] != "import" {
+            return Err("'--on-conflict' can only be used with 'template --import'.".to_string());
+        }
+        Some(ConflictPolicy::parse(&get_flag_value(&subcmd.flags, "on-conflict").unwrap_or_default())?)
+    } else {
+        None
+    };
+
+    match (actions[0], values.as_slice()) {
+        ("save", [name, pattern]) => handle_template_save(&store_path, name, pattern, &params, force),
+        ("save", _) => Err("'template --save' requires a name and a pattern.\nExample:\n  frencli template --save podcast \"%T%N_%C3.%E\"".to_string()),
+        ("delete", [name]) => handle_template_delete(&store_path, name),
+        ("delete", _) => Err("'template --delete' requires a template name.\nExample:\n  frencli template --delete podcast".to_string()),
+        ("rename", [old_name, new_name]) => handle_template_rename(&store_path, old_name, new_name, force),
+        ("rename", _) => Err("'template --rename' requires the current and the new template name.\nExample:\n  frencli template --rename podcast episode".to_string()),
+        ("export", [bundle_path, names @ ..]) => handle_template_export(template_registry, &resolve_bundle_path(base_dir, bundle_path), names).map(|_| ()),
+        ("export", _) => Err("'template --export' requires a bundle file name.\nExample:\n  frencli template --export team-templates.json podcast scan".to_string()),
+        ("import", [bundle_path]) => handle_template_import(template_registry, &store_path, &resolve_bundle_path(base_dir, bundle_path), on_conflict).map(|_| ()),
+        _ => Err("'template --import' requires one bundle file name.\nExample:\n  frencli template --import team-templates.json --on-conflict rename-on-import".to_string()),
+    }
+}
+
+/// Handles standalone commands that must be used alone (undo, audit, interactive, alias, completions, manpage, template --list/--check/--save/--delete/--rename/--export/--import)
 pub async fn handle_standalone_commands(
     subcommands: &[ParsedSubcommand],
     engine: &RenamingEngine,
     template_registry: &TemplateRegistry,
-) -> Result<Option<()>, String> {
+    base_dir: &Path,
+) -> Result<Option<()>, FrencliError> {
     // Check if template --list is present
     for subcmd in subcommands {
         if subcmd.name == "template" && has_flag(&subcmd.flags, "list") {
-            handle_template_command(template_registry, true, None)
-                .map_err(|e| format!("Error: {}", e))?;
+            let category = get_flag_value(&subcmd.flags, "category");
+            let json = has_flag(&subcmd.flags, "json");
+            handle_template_list(template_registry, category.as_deref(), json)
+                .map_err(FrencliError::Usage)?;
             return Ok(Some(()));
         }
     }
     
+    // Check if template --check is present - it must be used alone
+    if let Some(subcmd) = subcommands.iter().find(|s| s.name == "template" && has_flag(&s.flags, "check")) {
+        if subcommands.len() > 1 {
+            return Err(FrencliError::Usage("'template --check' cannot be used with other subcommands.\n\nExamples:\n  frencli template --check\n  frencli template --check podcast".to_string()));
+        }
+        handle_template_check(template_registry, &get_flag_values(&subcmd.flags, "check"))
+            .map_err(FrencliError::ValidationFailed)?;
+        return Ok(Some(()));
+    }
+    
+    // Check if template --save/--delete/--rename/--export/--import is present - it must be used alone
+    if let Some(subcmd) = subcommands.iter().find(|s| {
+        s.name == "template" && TEMPLATE_EDIT_ACTIONS.iter().any(|f| has_flag(&s.flags, f))
+    }) {
+        if subcommands.len() > 1 {
+            return Err(FrencliError::Usage("'template --save', '--delete', '--rename', '--export' and '--import' cannot be used with other subcommands.\n\nExamples:\n  frencli template --save podcast \"%T%N_%C3.%E\"\n  frencli template --delete podcast\n  frencli template --rename podcast episode\n  frencli template --export team-templates.json".to_string()));
+        }
+        handle_template_edit(subcmd, template_registry, base_dir).map_err(FrencliError::Usage)?;
+        return Ok(Some(()));
+    }
+    
     // Check if undo is present - it must be used alone
     let has_undo = subcommands.iter().any(|s| s.name == "undo");
     if has_undo {
         if subcommands.len() > 1 {
-            return Err("'undo' cannot be used with other subcommands.\nThe 'undo' subcommand is very important and must be used alone.\n\nExamples:\n  frencli undo --check\n  frencli undo --apply\n  frencli undo --apply --yes".to_string());
+            return Err(FrencliError::Usage("'undo' cannot be used with other subcommands.\nThe 'undo' subcommand is very important and must be used alone.\n\nExamples:\n  frencli undo --check\n  frencli undo --apply\n  frencli undo --apply --yes".to_string()));
         }
         
         let undo_subcmd = subcommands.iter().find(|s| s.name == "undo").unwrap();
@@ -102,17 +242,17 @@ pub async fn handle_standalone_commands(
         let undo_yes = has_flag(&undo_subcmd.flags, "yes");
         
         if has_check && has_apply {
-            return Err("Cannot use both 'undo --check' and 'undo --apply' together.\nUse either:\n  - 'undo --check' to check what can be undone\n  - 'undo --apply' to actually perform the undo".to_string());
+            return Err(FrencliError::Usage("Cannot use both 'undo --check' and 'undo --apply' together.\nUse either:\n  - 'undo --check' to check what can be undone\n  - 'undo --apply' to actually perform the undo".to_string()));
         }
         
         if has_check {
-            handle_undo_check(engine).await;
+            handle_undo_check(engine, base_dir).await?;
             return Ok(Some(()));
         } else if has_apply {
-            handle_undo_apply(engine, undo_yes).await;
+            handle_undo_apply(engine, base_dir, undo_yes).await?;
             return Ok(Some(()));
         } else {
-            return Err("'undo' requires either '--check' or '--apply' flag.\nUse:\n  - 'undo --check' to check what can be undone\n  - 'undo --apply' to actually perform the undo".to_string());
+            return Err(FrencliError::Usage("'undo' requires either '--check' or '--apply' flag.\nUse:\n  - 'undo --check' to check what can be undone\n  - 'undo --apply' to actually perform the undo".to_string()));
         }
     }
     
@@ -120,7 +260,7 @@ pub async fn handle_standalone_commands(
     let has_audit = subcommands.iter().any(|s| s.name == "audit");
     if has_audit {
         if subcommands.len() > 1 {
-            return Err("'audit' cannot be used with other subcommands.\nThe 'audit' subcommand is standalone and must be used alone.\n\nExamples:\n  frencli audit\n  frencli audit --limit 10\n  frencli audit --json".to_string());
+            return Err(FrencliError::Usage("'audit' cannot be used with other subcommands.\nThe 'audit' subcommand is standalone and must be used alone.\n\nExamples:\n  frencli audit\n  frencli audit --limit 10\n  frencli audit --json".to_string()));
         }
         
         let audit_subcmd = subcommands.iter().find(|s| s.name == "audit").unwrap();
@@ -128,8 +268,38 @@ pub async fn handle_standalone_commands(
         let limit = limit_str.and_then(|s| s.parse::<usize>().ok());
         let json = has_flag(&audit_subcmd.flags, "json");
         
-        handle_audit_command(limit, json).await
-            .map_err(|e| format!("Error: {}", e))?;
+        handle_audit_command(base_dir, limit, json).await
+            .map_err(FrencliError::Io)?;
+        return Ok(Some(()));
+    }
+    
+    // Check if alias is present - it must be used alone
+    if let Some(subcmd) = subcommands.iter().find(|s| s.name == "alias") {
+        if subcommands.len() > 1 {
+            return Err(FrencliError::Usage("'alias' cannot be used with other subcommands. Put the subcommands to save after '--'.\n\nExample:\n  frencli alias --save photos -- list \"*.jpg\" rename \"%N_%C3.%E\"".to_string()));
+        }
+        let store_path = aliases_path()
+            .ok_or_else(|| FrencliError::Usage("Cannot determine the user config directory. Set XDG_CONFIG_HOME or HOME.".to_string()))?;
+        handle_alias_command(subcmd, &store_path).map_err(FrencliError::Usage)?;
+        return Ok(Some(()));
+    }
+    
+    // Check if completions is present - it must be used alone
+    if let Some(subcmd) = subcommands.iter().find(|s| s.name == "completions") {
+        if subcommands.len() > 1 {
+            return Err(FrencliError::Usage("'completions' cannot be used with other subcommands.\n\nExample:\n  frencli completions bash".to_string()));
+        }
+        handle_completions_command(subcmd, template_registry)
+            .map_err(FrencliError::Usage)?;
+        return Ok(Some(()));
+    }
+    
+    // Check if manpage is present - it must be used alone
+    if let Some(subcmd) = subcommands.iter().find(|s| s.name == "manpage") {
+        if subcommands.len() > 1 {
+            return Err(FrencliError::Usage("'manpage' cannot be used with other subcommands.\n\nExample:\n  frencli manpage --output man".to_string()));
+        }
+        handle_manpage_command(subcmd).map_err(FrencliError::Usage)?;
         return Ok(Some(()));
     }
     
@@ -137,11 +307,11 @@ pub async fn handle_standalone_commands(
     let has_interactive = subcommands.iter().any(|s| s.name == "interactive");
     if has_interactive {
         if subcommands.len() > 1 {
-            return Err("'interactive' cannot be used with other subcommands.\nThe 'interactive' subcommand is standalone and must be used alone.\n\nExample:\n  frencli interactive".to_string());
+            return Err(FrencliError::Usage("'interactive' cannot be used with other subcommands.\nThe 'interactive' subcommand is standalone and must be used alone.\n\nExample:\n  frencli interactive".to_string()));
         }
         
         handle_interactive_command().await
-            .map_err(|e| format!("Error: {}", e))?;
+            .map_err(FrencliError::Usage)?;
         return Ok(Some(()));
     }
     
@@ -159,12 +329,49 @@ pub fn validate_subcommand_combinations(subcommands: &[ParsedSubcommand]) -> Res
         return Err("Cannot use both 'rename' and 'template --use' in the same command.\nUse either:\n  - 'rename <PATTERN>' to specify a pattern directly\n  - 'template --use <NAME|NUMBER>' to use a template pattern".to_string());
     }
     
+    let has_template_try = subcommands.iter().any(|s| {
+        s.name == "template" && has_flag(&s.flags, "try")
+    });
+    if has_template_try && (has_rename || has_template_use
+        || subcommands.iter().any(|s| s.name == "validate" || s.name == "apply")) {
+        return Err("'template --try' only previews templates and can only be combined with 'list'.\nPick a template, then run it with 'template --use <NAME|NUMBER>'.\n\nExample:\n  frencli list \"*.jpg\" template --try".to_string());
+    }
+    
     Ok(())
 }
 
 /// Extracts configuration from parsed subcommands
 pub fn extract_config(subcommands: &[ParsedSubcommand]) -> Result<CommandConfig, String> {
-    let mut config = CommandConfig::default();
+    extract_config_with_defaults(subcommands, &Config::default())
+}
+
+/// Extracts configuration from parsed subcommands, starting from the config file defaults
+///
+/// Flags on the command line take precedence: each switch the config file can
+/// set has a negating flag (`--no-recursive`, `--no-overwrite`, `--audit`, ...),
+/// `--exclude` replaces the config file excludes and `--no-exclude` drops them.
+///
+/// # Arguments
+///
+/// * `subcommands` - The parsed subcommands
+/// * `defaults` - Default options from the config file
+///
+/// # Returns
+///
+/// * `Ok(CommandConfig)` - The configuration
+/// * `Err(String)` - If the subcommands are missing required arguments or combine flags incorrectly
+pub fn extract_config_with_defaults(subcommands: &[ParsedSubcommand], defaults: &Config) -> Result<CommandConfig, String> {
+    let mut config = CommandConfig {
+        list_recursive: defaults.list.recursive,
+        list_exclude: defaults.list.exclude.clone(),
+        list_fullpath: defaults.list.fullpath,
+        list_json: defaults.list.json,
+        rename_json: defaults.rename.json,
+        apply_overwrite: defaults.apply.overwrite,
+        apply_json: defaults.apply.json,
+        apply_no_audit: !defaults.apply.audit,
+        ..CommandConfig::default()
+    };
     
     for subcmd in subcommands {
         match subcmd.name.as_str() {
@@ -180,33 +387,74 @@ pub fn extract_config(subcommands: &[ParsedSubcommand]) -> Result<CommandConfig,
                     }
                     config.list_patterns = Some(patterns);
                 }
-                config.list_recursive = has_flag(&subcmd.flags, "recursive");
-                config.list_exclude = get_flag_values(&subcmd.flags, "exclude");
-                config.list_fullpath = has_flag(&subcmd.flags, "fullpath");
-                config.list_json = has_flag(&subcmd.flags, "json");
+                config.list_recursive = switch_setting(subcmd, "recursive", "no-recursive", config.list_recursive)?;
+                if has_flag(&subcmd.flags, "exclude") && has_flag(&subcmd.flags, "no-exclude") {
+                    return Err("Cannot use both '--exclude' and '--no-exclude'.".to_string());
+                }
+                if has_flag(&subcmd.flags, "exclude") {
+                    config.list_exclude = get_flag_values(&subcmd.flags, "exclude");
+                } else if has_flag(&subcmd.flags, "no-exclude") {
+                    config.list_exclude = Vec::new();
+                }
+                config.list_fullpath = switch_setting(subcmd, "fullpath", "no-fullpath", config.list_fullpath)?;
+                config.list_json = switch_setting(subcmd, "json", "no-json", config.list_json)?;
+                config.list_filters = extract_list_filters(subcmd)?;
             }
             "rename" => {
                 let pattern = subcmd.args.first().cloned().unwrap_or_default();
-                if pattern.is_empty() {
+                if pattern.is_empty() || subcmd.args.iter().any(|p| p.is_empty()) {
                     return Err("Rename pattern required.".to_string());
                 }
                 config.rename_pattern = Some(pattern);
-                config.rename_json = has_flag(&subcmd.flags, "json");
+                // Further patterns are applied in order to the result of the first
+                config.rename_chain = subcmd.args[1..].to_vec();
+                config.rename_json = switch_setting(subcmd, "json", "no-json", config.rename_json)?;
+                config.show_stages = config.show_stages || has_flag(&subcmd.flags, "stages");
             }
             "template" => {
                 let use_template = get_flag_value(&subcmd.flags, "use");
                 if let Some(name) = use_template {
                     config.template_use = Some(name);
                 }
+                if has_flag(&subcmd.flags, "stages") {
+                    if config.template_use.is_none() {
+                        return Err("'template --stages' requires '--use <NAME|NUMBER>'.".to_string());
+                    }
+                    config.show_stages = true;
+                }
+                config.template_set = parse_param_assignments(&get_flag_values(&subcmd.flags, "set"))?;
+                if !config.template_set.is_empty() && config.template_use.is_none() {
+                    return Err("'template --set' requires '--use <NAME|NUMBER>'.".to_string());
+                }
+                if has_flag(&subcmd.flags, "try") {
+                    config.template_try = Some(get_flag_values(&subcmd.flags, "try"));
+                    config.template_category = get_flag_value(&subcmd.flags, "category");
+                } else if has_flag(&subcmd.flags, "category") {
+                    return Err("'template --category' can only be used with 'template --list' or 'template --try'.".to_string());
+                }
+                if has_flag(&subcmd.flags, "json") {
+                    return Err("'template --json' can only be used with 'template --list'.".to_string());
+                }
+                if has_flag(&subcmd.flags, "no-json") {
+                    if config.template_use.is_none() {
+                        return Err("'template --no-json' requires '--use <NAME|NUMBER>'.".to_string());
+                    }
+                    config.rename_json = false;
+                }
             }
             "validate" => {
                 config.validate_skip_invalid = has_flag(&subcmd.flags, "skip-invalid");
             }
             "apply" => {
-                config.apply_overwrite = has_flag(&subcmd.flags, "overwrite");
+                config.apply_overwrite = switch_setting(subcmd, "overwrite", "no-overwrite", config.apply_overwrite)?;
                 config.apply_yes = has_flag(&subcmd.flags, "yes");
                 config.apply_interactive = has_flag(&subcmd.flags, "interactive");
-                config.apply_json = has_flag(&subcmd.flags, "json");
+                config.apply_json = switch_setting(subcmd, "json", "no-json", config.apply_json)?;
+                config.apply_no_audit = switch_setting(subcmd, "no-audit", "audit", config.apply_no_audit)?;
+                config.apply_dry_run = has_flag(&subcmd.flags, "dry-run");
+                if config.apply_dry_run && config.apply_interactive {
+                    return Err("Cannot use both 'apply --dry-run' and 'apply --interactive'.".to_string());
+                }
             }
             _ => {}
         }
@@ -215,45 +463,75 @@ pub fn extract_config(subcommands: &[ParsedSubcommand]) -> Result<CommandConfig,
     Ok(config)
 }
 
-/// Resolves template pattern from name or index
+/// Resolves template pattern from name or index, filling in template parameters
+/// 
+/// # Arguments
+/// 
+/// * `template_registry` - The template registry
+/// * `template_name` - Template name or 1-based index from `template --list`
+/// * `params` - Parameter values from `template --set KEY=VALUE`
+/// 
+/// # Returns
+/// 
+/// * `Ok(Vec<String>)` - The template's patterns (several for a chained template),
+///   ready to pass to the renaming engine
+/// * `Err(String)` - If the template is unknown or a parameter is unknown or missing
 pub fn resolve_template_pattern(
     template_registry: &TemplateRegistry,
     template_name: &str,
-) -> Result<String, String> {
-    if let Ok(index) = template_name.parse::<usize>() {
-        let templates = template_registry.list();
-        if index == 0 || index > templates.len() {
-            return Err(format!("Template index {} out of range (1-{})", index, templates.len()));
-        }
-        Ok(templates[index - 1].1.clone())
-    } else {
-        template_registry.get(template_name)
-            .cloned()
-            .ok_or_else(|| format!("Unknown template '{}'. Use 'template --list' to see all available templates.", template_name))
-    }
+    params: &[(String, String)],
+) -> Result<Vec<String>, String> {
+    let (name, template) = template_registry.find(template_name)?;
+    template.fill(params)
+        .map_err(|e| format!("Template '{}': {}", name, e))
 }
 
-/// Gets the audit pattern from rename pattern or template
+/// Gets the audit pattern from rename patterns or template
+///
+/// Chained patterns are joined with ` => `, in the order they were applied.
 pub fn get_audit_pattern(
-    rename_pattern: &Option<String>,
+    rename_patterns: &[String],
     template_use: &Option<String>,
+    template_params: &[(String, String)],
     template_registry: &TemplateRegistry,
 ) -> Option<String> {
-    rename_pattern.clone().or_else(|| {
+    let patterns = if rename_patterns.is_empty() {
         template_use.as_ref().and_then(|name| {
-            resolve_template_pattern(template_registry, name).ok()
-        })
-    })
+            resolve_template_pattern(template_registry, name, template_params).ok()
+        })?
+    } else {
+        rename_patterns.to_vec()
+    };
+    Some(patterns.join(" => "))
+}
+
+/// Error for `validate` or `apply` without a preview to work on
+const NO_PREVIEW: &str = "No preview available. 'rename' or 'template --use' subcommand is required to generate preview.";
+
+/// Builds the error for a step that needs files when there are none.
+///
+/// If `list` ran but selected nothing, the error is [`FrencliError::NoFilesMatched`];
+/// without a `list` subcommand it is a usage error with `usage` as the message.
+fn no_files_error(config: &CommandConfig, usage: &str) -> FrencliError {
+    match (&config.list_files_from, &config.list_patterns) {
+        (Some(files_from), _) => FrencliError::NoFilesMatched(format!("No files to process: '{}' lists no files.", files_from)),
+        (None, Some(patterns)) => FrencliError::NoFilesMatched(format!("No files matched {}.", patterns.join(" "))),
+        (None, None) => FrencliError::Usage(usage.to_string()),
+    }
 }
 
 /// Executes the command pipeline: list -> rename -> validate -> apply
+///
+/// Relative `list` patterns and `--files-from` paths are resolved against
+/// `base_dir`, and `apply` keeps its undo history and audit log there.
 pub async fn execute_command_pipeline(
     config: CommandConfig,
     subcommands: &[ParsedSubcommand],
     engine: &RenamingEngine,
     template_registry: &TemplateRegistry,
     full_command: String,
-) -> Result<(), String> {
+    base_dir: &Path,
+) -> Result<(), FrencliError> {
     // Step 1: Execute list to get files (if present)
     let mut files: Vec<PathBuf> = Vec::new();
     let mut preview_result: Option<freneng::EnginePreviewResult> = None;
@@ -261,11 +539,13 @@ pub async fn execute_command_pipeline(
     // Read files from --files-from if provided, otherwise use patterns
     if let Some(files_from) = &config.list_files_from {
         // Read files from file or stdin
-        files = read_files_from_source(files_from)
-            .map_err(|e| format!("Error reading files from {}: {}", files_from, e))?;
+        files = read_files_from_source(files_from, base_dir)
+            .map_err(|e| FrencliError::Io(format!("Error reading files from {}: {}", files_from, e)))?;
+        files = config.list_filters.retain(files).await;
+        verbose!("Read {} file(s) from {}", files.len(), files_from);
         
-        // Display files if rename/template --use/validate/apply is not present
-        if config.rename_pattern.is_none() && config.template_use.is_none() 
+        // Display files if rename/template --use/--try/validate/apply is not present
+        if config.rename_pattern.is_none() && config.template_use.is_none() && config.template_try.is_none()
             && !subcommands.iter().any(|s| s.name == "validate")
             && !subcommands.iter().any(|s| s.name == "apply") {
             if config.list_json {
@@ -275,11 +555,15 @@ pub async fn execute_command_pipeline(
             }
         }
     } else if let Some(patterns) = &config.list_patterns {
-        files = find_files(patterns, config.list_recursive, &config.list_exclude).await
-            .map_err(|e| format!("Error finding files: {}", e))?;
+        let patterns: Vec<String> = patterns.iter()
+            .map(|pattern| resolve_pattern(base_dir, pattern))
+            .collect();
+        let started = Instant::now();
+        files = find_files_filtered(&patterns, config.list_recursive, &config.list_exclude, &config.list_filters).await?;
+        verbose!("Found {} file(s) matching {} in {:.2?}", files.len(), patterns.join(" "), started.elapsed());
         
-        // Display files if rename/template --use/validate/apply is not present
-        if config.rename_pattern.is_none() && config.template_use.is_none() 
+        // Display files if rename/template --use/--try/validate/apply is not present
+        if config.rename_pattern.is_none() && config.template_use.is_none() && config.template_try.is_none()
             && !subcommands.iter().any(|s| s.name == "validate")
             && !subcommands.iter().any(|s| s.name == "apply") {
             if config.list_json {
@@ -290,58 +574,69 @@ pub async fn execute_command_pipeline(
         }
     }
     
+    // Template --try previews every selected template and stops there
+    if let Some(names) = &config.template_try {
+        if files.is_empty() {
+            return Err(no_files_error(&config, "'template --try' requires 'list' subcommand to select files."));
+        }
+        return handle_template_try(engine, template_registry, &files, names, config.template_category.as_deref()).await
+            .map_err(FrencliError::Usage);
+    }
+    
     // Step 2: Execute rename or template --use to generate preview (if present)
-    if let Some(pattern) = config.rename_pattern.clone() {
+    if config.rename_pattern.is_some() {
         if files.is_empty() {
-            return Err("No files to process. 'list' subcommand is required to select files.".to_string());
+            return Err(no_files_error(&config, "No files to process. 'list' subcommand is required to select files."));
         }
         
-        preview_result = Some(handle_rename_command(&engine, files.clone(), pattern, config.rename_json).await
-            .map_err(|e| format!("Error: {}", e))?);
+        preview_result = Some(handle_rename_chain_command(engine, files.clone(), config.rename_patterns(), config.show_stages, config.rename_json).await?);
     } else if let Some(template_name) = config.template_use.clone() {
         if files.is_empty() {
-            return Err("'template --use' requires 'list' subcommand to select files.".to_string());
+            return Err(no_files_error(&config, "'template --use' requires 'list' subcommand to select files."));
         }
         
-        let pattern = resolve_template_pattern(template_registry, &template_name)?;
-        preview_result = Some(handle_rename_command(&engine, files.clone(), pattern, config.rename_json).await
-            .map_err(|e| format!("Error: {}", e))?);
+        let patterns = resolve_template_pattern(template_registry, &template_name, &config.template_set)
+            .map_err(FrencliError::Usage)?;
+        verbose!("Template '{}' resolves to: {}", template_name, patterns.join(" => "));
+        preview_result = Some(handle_rename_chain_command(engine, files.clone(), patterns, config.show_stages, config.rename_json).await?);
     }
     
     // Step 3: Execute validate (if present)
     if subcommands.iter().any(|s| s.name == "validate") {
         let result = preview_result.as_ref()
-            .ok_or("No preview available. 'rename' or 'template --use' subcommand is required to generate preview.")?;
-        handle_validate_command(&engine, result, config.validate_skip_invalid).await;
+            .ok_or_else(|| FrencliError::Usage(NO_PREVIEW.to_string()))?;
+        handle_validate_command(engine, result, config.validate_skip_invalid).await?;
     }
     
     // Step 4: Execute apply (if present)
     if subcommands.iter().any(|s| s.name == "apply") {
         let result = preview_result.take()
-            .ok_or("No preview available. 'rename' or 'template --use' subcommand is required to generate preview.")?;
+            .ok_or_else(|| FrencliError::Usage(NO_PREVIEW.to_string()))?;
         
-        let enable_audit = !subcommands.iter()
-            .any(|s| s.name == "apply" && has_flag(&s.flags, "no-audit"));
+        if config.apply_dry_run {
+            handle_apply_dry_run(engine, &result, config.apply_overwrite, config.apply_json).await?;
+            return Ok(());
+        }
         
         let audit_pattern = get_audit_pattern(
-            &config.rename_pattern,
+            &config.rename_patterns(),
             &config.template_use,
+            &config.template_set,
             template_registry,
         );
         
         handle_apply_command(
+            base_dir,
             result, 
             config.apply_overwrite, 
             config.apply_yes, 
             config.apply_interactive,
             format!("frencli {}", full_command),
             audit_pattern,
-            enable_audit,
+            !config.apply_no_audit,
             config.apply_json,
-        ).await
-            .map_err(|e| format!("Error: {}", e))?;
+        ).await?;
     }
     
     Ok(())
 }
-
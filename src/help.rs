@@ -1,189 +1,126 @@
 //! Help text generation for help-probe compatibility.
 //! 
 //! All help output follows the help-probe specification for optimal parsing.
+//! The text is generated from the command spec in `spec.rs`, so it always
+//! matches what the parser accepts.
 
-/// Print main help message
-pub fn print_main_help() {
-    println!("Batch file renamer with pattern matching");
-    println!();
-    println!("Usage: frencli [OPTIONS] <SUBCOMMAND>...");
-    println!();
-    println!("SUBCOMMANDS:");
-    println!("    list        List files matching patterns");
-    println!("    rename      Generate rename preview using a pattern");
-    println!("    validate    Validate a rename pattern");
-    println!("    apply       Apply rename operations (performs the rename)");
-    println!("    template    Manage templates");
-    println!("    undo        Undo operations");
-    println!("    audit       View audit log");
-    println!("    interactive    Interactive workflow guide");
-    println!();
-    println!("OPTIONS:");
-    println!("    -h, --help          Print help");
-    println!("    -V, --version       Print version");
-    println!();
-    println!("Examples:");
-    println!("  frencli list *.txt");
-    println!("  frencli list *.txt rename \"%N_backup.%E\"");
-    println!("  frencli list *.txt rename \"%N_backup.%E\" apply --yes");
-}
+use std::fmt::Write;
+use crate::spec::{
+    FlagSpec, SubcommandSpec, ABOUT, GLOBAL_FLAGS, HELP_FLAG, MAIN_EXAMPLES, SUBCOMMANDS, TOP_LEVEL_OPTIONS, find_subcommand,
+};
 
-/// Print help for a specific subcommand
-pub fn print_subcommand_help(subcommand: &str) {
-    match subcommand {
-        "list" => print_list_help(),
-        "rename" => print_rename_help(),
-        "validate" => print_validate_help(),
-        "apply" => print_apply_help(),
-        "template" => print_template_help(),
-        "undo" => print_undo_help(),
-        "audit" => print_audit_help(),
-        "interactive" => print_interactive_help(),
-        _ => {
-            eprintln!("Unknown subcommand: {}", subcommand);
-            print_main_help();
-        }
+/// Builds the main help message
+pub fn main_help_text() -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{}\n", ABOUT);
+    out.push_str("Usage: frencli [OPTIONS] <SUBCOMMAND>...\n\n");
+
+    out.push_str("SUBCOMMANDS:\n");
+    let width = SUBCOMMANDS.iter().map(|spec| spec.name.len()).max().unwrap_or(0);
+    for spec in SUBCOMMANDS {
+        let _ = writeln!(out, "    {:<width$}    {}", spec.name, spec.summary, width = width);
     }
-}
 
-fn print_list_help() {
-    println!("List files matching patterns");
-    println!();
-    println!("Usage: frencli list [OPTIONS] <PATTERN>...");
-    println!("   or: frencli list [OPTIONS] --files-from <FILE>");
-    println!();
-    println!("Arguments:");
-    println!("    <PATTERN>...    Search patterns (glob patterns, e.g., \"*.txt\")");
-    println!();
-    println!("Options:");
-    println!("    --files-from <FILE>           Read file paths from FILE (one per line)");
-    println!("                                   Use \"-\" to read from stdin");
-    println!("    --recursive              Recursively search subdirectories (supports ** glob pattern)");
-    println!("    --exclude <EXCLUDE>...    Exclude files matching these patterns");
-    println!("    --fullpath                Display full paths instead of just filenames");
-    println!("    --json                    Output as JSON array");
-    println!("    --apply <RENAME_PATTERN>  Chain to apply command with this pattern");
-    println!("    --overwrite               Overwrite existing files (when using --apply)");
-    println!("    --yes                     Skip confirmation prompt (when using --apply)");
-    println!("    -h, --help                Print help");
-}
+    out.push_str("\nOPTIONS:\n");
+    let mut rows: Vec<(String, &[&str])> = GLOBAL_FLAGS.iter()
+        .map(|flag| (flag_label(flag), flag.help))
+        .collect();
+    for (name, help) in &TOP_LEVEL_OPTIONS {
+        rows.push((name.to_string(), std::slice::from_ref(help)));
+    }
+    write_rows(&mut out, &rows);
 
-fn print_apply_help() {
-    println!("Apply rename operations (performs the rename)");
-    println!();
-    println!("Operates on files from the last `list` command.");
-    println!("Run `frencli list` first to select files, then use `frencli rename` to generate a preview,");
-    println!("and finally `frencli apply` to perform the rename.");
-    println!();
-    println!("Usage: frencli apply [OPTIONS]");
-    println!();
-    println!("Options:");
-    println!("    --overwrite    Overwrite existing files");
-    println!("    --yes          Skip confirmation prompt");
-    println!("    --interactive  Interactive mode (edit filenames individually)");
-    println!("    --json         Output as JSON");
-    println!("    -h, --help     Print help");
+    out.push_str("\nExamples:\n");
+    for example in MAIN_EXAMPLES {
+        let _ = writeln!(out, "  {}", example);
+    }
+    out
 }
 
-fn print_validate_help() {
-    println!("Validate a rename pattern");
-    println!();
-    println!("Usage: frencli validate [OPTIONS] <PATTERN>...");
-    println!();
-    println!("Arguments:");
-    println!("    <PATTERN>...    Search patterns (glob patterns, e.g., \"*.txt\")");
-    println!();
-    println!("Options:");
-    println!("    -r, --recursive              Recursively search subdirectories (supports ** glob pattern)");
-    println!("    -e, --exclude <EXCLUDE>...    Exclude files matching these patterns");
-    println!("    --skip-invalid                Skip invalid files instead of aborting");
-    println!("    --change <TEMPLATE>            Renaming template");
-    println!("    --template <TEMPLATE_NAME>    Use a preset template pattern");
-    println!("    -h, --help                     Print help");
+/// Builds the help message for a subcommand
+/// 
+/// # Returns
+/// 
+/// * `Some(String)` - The help text
+/// * `None` - If `subcommand` is not a known subcommand
+pub fn subcommand_help_text(subcommand: &str) -> Option<String> {
+    find_subcommand(subcommand).map(render_subcommand_help)
 }
 
-fn print_rename_help() {
-    println!("Generate rename preview using a pattern");
-    println!();
-    println!("Generates a preview of file names using a pattern without applying the rename.");
-    println!("Operates on files from the last `list` command.");
-    println!("Use `frencli apply` to actually perform the rename.");
-    println!();
-    println!("Usage: frencli rename [OPTIONS] <RENAME_PATTERN>");
-    println!();
-    println!("Arguments:");
-    println!("    <RENAME_PATTERN>    Pattern to generate new file names (e.g., \"%N.%E\", \"%N2-7.%E\")");
-    println!();
-    println!("Options:");
-    println!("    --json         Output as JSON");
-    println!("    -h, --help     Print help");
-}
+/// Renders the help for one subcommand spec
+fn render_subcommand_help(spec: &SubcommandSpec) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", spec.summary);
+    if !spec.description.is_empty() {
+        out.push('\n');
+        for line in spec.description {
+            let _ = writeln!(out, "{}", line);
+        }
+    }
+
+    out.push('\n');
+    let _ = writeln!(out, "Usage: frencli {}", spec.usage());
+    for alt in spec.alt_usage {
+        let _ = writeln!(out, "   or: frencli {}", alt);
+    }
 
-fn print_template_help() {
-    println!("Manage templates");
-    println!();
-    println!("Usage: frencli template [OPTIONS]");
-    println!();
-    println!("Options:");
-    println!("    --list        List available templates");
-    println!("    --use <NAME>   Use a template pattern");
-    println!("    -h, --help    Print help");
-    println!();
-    println!("Examples:");
-    println!("    frencli template --list");
-    println!("    frencli list *.txt template --use photo-date");
+    if !spec.args.is_empty() {
+        out.push_str("\nArguments:\n");
+        let rows: Vec<(String, &[&str])> = spec.args.iter()
+            .map(|arg| (arg.name.to_string(), arg.help))
+            .collect();
+        write_rows(&mut out, &rows);
+    }
+
+    out.push_str("\nOptions:\n");
+    let rows: Vec<(String, &[&str])> = spec.flags.iter()
+        .chain(std::iter::once(&HELP_FLAG))
+        .map(|flag| (flag_label(flag), flag.help))
+        .collect();
+    write_rows(&mut out, &rows);
+
+    if !spec.examples.is_empty() {
+        out.push_str("\nExamples:\n");
+        for example in spec.examples {
+            let _ = writeln!(out, "    {}", example);
+        }
+    }
+    out
 }
 
-fn print_undo_help() {
-    println!("Undo operations");
-    println!();
-    println!("Usage: frencli undo [OPTIONS]");
-    println!();
-    println!("Options:");
-    println!("    --check    Check undo status");
-    println!("    --apply    Apply undo");
-    println!("    --yes      Skip confirmation prompt (when using --apply)");
-    println!("    -h, --help  Print help");
-    println!();
-    println!("Examples:");
-    println!("    frencli undo --check");
-    println!("    frencli undo --apply");
-    println!("    frencli undo --apply --yes");
+/// Formats a flag as shown in help, e.g. `--use <NAME>`
+fn flag_label(flag: &FlagSpec) -> String {
+    if flag.value_name.is_empty() {
+        format!("--{}", flag.name)
+    } else {
+        format!("--{} {}", flag.name, flag.value_name)
+    }
 }
 
-fn print_audit_help() {
-    println!("View audit log");
-    println!();
-    println!("View audit log of rename operations.");
-    println!();
-    println!("Usage: frencli audit [OPTIONS]");
-    println!();
-    println!("Options:");
-    println!("    --limit <N>    Limit number of entries to show");
-    println!("    --json         Output in JSON format");
-    println!("    -h, --help     Print help");
-    println!();
-    println!("Examples:");
-    println!("    frencli audit");
-    println!("    frencli audit --limit 10");
-    println!("    frencli audit --json");
+/// Writes label/description rows with the descriptions aligned in one column
+fn write_rows(out: &mut String, rows: &[(String, &[&str])]) {
+    let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    for (label, help) in rows {
+        let mut lines = help.iter();
+        let _ = writeln!(out, "    {:<width$}    {}", label, lines.next().unwrap_or(&""), width = width);
+        for line in lines {
+            let _ = writeln!(out, "    {:<width$}    {}", "", line, width = width);
+        }
+    }
 }
 
-fn print_interactive_help() {
-    println!("Interactive workflow guide");
-    println!();
-    println!("Usage: frencli interactive [OPTIONS]");
-    println!();
-    println!("Guides you through the standard frencli workflow step by step:");
-    println!("  1. Select files to rename");
-    println!("  2. Define rename pattern");
-    println!("  3. Preview and validate");
-    println!("  4. Apply rename");
-    println!();
-    println!("Options:");
-    println!("    -h, --help     Print help");
-    println!();
-    println!("Examples:");
-    println!("    frencli interactive");
+/// Print main help message
+pub fn print_main_help() {
+    print!("{}", main_help_text());
 }
 
+/// Print help for a specific subcommand
+pub fn print_subcommand_help(subcommand: &str) {
+    match subcommand_help_text(subcommand) {
+        Some(text) => print!("{}", text),
+        None => {
+            eprintln!("Unknown subcommand: {}", subcommand);
+            print_main_help();
+        }
+    }
+}
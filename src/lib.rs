@@ -1,16 +1,31 @@
-//! Fren CLI library - exposes modules for testing
+//! Fren CLI library - exposes modules for testing, and the [`pipeline::Pipeline`]
+//! builder for running frencli's rename pipeline from other programs
 
+#[macro_use]
+pub mod output;
 pub mod list;
 pub mod rename;
 pub mod apply;
 pub mod template;
 pub mod templates;
+pub mod template_store;
+pub mod config;
+pub mod error;
+pub mod alias;
+pub mod lint;
+pub mod suggest;
+pub mod spec;
+pub mod completions;
+pub mod manpage;
 pub mod ui;
 pub mod validate;
 pub mod undo;
 pub mod audit;
+pub mod workdir;
+pub mod style;
 pub mod interactive;
 pub mod subcommands;
 pub mod executor;
+pub mod pipeline;
 pub mod help;
 
@@ -0,0 +1,188 @@
+//! Static checks for rename patterns and templates.
+//!
+//! The renaming engine silently keeps unknown tokens and ignores a `%R`/`%X`
+//! modifier it cannot parse, so a broken template only shows up as odd file
+//! names. This module finds those mistakes up front: unknown `%` tokens,
+//! malformed `%R`/`%X` modifiers, invalid regular expressions, patterns
+//! that drop the file extension, and template braces that are not parameters.
+
+use regex::Regex;
+use crate::templates::{Template, is_param_name};
+
+/// Placeholder letters understood by the renaming engine
+const PLACEHOLDERS: &str = "CNEFPDH";
+
+/// Modifier letters understood by the renaming engine
+const MODIFIERS: &str = "LUTRMX";
+
+/// Delimiters accepted by `%R` and `%X`
+const REPLACE_DELIMITERS: [char; 5] = ['/', '|', ':', ',', '@'];
+
+/// Checks a single rename pattern.
+///
+/// # Arguments
+///
+/// * `pattern` - The rename pattern (without `{name}` template parameters)
+///
+/// # Returns
+///
+/// * `Vec<String>` - One message per problem found; empty if the pattern looks fine
+pub fn lint_pattern(pattern: &str) -> Vec<String> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut problems = Vec::new();
+    let mut keeps_extension = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] != '%' {
+            i += 1;
+            continue;
+        }
+        let Some(&next) = chars.get(i + 1) else {
+            problems.push("'%' at the end of the pattern is not a token".to_string());
+            break;
+        };
+
+        match next.to_ascii_uppercase() {
+            'F' if chars.get(i + 2).is_some_and(|c| matches!(c.to_ascii_uppercase(), 'D' | 'H')) => {
+                i += 3;
+            }
+            'E' | 'F' => {
+                keeps_extension = true;
+                i += 2;
+            }
+            token @ ('R' | 'X') => {
+                i = lint_replacement(&chars, i, token, &mut problems);
+            }
+            token if PLACEHOLDERS.contains(token) || MODIFIERS.contains(token) => {
+                i += 2;
+            }
+            _ if next.is_alphabetic() => {
+                problems.push(format!("unknown token '%{}'", next));
+                i += 2;
+            }
+            // A '%' before anything else is kept as a literal character
+            _ => i += 1,
+        }
+    }
+
+    if !keeps_extension {
+        problems.push("pattern drops the file extension (it has no %E or %F)".to_string());
+    }
+    problems
+}
+
+/// Checks a `%R` or `%X` modifier starting at `start` and returns the index after it.
+fn lint_replacement(chars: &[char], start: usize, token: char, problems: &mut Vec<String>) -> usize {
+    let delimiter = match chars.get(start + 2) {
+        Some(c) if REPLACE_DELIMITERS.contains(c) => *c,
+        _ => {
+            problems.push(format!(
+                "%{} must be followed by a delimiter (/ | : , @), e.g. %{}/old/new",
+                token, token
+            ));
+            return start + 2;
+        }
+    };
+
+    let search_start = start + 3;
+    let Some(search_end) = chars[search_start.min(chars.len())..].iter()
+        .position(|c| *c == delimiter)
+        .map(|offset| search_start + offset)
+    else {
+        problems.push(format!(
+            "%{}{}: missing the '{}' that ends the search text (expected %{}{}old{}new)",
+            token, delimiter, delimiter, token, delimiter, delimiter
+        ));
+        return chars.len();
+    };
+
+    let search: String = chars[search_start..search_end].iter().collect();
+    if search.is_empty() {
+        problems.push(format!("%{}{}{}: search text is empty", token, delimiter, delimiter));
+    } else if token == 'X'
+        && let Err(e) = Regex::new(&search) {
+        let reason = e.to_string().lines().last().unwrap_or_default().trim().to_string();
+        problems.push(format!("invalid regular expression '{}' in %X: {}", search, reason));
+    }
+
+    // The replacement text runs until the extension dot or the next token
+    let mut end = search_end + 1;
+    while end < chars.len() && chars[end] != '.' && chars[end] != '%' {
+        end += 1;
+    }
+    end
+}
+
+/// Checks a template pattern for `{...}` groups that are not parameters.
+///
+/// Such braces are kept literally, so `{my-prefix}` silently ends up in the
+/// file name instead of being offered to `--set`. Regex repetitions like
+/// `{4}` or `{2,3}` and backslash-escaped braces are left alone.
+fn lint_braces(pattern: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut rest = pattern;
+    let mut escaped = false;
+
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            rest = &rest[2..];
+            escaped = false;
+            continue;
+        }
+        if c == '{' && !escaped {
+            match rest[1..].split_once('}') {
+                Some((name, _)) if is_param_name(name) => {}
+                Some((name, _)) if !name.is_empty() && name.chars().all(|c| c.is_ascii_digit() || c == ',') => {}
+                Some((name, _)) => problems.push(format!(
+                    "'{{{}}}' is not a parameter (names use letters, digits and underscores and cannot start with a digit); it is kept literally",
+                    name
+                )),
+                None => problems.push("'{' has no closing '}' and is kept literally (use '{{' for a literal brace)".to_string()),
+            }
+        }
+        escaped = c == '\\' && !escaped;
+        rest = &rest[c.len_utf8()..];
+    }
+    problems
+}
+
+/// Checks every pattern of a template.
+///
+/// Braces that are not parameters and defaults for parameters the patterns
+/// never use are reported first. Parameters are then filled with their
+/// defaults, or a placeholder value when they have none, before the patterns
+/// are checked. Problems in a chained template are prefixed with the number
+/// of the pattern they belong to.
+///
+/// # Returns
+///
+/// * `Vec<String>` - One message per problem found; empty if the template looks fine
+pub fn lint_template(template: &Template) -> Vec<String> {
+    let parameters = template.parameters();
+    let values: Vec<(String, String)> = parameters.iter()
+        .filter(|param| !template.defaults.contains_key(*param))
+        .map(|param| (param.clone(), "x".to_string()))
+        .collect();
+    let stages = match template.fill(&values) {
+        Ok(stages) => stages,
+        Err(e) => return vec![e],
+    };
+
+    let unused_defaults = template.defaults.keys()
+        .filter(|name| !parameters.contains(name))
+        .map(|name| format!("default for '{}' is never used: no pattern has a {{{}}} parameter", name, name));
+
+    let chained = stages.len() > 1;
+    let pattern_problems = template.patterns().into_iter().zip(&stages).enumerate()
+        .flat_map(|(i, (raw, pattern))| {
+            lint_braces(raw).into_iter().chain(lint_pattern(pattern)).map(move |problem| {
+                if chained {
+                    format!("pattern {}: {}", i + 1, problem)
+                } else {
+                    problem
+                }
+            })
+        });
+    unused_defaults.chain(pattern_problems).collect()
+}
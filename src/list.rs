@@ -1,11 +1,273 @@
 //! List subcommand for finding and displaying matching files.
 //! 
 //! This module handles the `fren list` command which searches for files
-//! matching given patterns, optionally recursively, and with exclusion support.
+//! matching given patterns, optionally recursively, with exclusion support and
+//! filters on size, modification time and file type.
 //! All operations are async to match the async API of freneng.
 
-use std::path::PathBuf;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
 use freneng::{find_matching_files_recursive, FrenError};
+use glob::MatchOptions;
+use tokio::fs;
+use crate::error::FrencliError;
+use crate::workdir::resolve_pattern;
+
+/// Kind of directory entry selected by `list --type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+    /// Regular files (not symbolic links)
+    File,
+    /// Directories
+    Dir,
+    /// Symbolic links
+    Symlink,
+}
+
+impl FileType {
+    /// Parses the value of `--type`.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "file" => Ok(FileType::File),
+            "dir" => Ok(FileType::Dir),
+            "symlink" => Ok(FileType::Symlink),
+            _ => Err(format!("Invalid --type value '{}'. Use file, dir or symlink.", value)),
+        }
+    }
+}
+
+/// Metadata filters for `list`: `--min-size`, `--max-size`, `--newer`, `--older` and `--type`.
+///
+/// Sizes and times are checked against the entry itself, not the target of a
+/// symbolic link. The default filters select everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListFilters {
+    /// Smallest size in bytes, inclusive
+    pub min_size: Option<u64>,
+    /// Largest size in bytes, inclusive
+    pub max_size: Option<u64>,
+    /// Only entries modified after this time
+    pub newer: Option<SystemTime>,
+    /// Only entries modified before this time
+    pub older: Option<SystemTime>,
+    /// Only entries of this kind; `None` lists files and links to files, as without filters
+    pub file_type: Option<FileType>,
+}
+
+impl ListFilters {
+    /// Returns true if no filter is set.
+    pub fn is_empty(&self) -> bool {
+        *self == ListFilters::default()
+    }
+
+    /// Returns true if an entry with this metadata (from `symlink_metadata`) passes the filters.
+    pub fn matches(&self, metadata: &Metadata) -> bool {
+        let file_type = metadata.file_type();
+        let type_matches = match self.file_type {
+            Some(FileType::File) => file_type.is_file(),
+            Some(FileType::Dir) => file_type.is_dir(),
+            Some(FileType::Symlink) => file_type.is_symlink(),
+            None => true,
+        };
+        if !type_matches
+            || self.min_size.is_some_and(|min| metadata.len() < min)
+            || self.max_size.is_some_and(|max| metadata.len() > max) {
+            return false;
+        }
+        if self.newer.is_none() && self.older.is_none() {
+            return true;
+        }
+        let Ok(modified) = metadata.modified() else {
+            return false;
+        };
+        self.newer.is_none_or(|newer| modified > newer)
+            && self.older.is_none_or(|older| modified < older)
+    }
+
+    /// Returns true if a found entry is listed: it passes the filters and, without
+    /// `--type`, is a file or a link to a file, as in an unfiltered listing.
+    async fn selects(&self, path: &Path, metadata: &Metadata) -> bool {
+        if !self.matches(metadata) {
+            return false;
+        }
+        self.file_type.is_some() || fs::metadata(path).await.is_ok_and(|target| target.is_file())
+    }
+
+    /// Keeps the paths that pass the filters; paths that cannot be read are dropped.
+    pub async fn retain(&self, paths: Vec<PathBuf>) -> Vec<PathBuf> {
+        if self.is_empty() {
+            return paths;
+        }
+        let mut kept = Vec::with_capacity(paths.len());
+        for path in paths {
+            if let Ok(metadata) = fs::symlink_metadata(&path).await
+                && self.matches(&metadata) {
+                kept.push(path);
+            }
+        }
+        kept
+    }
+}
+
+/// Parses a size for `--min-size`/`--max-size`: bytes, or a number with a unit.
+///
+/// `K`, `M`, `G` and `T` (optionally followed by `B`) are powers of 1000, and
+/// `KiB`, `MiB`, `GiB` and `TiB` are powers of 1024. Units are case-insensitive,
+/// so `2MB`, `2mb` and `2M` are all 2,000,000 bytes.
+///
+/// # Returns
+///
+/// * `Ok(u64)` - The size in bytes
+/// * `Err(String)` - If the number or unit is invalid
+pub fn parse_size(value: &str) -> Result<u64, String> {
+    let invalid = || format!("Invalid size '{}'. Use bytes or a number with a unit, e.g. 500K, 2MB or 1GiB.", value);
+    let trimmed = value.trim();
+    let split = trimmed.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(split);
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1_000,
+        "m" | "mb" => 1_000_000,
+        "g" | "gb" => 1_000_000_000,
+        "t" | "tb" => 1_000_000_000_000,
+        "ki" | "kib" => 1 << 10,
+        "mi" | "mib" => 1 << 20,
+        "gi" | "gib" => 1 << 30,
+        "ti" | "tib" => 1 << 40,
+        _ => return Err(invalid()),
+    };
+    Ok((number * multiplier as f64).round() as u64)
+}
+
+/// Parses a time for `--newer`/`--older`: a date, a date and time, or a duration before `now`.
+///
+/// Dates are `YYYY-MM-DD` (midnight) or `YYYY-MM-DD HH:MM[:SS]` (also with a
+/// `T` separator) in local time. Durations are a number followed by `s`, `m`,
+/// `h`, `d` or `w`, e.g. `7d` is seven days before `now`.
+///
+/// # Returns
+///
+/// * `Ok(SystemTime)` - The point in time
+/// * `Err(String)` - If the value is neither a date nor a duration
+pub fn parse_time(value: &str, now: SystemTime) -> Result<SystemTime, String> {
+    let value = value.trim();
+    let invalid = || format!("Invalid time '{}'. Use a date (2026-01-31, \"2026-01-31 18:00\") or a duration (30m, 12h, 7d, 2w).", value);
+
+    if let Some(unit) = value.chars().last()
+        && let Ok(count) = value[..value.len() - unit.len_utf8()].parse::<u64>() {
+        let seconds = match unit {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            'w' => 7 * 24 * 60 * 60,
+            _ => return Err(invalid()),
+        };
+        return now.checked_sub(Duration::from_secs(count.saturating_mul(seconds))).ok_or_else(invalid);
+    }
+
+    let datetime = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"].iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok().and_then(|date| date.and_hms_opt(0, 0, 0)))
+        .ok_or_else(invalid)?;
+    Local.from_local_datetime(&datetime).earliest()
+        .map(SystemTime::from)
+        .ok_or_else(invalid)
+}
+
+/// Returns true if a pattern component contains glob characters.
+fn has_glob_chars(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', ']'])
+}
+
+/// Returns true if the file name of `path` starts with a dot.
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|s| s.starts_with('.'))
+}
+
+/// Finds the entries matching a pattern that pass `filters`, for a filtered `list`.
+///
+/// freneng's finder only returns files and has no hook for filters, so every
+/// filtered listing (whatever its `--type`) uses this walker, which checks each
+/// entry against the filters as it is visited. Patterns are matched the way
+/// freneng matches them: a literal path is taken as is (a directory with
+/// `recursive` becomes `DIR/**/*`), the glob is matched against the path
+/// relative to the directory before its first wildcard, `recursive` adds a
+/// leading `**/`, and hidden entries are only matched by patterns that name
+/// them with a leading dot. Symbolic links to directories are not followed.
+async fn find_matching_entries(
+    pattern: &str,
+    recursive: bool,
+    filters: &ListFilters,
+) -> Result<Vec<PathBuf>, FrenError> {
+    let mut pattern = pattern.to_string();
+    if !has_glob_chars(&pattern) {
+        let path = PathBuf::from(&pattern);
+        match fs::symlink_metadata(&path).await {
+            Ok(metadata) if metadata.is_dir() && recursive => pattern = format!("{}/**/*", pattern),
+            Ok(metadata) => {
+                let selected = filters.selects(&path, &metadata).await;
+                return Ok(if selected { vec![path] } else { Vec::new() });
+            }
+            // Not an existing path: it matches nothing, as with the engine
+            Err(_) => return Ok(Vec::new()),
+        }
+    }
+
+    let include_hidden = pattern.starts_with('.') || pattern.contains("/.");
+    let descend = recursive || pattern.contains("**");
+
+    // The base directory is everything before the first component with a wildcard
+    let components: Vec<&str> = pattern.split('/').collect();
+    let split = components.iter().position(|c| has_glob_chars(c)).unwrap_or(components.len() - 1);
+    let base = components[..split].join("/");
+    let mut relative = components[split..].join("/");
+    let base = match base.as_str() {
+        "" if pattern.starts_with('/') => PathBuf::from("/"),
+        "" => PathBuf::from("."),
+        base => PathBuf::from(base),
+    };
+    if recursive && !relative.contains("**") {
+        relative = format!("**/{}", relative);
+    }
+    let matcher = glob::Pattern::new(&relative)
+        .map_err(|e| FrenError::Pattern(format!("Invalid pattern: {}", e)))?;
+    let options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::new()
+    };
+
+    let base = fs::canonicalize(&base).await.unwrap_or(base);
+    let mut matches = Vec::new();
+    let mut dirs_to_search = vec![base.clone()];
+    while let Some(dir) = dirs_to_search.pop() {
+        let Ok(mut entries) = fs::read_dir(&dir).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            let Ok(metadata) = fs::symlink_metadata(&path).await else {
+                continue;
+            };
+            if is_hidden(&path) && !include_hidden {
+                continue;
+            }
+            let relative_path = path.strip_prefix(&base).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+            if matcher.matches_with(&relative_path, options) && filters.selects(&path, &metadata).await {
+                matches.push(path);
+            }
+            if descend && metadata.is_dir() {
+                dirs_to_search.push(entry.path());
+            }
+        }
+    }
+    Ok(matches)
+}
 
 /// Finds files matching the given patterns, with optional recursion and exclusions.
 /// 
@@ -23,6 +285,21 @@ pub async fn find_files(
     patterns: &[String],
     recursive: bool,
     exclude: &[String],
+) -> Result<Vec<PathBuf>, FrenError> {
+    find_files_filtered(patterns, recursive, exclude, &ListFilters::default()).await
+}
+
+/// Like [`find_files`], but only keeps the entries that pass `filters`.
+///
+/// Without filters, files are found by freneng. With filters, every pattern
+/// is walked by the same finder, which applies the filters during the walk
+/// (see `find_matching_entries`), so `--type file`, `dir` and `symlink` match
+/// patterns the same way. Duplicates and exclusions are removed afterwards.
+pub async fn find_files_filtered(
+    patterns: &[String],
+    recursive: bool,
+    exclude: &[String],
+    filters: &ListFilters,
 ) -> Result<Vec<PathBuf>, FrenError> {
     let mut all_files = Vec::new();
     let mut seen = std::collections::HashSet::new();
@@ -31,7 +308,11 @@ pub async fn find_files(
     // The engine now handles both glob patterns and literal file paths automatically,
     // but we keep this structure for clarity and potential future CLI-specific handling
     for pat in patterns {
-        let files = find_matching_files_recursive(pat, recursive).await?;
+        let files = if filters.is_empty() {
+            find_matching_files_recursive(pat, recursive).await?
+        } else {
+            find_matching_entries(pat, recursive, filters).await?
+        };
         
         // Add files, avoiding duplicates
         for file in files {
@@ -64,10 +345,9 @@ pub async fn find_files(
                         // Check each directory component in the path
                         if let Some(parent) = path.parent() {
                             for component in parent.components() {
-                                if let Some(comp_str) = component.as_os_str().to_str() {
-                                    if glob_pattern.matches(comp_str) {
-                                        return true;
-                                    }
+                                if let Some(comp_str) = component.as_os_str().to_str()
+                                    && glob_pattern.matches(comp_str) {
+                                    return true;
                                 }
                             }
                         }
@@ -81,14 +361,12 @@ pub async fn find_files(
                 let is_directory_pattern = excl_pattern.contains('/') 
                     || excl_pattern.starts_with("**")
                     || excl_pattern.chars().any(|c| c.is_uppercase());
-                if is_directory_pattern {
-                    if let Some(parent) = path.parent() {
-                        for component in parent.components() {
-                            if let Some(comp_str) = component.as_os_str().to_str() {
-                                if comp_str.contains(excl_pattern) {
-                                    return true;
-                                }
-                            }
+                if is_directory_pattern
+                    && let Some(parent) = path.parent() {
+                    for component in parent.components() {
+                        if let Some(comp_str) = component.as_os_str().to_str()
+                            && comp_str.contains(excl_pattern) {
+                            return true;
                         }
                     }
                 }
@@ -108,17 +386,17 @@ pub async fn find_files(
 /// * `fullpath` - If true, display full paths; if false, display just filenames
 pub fn display_files(files: &[PathBuf], fullpath: bool) {
     if files.is_empty() {
-        println!("No matching files found.");
+        info!("No matching files found.");
     } else {
-        println!("Found {} matching file(s):", files.len());
+        info!("Found {} matching file(s):", files.len());
         for file in files {
             if fullpath {
-                println!("  {}", file.display());
+                info!("  {}", file.display());
             } else {
                 let name = file.file_name()
                     .and_then(|n| n.to_str())
                     .unwrap_or("?");
-                println!("  {}", name);
+                info!("  {}", name);
             }
         }
     }
@@ -152,6 +430,7 @@ pub fn display_files_json(files: &[PathBuf], fullpath: bool) {
 /// 
 /// # Arguments
 /// 
+/// * `base_dir` - Directory relative patterns are resolved against
 /// * `patterns` - List of search patterns
 /// * `recursive` - Whether to search recursively
 /// * `exclude` - List of exclusion patterns
@@ -159,22 +438,26 @@ pub fn display_files_json(files: &[PathBuf], fullpath: bool) {
 /// 
 /// # Returns
 /// 
-/// * `Ok(())` - Command completed successfully
-/// * `Err(FrenError)` - If file finding fails
+/// * `Ok(Vec<PathBuf>)` - The files found, as displayed
+/// * `Err(FrencliError)` - [`FrencliError::Usage`] if no pattern is given, or the
+///   engine's error if file finding fails
 pub async fn handle_list_command(
+    base_dir: &Path,
     patterns: Vec<String>,
     recursive: bool,
     exclude: Vec<String>,
     fullpath: bool,
-) -> Result<(), FrenError> {
+) -> Result<Vec<PathBuf>, FrencliError> {
     if patterns.is_empty() {
-        eprintln!("Error: No search pattern provided.");
-        std::process::exit(1);
+        return Err(FrencliError::Usage("No search pattern provided.".to_string()));
     }
 
+    let patterns: Vec<String> = patterns.iter()
+        .map(|pattern| resolve_pattern(base_dir, pattern))
+        .collect();
     let files = find_files(&patterns, recursive, &exclude).await?;
     display_files(&files, fullpath);
     
-    Ok(())
+    Ok(files)
 }
 
@@ -1,9 +1,21 @@
+use std::path::PathBuf;
 use freneng::RenamingEngine;
 
+#[macro_use]
+pub mod output;
 mod ui;
-mod templates;
+pub mod templates;
+pub mod template_store;
+pub mod config;
+pub mod error;
+pub mod alias;
+pub mod lint;
+pub mod suggest;
+pub mod spec;
+pub mod completions;
+pub mod manpage;
 mod subcommands;
-mod template;
+pub mod template;
 mod help;
 mod executor;
 pub mod list;
@@ -12,10 +24,18 @@ pub mod apply;
 pub mod validate;
 pub mod undo;
 pub mod audit;
+pub mod workdir;
+pub mod style;
 pub mod interactive;
-use subcommands::{parse_multi_subcommand, has_flag};
-use executor::{handle_standalone_commands, validate_subcommand_combinations, extract_config, execute_command_pipeline};
+use subcommands::{parse_global_options, parse_multi_subcommand, has_flag, get_flag_value};
+use executor::{handle_standalone_commands, validate_subcommand_combinations, extract_config, extract_config_with_defaults, execute_command_pipeline};
 use templates::TemplateRegistry;
+use config::Config;
+use alias::expand_run_command;
+use error::FrencliError;
+use workdir::check_base_dir;
+use style::ColorChoice;
+use output::Verbosity;
 
 /// Print version information
 fn print_version() {
@@ -24,20 +44,38 @@ fn print_version() {
 
 #[tokio::main]
 async fn main() {
-    let raw_args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run().await {
+        eprintln!("{} {}", style::error("Error:"), e);
+        std::process::exit(e.exit_code());
+    }
+}
+
+/// Runs frencli with the process arguments
+async fn run() -> Result<(), FrencliError> {
+    // Global options (--config, --no-config, --chdir, --color, --ascii, --quiet, --verbose) come before everything else
+    let (global_flags, raw_args) = parse_global_options(std::env::args().skip(1).collect())
+        .map_err(FrencliError::Usage)?;
+    let verbosity = Verbosity::from_flags(has_flag(&global_flags, "quiet"), has_flag(&global_flags, "verbose"))
+        .map_err(FrencliError::Usage)?;
+    output::set_verbosity(verbosity);
+    let color = match get_flag_value(&global_flags, "color") {
+        Some(value) => ColorChoice::parse(&value).map_err(FrencliError::Usage)?,
+        None => ColorChoice::Auto,
+    };
+    style::init(color, has_flag(&global_flags, "ascii"));
     
     // Handle top-level flags before parsing subcommands
-    // Only check if --help/--version is the first argument (top-level)
+    // Only check if --help/--version is the first argument after the global options
     // NO SHORT FLAGS - only long forms are supported
     if !raw_args.is_empty() {
         let first_arg = &raw_args[0];
         if first_arg == "--version" {
             print_version();
-            return;
+            return Ok(());
         }
         if first_arg == "--help" {
             help::print_main_help();
-            return;
+            return Ok(());
         }
         // Only --<something> is interpreted as flags at top level
         // Single dash arguments are not flags, they would be subcommands or positional args
@@ -48,12 +86,15 @@ async fn main() {
     let full_command = std::env::args().skip(1).collect::<Vec<String>>().join(" ");
     
     // Parse subcommands
-    let subcommands = parse_multi_subcommand(raw_args);
+    let subcommands = parse_multi_subcommand(raw_args).map_err(FrencliError::Usage)?;
+    for warning in subcommands.iter().flat_map(|s| &s.warnings) {
+        warning!("{}", style::warning(&format!("Warning: {}", warning)));
+    }
     
     if subcommands.is_empty() {
         // No subcommands - show help
         help::print_main_help();
-        return;
+        return Ok(());
     }
     
     // Check for --help flags in subcommands
@@ -65,60 +106,71 @@ async fn main() {
     if !help_subcommands.is_empty() {
         // If multiple subcommands present with --help, error
         if subcommands.len() > 1 {
-            eprintln!("Error: '--help' cannot be used with multiple subcommands.");
-            eprintln!("Use '--help' with a single subcommand, e.g.:");
-            eprintln!("  frencli list --help");
-            eprintln!("  frencli rename --help");
-            eprintln!("  frencli validate --help");
-            std::process::exit(1);
+            return Err(FrencliError::Usage("'--help' cannot be used with multiple subcommands.\n\
+                Use '--help' with a single subcommand, e.g.:\n\
+                \x20 frencli list --help\n\
+                \x20 frencli rename --help\n\
+                \x20 frencli validate --help".to_string()));
         }
         
         // Show help for the single subcommand
         let subcmd_name = help_subcommands[0].name.as_str();
         help::print_subcommand_help(subcmd_name);
-        return;
+        return Ok(());
     }
-    
+
+    // Replace 'run NAME' with the subcommands saved in the alias
+    let subcommands = expand_run_command(subcommands).map_err(FrencliError::Usage)?;
+
+    // Relative paths, the undo history and the audit log are relative to --chdir
+    let base_dir = match get_flag_value(&global_flags, "chdir") {
+        Some(dir) => check_base_dir(&dir).map_err(FrencliError::Usage)?,
+        None => PathBuf::new(),
+    };
+    verbose!("Working directory: {}", workdir::absolute_dir(&base_dir).display());
+
     let engine = RenamingEngine;
-    let template_registry = TemplateRegistry::new();
+    let template_registry = TemplateRegistry::load_in(&base_dir);
+    // 'template --check' reports the same problems in full, and completion
+    // scripts call 'completions --template-names' on every key press
+    let quiet_template_load = subcommands.iter()
+        .any(|s| (s.name == "template" && has_flag(&s.flags, "check")) || s.name == "completions");
+    if !quiet_template_load {
+        template::warn_template_problems(&template_registry);
+    }
     
     // Handle standalone commands (undo, audit, interactive, template --list)
-    match handle_standalone_commands(&subcommands, &engine, &template_registry).await {
-        Ok(Some(_)) => return, // Command executed and completed
-        Ok(None) => {}, // No standalone command, continue
-        Err(e) => {
-            eprintln!("{}", e);
-            std::process::exit(1);
-        }
+    if handle_standalone_commands(&subcommands, &engine, &template_registry, &base_dir).await?.is_some() {
+        return Ok(());
     }
     
     // Validate subcommand combinations
-    if let Err(e) = validate_subcommand_combinations(&subcommands) {
-        eprintln!("Error: {}", e);
-        std::process::exit(1);
-    }
+    validate_subcommand_combinations(&subcommands).map_err(FrencliError::Usage)?;
     
-    // Extract configuration from subcommands
-    let config = match extract_config(&subcommands) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+    // Load defaults from the config file, unless --no-config is given
+    let defaults = if has_flag(&global_flags, "no-config") {
+        if has_flag(&global_flags, "config") {
+            return Err(FrencliError::Usage("Cannot use both '--config' and '--no-config'.".to_string()));
         }
+        verbose!("Ignoring the config file (--no-config)");
+        None
+    } else {
+        Config::load(get_flag_value(&global_flags, "config").as_deref()).map_err(FrencliError::Usage)?
     };
     
+    // Extract configuration from subcommands, on top of the config file defaults
+    let config = match defaults {
+        Some(defaults) => extract_config_with_defaults(&subcommands, &defaults),
+        None => extract_config(&subcommands),
+    }.map_err(FrencliError::Usage)?;
+    
     // Execute command pipeline
-    if let Err(e) = execute_command_pipeline(
+    execute_command_pipeline(
         config,
         &subcommands,
         &engine,
         &template_registry,
         full_command,
-    ).await {
-        eprintln!("{}", e);
-        std::process::exit(1);
-    }
+        &base_dir,
+    ).await
 }
-
-
-
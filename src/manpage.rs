@@ -0,0 +1,224 @@
+//! Man page generation for the manpage subcommand.
+//!
+//! Pages are written in roff (the `man` macro package) from the same command
+//! spec as the `--help` output: one page for `frencli` itself, with the
+//! pattern token reference, and one page per subcommand.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use crate::spec::{
+    FlagSpec, SubcommandSpec, ABOUT, GLOBAL_FLAGS, HELP_FLAG, MAIN_EXAMPLES, PATTERN_MODIFIERS, PATTERN_PLACEHOLDERS,
+    PATTERN_SUBSTRINGS, SUBCOMMANDS, TOP_LEVEL_OPTIONS, find_subcommand,
+};
+use crate::subcommands::{ParsedSubcommand, get_flag_value};
+use crate::suggest::did_you_mean;
+
+/// Handles the manpage subcommand
+///
+/// # Arguments
+///
+/// * `subcmd` - The parsed `manpage` subcommand
+///
+/// # Returns
+///
+/// * `Ok(())` - The page was printed, or all pages were written
+/// * `Err(String)` - If the subcommand is unknown or the pages cannot be written
+pub fn handle_manpage_command(subcmd: &ParsedSubcommand) -> Result<(), String> {
+    if let Some(dir) = get_flag_value(&subcmd.flags, "output") {
+        if !subcmd.args.is_empty() {
+            return Err("'manpage --output' writes all pages and does not take a subcommand name.".to_string());
+        }
+        let written = write_manpages(Path::new(&dir))?;
+        println!("Wrote {} man page(s) to {}", written, dir);
+        return Ok(());
+    }
+    match subcmd.args.as_slice() {
+        [] => print!("{}", main_manpage()),
+        [name] => print!("{}", subcommand_manpage(name)?),
+        _ => return Err("'manpage' takes at most one subcommand name.".to_string()),
+    }
+    Ok(())
+}
+
+/// Writes `frencli.1` and `frencli-<SUBCOMMAND>.1` for every subcommand into `dir`
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of pages written
+/// * `Err(String)` - If the directory or a page cannot be written
+pub fn write_manpages(dir: &Path) -> Result<usize, String> {
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create directory '{}': {}", dir.display(), e))?;
+    let mut pages = vec![("frencli.1".to_string(), main_manpage())];
+    for spec in SUBCOMMANDS {
+        pages.push((format!("frencli-{}.1", spec.name), render_subcommand_page(spec)));
+    }
+    for (file_name, page) in &pages {
+        let path = dir.join(file_name);
+        fs::write(&path, page)
+            .map_err(|e| format!("Failed to write man page '{}': {}", path.display(), e))?;
+    }
+    Ok(pages.len())
+}
+
+/// Builds the `frencli(1)` man page
+pub fn main_manpage() -> String {
+    let mut out = header("frencli", ABOUT);
+    out.push_str(".SH SYNOPSIS\n");
+    out.push_str(".B frencli\n");
+    out.push_str("[\\fIOPTIONS\\fR] \\fISUBCOMMAND\\fR...\n");
+
+    out.push_str(".SH DESCRIPTION\n");
+    out.push_str("Several subcommands can be combined in one invocation, in any order.\n");
+    out.push_str("They always run in the order list, rename or template \\-\\-use, validate, apply.\n");
+    out.push_str("Each subcommand is described in its own page, e.g.\n");
+    out.push_str(".BR frencli\\-list (1).\n");
+
+    out.push_str(".SH SUBCOMMANDS\n");
+    for spec in SUBCOMMANDS {
+        let _ = writeln!(out, ".TP\n.B {}\n{}", escape(spec.name), escape(spec.summary));
+    }
+
+    out.push_str(".SH OPTIONS\n");
+    for flag in GLOBAL_FLAGS {
+        write_flag(&mut out, flag);
+    }
+    for (name, help) in TOP_LEVEL_OPTIONS {
+        let _ = writeln!(out, ".TP\n.B {}\n{}", escape(name), escape(help));
+    }
+
+    write_pattern_reference(&mut out);
+    write_examples(&mut out, &MAIN_EXAMPLES);
+
+    out.push_str(".SH SEE ALSO\n");
+    let pages: Vec<String> = SUBCOMMANDS.iter()
+        .map(|spec| format!(".BR frencli\\-{} (1)", escape(spec.name)))
+        .collect();
+    out.push_str(&pages.join(",\n"));
+    out.push('\n');
+    out
+}
+
+/// Builds the `frencli-<SUBCOMMAND>(1)` man page
+///
+/// # Returns
+///
+/// * `Ok(String)` - The page
+/// * `Err(String)` - If `name` is not a subcommand
+pub fn subcommand_manpage(name: &str) -> Result<String, String> {
+    find_subcommand(name)
+        .map(render_subcommand_page)
+        .ok_or_else(|| format!(
+            "Unknown subcommand '{}'.{}",
+            name,
+            did_you_mean(name, SUBCOMMANDS.iter().map(|spec| spec.name))
+        ))
+}
+
+fn render_subcommand_page(spec: &SubcommandSpec) -> String {
+    let mut out = header(&format!("frencli-{}", spec.name), spec.summary);
+
+    out.push_str(".SH SYNOPSIS\n");
+    let usages: Vec<String> = std::iter::once(spec.usage())
+        .chain(spec.alt_usage.iter().map(|usage| usage.to_string()))
+        .map(|usage| format!(".B frencli\n{}", escape(&usage)))
+        .collect();
+    out.push_str(&usages.join("\n.br\n"));
+    out.push('\n');
+
+    out.push_str(".SH DESCRIPTION\n");
+    let _ = writeln!(out, "{}.", escape(spec.summary));
+    if !spec.description.is_empty() {
+        out.push_str(".PP\n.nf\n");
+        for line in spec.description {
+            let _ = writeln!(out, "{}", escape(line));
+        }
+        out.push_str(".fi\n");
+    }
+
+    if !spec.args.is_empty() {
+        out.push_str(".SH ARGUMENTS\n");
+        for arg in spec.args {
+            let _ = writeln!(out, ".TP\n.I {}\n{}", escape(arg.name), escape(&arg.help.join(" ")));
+        }
+    }
+
+    out.push_str(".SH OPTIONS\n");
+    for flag in spec.flags.iter().chain(std::iter::once(&HELP_FLAG)) {
+        write_flag(&mut out, flag);
+    }
+
+    if spec.name == "rename" {
+        write_pattern_reference(&mut out);
+    }
+    write_examples(&mut out, spec.examples);
+
+    out.push_str(".SH SEE ALSO\n.BR frencli (1)\n");
+    out
+}
+
+/// Writes a flag as a tagged paragraph
+fn write_flag(out: &mut String, flag: &FlagSpec) {
+    out.push_str(".TP\n");
+    if flag.value_name.is_empty() {
+        let _ = writeln!(out, ".B \\-\\-{}", escape(flag.name));
+    } else {
+        let _ = writeln!(out, ".BI \\-\\-{} \" \" {}", escape(flag.name), escape(flag.value_name));
+    }
+    let _ = writeln!(out, "{}", escape(&flag.help.join(" ")));
+}
+
+/// Writes the title and NAME section
+fn header(page: &str, summary: &str) -> String {
+    format!(
+        ".TH {} 1 \"\" \"frencli {}\" \"User Commands\"\n.SH NAME\n{} \\- {}\n",
+        escape(&page.to_uppercase()),
+        env!("CARGO_PKG_VERSION"),
+        escape(page),
+        escape(summary)
+    )
+}
+
+/// Writes the PATTERNS section with every placeholder and modifier token
+fn write_pattern_reference(out: &mut String) {
+    out.push_str(".SH PATTERNS\n");
+    out.push_str("Patterns use % as a prefix for tokens. Tokens are case\\-insensitive.\n");
+    out.push_str(".SS Placeholders\n");
+    for (token, description, example) in PATTERN_PLACEHOLDERS {
+        let _ = writeln!(out, ".TP\n.B {}\n{} (e.g. {})", escape(token), escape(description), escape(example));
+    }
+    out.push_str(".SS Substring selection\n");
+    out.push_str("Parts of the name or extension can be selected with 1\\-based start\\-end indices;\n");
+    out.push_str("a double hyphen counts from the end.\n");
+    for (token, description) in PATTERN_SUBSTRINGS {
+        let _ = writeln!(out, ".TP\n.B {}\n{}", escape(token), escape(description));
+    }
+    out.push_str(".SS Modifiers\n");
+    out.push_str("Modifiers are processed left to right and apply to everything accumulated so far.\n");
+    for (token, description) in PATTERN_MODIFIERS {
+        let _ = writeln!(out, ".TP\n.B {}\n{}", escape(token), escape(description));
+    }
+}
+
+/// Writes the EXAMPLES section, if there are any
+fn write_examples(out: &mut String, examples: &[&str]) {
+    if examples.is_empty() {
+        return;
+    }
+    out.push_str(".SH EXAMPLES\n.nf\n");
+    for example in examples {
+        let _ = writeln!(out, "{}", escape(example));
+    }
+    out.push_str(".fi\n");
+}
+
+/// Escapes text for roff: backslashes and hyphens, and lines starting with a control character
+fn escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
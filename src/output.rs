@@ -0,0 +1,98 @@
+//! Output verbosity: the global `--quiet` and `--verbose` options.
+//!
+//! Handlers print through three macros instead of `println!`:
+//!
+//! - `info!` - messages and human-readable reports on stdout; hidden by `--quiet`
+//! - `warning!` - warnings on stderr; hidden by `--quiet`
+//! - `verbose!` - details on stderr (per-file decisions, timing, resolved
+//!   template patterns, config sources); shown only with `--verbose`
+//!
+//! Errors are always shown. Machine-readable output (`--json`, exported
+//! template bundles, completion scripts, man pages) and prompts are printed
+//! directly and are not affected by the verbosity.
+//!
+//! The verbosity is chosen once in `main` with [`set_verbosity`]; until then,
+//! and in library use, it is [`Verbosity::Normal`].
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// How much frencli prints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Errors only (`--quiet`)
+    Quiet = 0,
+    /// Messages, reports and warnings
+    Normal = 1,
+    /// Also per-file decisions, timing and where settings came from (`--verbose`)
+    Verbose = 2,
+}
+
+impl Verbosity {
+    /// Chooses the verbosity from the `--quiet` and `--verbose` options.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Verbosity)` - The chosen verbosity
+    /// * `Err(String)` - If both options are given
+    pub fn from_flags(quiet: bool, verbose: bool) -> Result<Self, String> {
+        match (quiet, verbose) {
+            (true, true) => Err("Cannot use both '--quiet' and '--verbose'.".to_string()),
+            (true, false) => Ok(Verbosity::Quiet),
+            (false, true) => Ok(Verbosity::Verbose),
+            (false, false) => Ok(Verbosity::Normal),
+        }
+    }
+}
+
+/// Sets the verbosity for the rest of the run.
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Returns the current verbosity.
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        _ => Verbosity::Verbose,
+    }
+}
+
+/// Returns true unless `--quiet` is given.
+pub fn shows_info() -> bool {
+    verbosity() >= Verbosity::Normal
+}
+
+/// Returns true if `--verbose` is given.
+pub fn is_verbose() -> bool {
+    verbosity() == Verbosity::Verbose
+}
+
+/// Prints a message or report line to stdout, unless `--quiet` is given.
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::output::shows_info() {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Prints a warning to stderr, unless `--quiet` is given.
+macro_rules! warning {
+    ($($arg:tt)*) => {
+        if $crate::output::shows_info() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Prints a detail to stderr, only with `--verbose`.
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        if $crate::output::is_verbose() {
+            eprintln!($($arg)*);
+        }
+    };
+}
@@ -0,0 +1,363 @@
+//! Embeddable pipeline API: select files, rename, validate and apply without printing.
+//!
+//! [`Pipeline`] runs the same steps as `frencli list ... rename ... validate apply`
+//! and returns structured results instead of writing to stdout, so other tools
+//! can reuse frencli's orchestration without shelling out:
+//!
+//! ```no_run
+//! use frencli::pipeline::Pipeline;
+//!
+//! # async fn example() -> Result<(), frencli::error::FrencliError> {
+//! let pipeline = Pipeline::new()
+//!     .patterns(["*.jpg"])
+//!     .rename("%N_%C3.%E");
+//!
+//! let preview = pipeline.preview().await?;
+//! for rename in &preview.result.renames {
+//!     println!("{} -> {}", rename.old_path.display(), rename.new_name);
+//! }
+//!
+//! let report = pipeline.apply().await?;
+//! println!("renamed {} file(s)", report.execution.successful.len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Each step runs the steps before it: [`Pipeline::apply`] selects the files,
+//! generates the preview and validates it before renaming anything.
+
+use std::path::PathBuf;
+use freneng::{perform_renames, EnginePreviewResult, FileRename, RenameExecutionResult, RenamingEngine, ValidationResult};
+use crate::apply::check_duplicate_targets;
+use crate::error::FrencliError;
+use crate::executor::resolve_template_pattern;
+use crate::list::{find_files_filtered, ListFilters};
+use crate::rename::{generate_chained_preview, PreviewStage};
+use crate::templates::TemplateRegistry;
+use crate::validate::validation_failed;
+use crate::workdir::{log_audit, resolve, resolve_pattern, save_history};
+
+/// How the pipeline selects the files to rename
+#[derive(Debug, Clone)]
+enum Selection {
+    /// Glob patterns or file paths, as given to `list`
+    Patterns(Vec<String>),
+    /// An explicit list of files, as given to `list --files-from`
+    Files(Vec<PathBuf>),
+}
+
+/// Where the pipeline's rename patterns come from
+#[derive(Debug, Clone)]
+enum Renaming {
+    /// Patterns applied in order, as given to `rename`
+    Patterns(Vec<String>),
+    /// A template name (or 1-based index) and its parameter values, as given to `template --use`
+    Template(String, Vec<(String, String)>),
+}
+
+/// The files selected by a pipeline and the names they would get.
+pub struct Preview {
+    /// The selected files, in the order they were found
+    pub files: Vec<PathBuf>,
+    /// The final name of each file, plus pattern warnings
+    pub result: EnginePreviewResult,
+    /// The names after each pattern of a chained rename
+    pub stages: Vec<PreviewStage>,
+}
+
+/// A preview and the result of validating it.
+pub struct Validation {
+    pub preview: Preview,
+    pub result: ValidationResult,
+}
+
+impl Validation {
+    /// Returns true if no file has a validation issue.
+    pub fn is_valid(&self) -> bool {
+        self.result.issues.is_empty()
+    }
+}
+
+/// The outcome of applying a pipeline.
+pub struct Execution {
+    pub validation: Validation,
+    /// Renamed, skipped and failed files
+    pub execution: RenameExecutionResult,
+}
+
+impl Execution {
+    /// Returns true if every rename that was attempted succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.execution.errors.is_empty()
+    }
+}
+
+/// Builder for a list -> rename -> validate -> apply pipeline.
+///
+/// Nothing is printed: every step returns its results, and errors are
+/// [`FrencliError`]s with the same kinds (and exit codes) as the command line.
+pub struct Pipeline {
+    base_dir: PathBuf,
+    selection: Option<Selection>,
+    recursive: bool,
+    exclude: Vec<String>,
+    filters: ListFilters,
+    renaming: Option<Renaming>,
+    templates: Option<TemplateRegistry>,
+    skip_invalid: bool,
+    overwrite: bool,
+    history: bool,
+    audit_command: Option<String>,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pipeline {
+    /// Creates an empty pipeline that saves rename history and does not write the audit log.
+    pub fn new() -> Self {
+        Pipeline {
+            base_dir: PathBuf::new(),
+            selection: None,
+            recursive: false,
+            exclude: Vec::new(),
+            filters: ListFilters::default(),
+            renaming: None,
+            templates: None,
+            skip_invalid: false,
+            overwrite: false,
+            history: true,
+            audit_command: None,
+        }
+    }
+
+    /// Works in `dir` instead of the current directory, like `--chdir DIR`.
+    ///
+    /// Relative patterns and files are resolved against `dir`, and the rename
+    /// history and audit log are written there.
+    pub fn base_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = dir.into();
+        self
+    }
+
+    /// Selects files by glob patterns or paths, like `list PATTERN...`.
+    pub fn patterns<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.selection = Some(Selection::Patterns(patterns.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Selects an explicit list of files, like `list --files-from`.
+    pub fn files<I, P>(mut self, files: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.selection = Some(Selection::Files(files.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Searches subdirectories when selecting files by pattern, like `list --recursive`.
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Excludes files matching a pattern, like `list --exclude`. Can be called repeatedly.
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Only selects entries that pass `filters`, like `list --min-size`, `--newer` or `--type`.
+    pub fn filters(mut self, filters: ListFilters) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    /// Adds a rename pattern, like `rename PATTERN`.
+    ///
+    /// Calling this again chains another pattern, applied to the names produced
+    /// by the previous one. Replaces a template set with [`Pipeline::template`].
+    pub fn rename(mut self, pattern: impl Into<String>) -> Self {
+        match &mut self.renaming {
+            Some(Renaming::Patterns(patterns)) => patterns.push(pattern.into()),
+            _ => self.renaming = Some(Renaming::Patterns(vec![pattern.into()])),
+        }
+        self
+    }
+
+    /// Renames with a template by name or 1-based index, like `template --use NAME`.
+    ///
+    /// Replaces any patterns set with [`Pipeline::rename`].
+    pub fn template(mut self, name: impl Into<String>) -> Self {
+        self.renaming = Some(Renaming::Template(name.into(), Vec::new()));
+        self
+    }
+
+    /// Sets a template parameter, like `template --set KEY=VALUE`.
+    ///
+    /// Has no effect unless [`Pipeline::template`] was called first.
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        if let Some(Renaming::Template(_, params)) = &mut self.renaming {
+            params.push((key.into(), value.into()));
+        }
+        self
+    }
+
+    /// Looks templates up in `registry` instead of the built-in templates only.
+    ///
+    /// Pass [`TemplateRegistry::load`]'s result to include user and project templates.
+    pub fn templates(mut self, registry: TemplateRegistry) -> Self {
+        self.templates = Some(registry);
+        self
+    }
+
+    /// Applies the valid renames even if others have issues, like `validate --skip-invalid`.
+    pub fn skip_invalid(mut self, skip_invalid: bool) -> Self {
+        self.skip_invalid = skip_invalid;
+        self
+    }
+
+    /// Replaces existing target files, like `apply --overwrite`.
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Saves the renames so `frencli undo` can reverse them (on by default).
+    pub fn history(mut self, history: bool) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Writes the renames to the audit log, recording `command` as the command that ran them.
+    pub fn audit(mut self, command: impl Into<String>) -> Self {
+        self.audit_command = Some(command.into());
+        self
+    }
+
+    /// Selects the files, like `list`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<PathBuf>)` - The selected files (may be empty)
+    /// * `Err(FrencliError)` - [`FrencliError::Usage`] if no files or patterns were
+    ///   given, or the engine's error if a pattern is invalid
+    pub async fn select_files(&self) -> Result<Vec<PathBuf>, FrencliError> {
+        match &self.selection {
+            Some(Selection::Patterns(patterns)) if !patterns.is_empty() => {
+                let patterns: Vec<String> = patterns.iter()
+                    .map(|pattern| resolve_pattern(&self.base_dir, pattern))
+                    .collect();
+                Ok(find_files_filtered(&patterns, self.recursive, &self.exclude, &self.filters).await?)
+            }
+            Some(Selection::Files(files)) => {
+                let files = files.iter().map(|file| resolve(&self.base_dir, file)).collect();
+                Ok(self.filters.retain(files).await)
+            }
+            _ => Err(FrencliError::Usage("No files selected. Call 'patterns' or 'files' to select files.".to_string())),
+        }
+    }
+
+    /// Selects the files and generates the new names, like `list ... rename`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Preview)` - The files and their new names
+    /// * `Err(FrencliError)` - [`FrencliError::NoFilesMatched`] if no files were
+    ///   selected, [`FrencliError::Usage`] if no pattern or template was given, the
+    ///   template is unknown, or a pattern is invalid
+    pub async fn preview(&self) -> Result<Preview, FrencliError> {
+        let patterns = self.rename_patterns()?;
+        let files = self.select_files().await?;
+        if files.is_empty() {
+            return Err(FrencliError::NoFilesMatched(match &self.selection {
+                Some(Selection::Patterns(patterns)) => format!("No files matched {}.", patterns.join(" ")),
+                _ => "No files to process.".to_string(),
+            }));
+        }
+
+        let (result, stages) = generate_chained_preview(&RenamingEngine, &files, &patterns).await?;
+        Ok(Preview { files, result, stages })
+    }
+
+    /// Generates the preview and validates it, like `list ... rename ... validate`.
+    ///
+    /// Issues are returned in the [`Validation`] rather than as an error, so the
+    /// caller can inspect them. Existing targets are issues unless
+    /// [`Pipeline::overwrite`] is set.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Validation)` - The preview and its validation result
+    /// * `Err(FrencliError)` - If the preview cannot be generated
+    pub async fn validate(&self) -> Result<Validation, FrencliError> {
+        let preview = self.preview().await?;
+        let result = RenamingEngine.validate(&preview.result.renames, self.overwrite).await;
+        Ok(Validation { preview, result })
+    }
+
+    /// Validates the preview and renames the files, like `list ... rename ... validate apply --yes`.
+    ///
+    /// With [`Pipeline::skip_invalid`], only the renames without issues are
+    /// performed. Renames that fail are reported in the [`Execution`], not as an error.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Execution)` - The validation and the renamed, skipped and failed files
+    /// * `Err(FrencliError)` - [`FrencliError::ValidationFailed`] if validation found
+    ///   issues and `skip_invalid` is not set, or if two files would get the same
+    ///   name; or the preview cannot be generated
+    pub async fn apply(&self) -> Result<Execution, FrencliError> {
+        let validation = self.validate().await?;
+        if !validation.is_valid() && !self.skip_invalid {
+            return Err(validation_failed(validation.result.issues.len()));
+        }
+
+        let renames: Vec<FileRename> = validation.result.valid.clone();
+        check_duplicate_targets(&renames)?;
+        let execution = perform_renames(&renames, self.overwrite).await?;
+
+        if self.history {
+            save_history(&self.base_dir, execution.successful.clone()).await
+                .map_err(|e| FrencliError::Io(format!("Failed to save rename history: {}", e)))?;
+        }
+        if let Some(command) = &self.audit_command {
+            log_audit(&self.base_dir, command, self.audit_pattern(), &execution).await
+                .map_err(|e| FrencliError::Io(format!("Failed to write audit log: {}", e)))?;
+        }
+
+        Ok(Execution { validation, execution })
+    }
+
+    /// Resolves the rename patterns from `rename` patterns or the template.
+    fn rename_patterns(&self) -> Result<Vec<String>, FrencliError> {
+        match &self.renaming {
+            Some(Renaming::Patterns(patterns)) => Ok(patterns.clone()),
+            Some(Renaming::Template(name, params)) => {
+                let builtin;
+                let registry = match &self.templates {
+                    Some(registry) => registry,
+                    None => {
+                        builtin = TemplateRegistry::new();
+                        &builtin
+                    }
+                };
+                resolve_template_pattern(registry, name, params).map_err(FrencliError::Usage)
+            }
+            None => Err(FrencliError::Usage("No rename pattern. Call 'rename' or 'template' to set one.".to_string())),
+        }
+    }
+
+    /// Returns the patterns recorded in the audit log, joined like the command line does.
+    fn audit_pattern(&self) -> Option<String> {
+        self.rename_patterns().ok().map(|patterns| patterns.join(" => "))
+    }
+}
@@ -1,18 +1,28 @@
 //! Rename subcommand for generating rename pattern previews.
 //! 
 //! This module handles the `frencli rename` command which applies a rename pattern
-//! (template) to matching files and generates a preview. All operations are async to match the async API of freneng.
+//! (template) to matching files and generates a preview. Several patterns can be
+//! chained, each one applied to the names produced by the previous one.
+//! All operations are async to match the async API of freneng.
 
-use freneng::{RenamingEngine, FrenError, EnginePreviewResult};
+use freneng::{RenamingEngine, FrenError, EnginePreviewResult, FileRename};
+use crate::error::FrencliError;
 use crate::ui::display_preview;
+use crate::style;
 use std::path::PathBuf;
 use serde::Serialize;
 
+/// Error message when a pattern produces an empty file name
+pub const EMPTY_NAMES: &str = "One or more files would have an empty name. Operation aborted.\n\
+    Please check your pattern and ensure it generates valid filenames.";
+
 #[derive(Serialize)]
 struct RenameJsonOutput {
     renames: Vec<RenameJsonItem>,
     warnings: Vec<String>,
     has_empty_names: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stages: Option<Vec<PreviewStage>>,
 }
 
 #[derive(Serialize)]
@@ -22,6 +32,95 @@ struct RenameJsonItem {
     new_name: String,
 }
 
+/// The names produced by one pattern of a chained rename.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PreviewStage {
+    pub pattern: String,
+    /// New name of each file after this stage, in the order of the input files
+    pub new_names: Vec<String>,
+}
+
+/// Generates a preview for one or more patterns applied in sequence.
+///
+/// The first pattern is applied to `files`; every further pattern is applied
+/// to the names produced by the previous one. The returned preview maps each
+/// original file to its final name, so it can be validated and applied like a
+/// single-pattern preview. When there is more than one pattern, warnings are
+/// prefixed with the stage they come from.
+///
+/// # Arguments
+///
+/// * `engine` - The renaming engine
+/// * `files` - List of files to process
+/// * `patterns` - Rename patterns, applied in order
+///
+/// # Returns
+///
+/// * `Ok((EnginePreviewResult, Vec<PreviewStage>))` - Final preview and the names after each stage
+/// * `Err(FrenError)` - If no pattern is given, a pattern is invalid, or a
+///   later pattern uses file metadata tokens (`%FD`, `%FH`)
+pub async fn generate_chained_preview(
+    engine: &RenamingEngine,
+    files: &[PathBuf],
+    patterns: &[String],
+) -> Result<(EnginePreviewResult, Vec<PreviewStage>), FrenError> {
+    let (first, rest) = patterns.split_first()
+        .ok_or_else(|| FrenError::PatternApplication("No rename pattern provided".into()))?;
+
+    // Intermediate names do not exist on disk, so there is no metadata to read
+    if let Some(position) = rest.iter().position(|p| {
+        let upper = p.to_uppercase();
+        upper.contains("%FD") || upper.contains("%FH")
+    }) {
+        return Err(FrenError::PatternApplication(format!(
+            "Pattern {} of the chain uses %FD or %FH; file date and time tokens can only be used in the first pattern",
+            position + 2
+        )));
+    }
+
+    let chained = !rest.is_empty();
+    let mut result = engine.generate_preview(files, first).await?;
+    if chained {
+        result.warnings = result.warnings.iter().map(|w| format!("Stage 1: {}", w)).collect();
+    }
+    let mut stages = vec![PreviewStage {
+        pattern: first.clone(),
+        new_names: result.renames.iter().map(|r| r.new_name.clone()).collect(),
+    }];
+
+    for (i, pattern) in rest.iter().enumerate() {
+        // An empty name cannot be fed into the next stage
+        if result.has_empty_names {
+            break;
+        }
+
+        let current: Vec<PathBuf> = result.renames.iter().map(|r| r.new_path.clone()).collect();
+        let next = engine.generate_preview(&current, pattern).await?;
+
+        for warning in next.warnings {
+            let warning = format!("Stage {}: {}", i + 2, warning);
+            if !result.warnings.contains(&warning) {
+                result.warnings.push(warning);
+            }
+        }
+        result.has_empty_names = next.has_empty_names;
+        result.renames = result.renames.into_iter()
+            .zip(next.renames)
+            .map(|(original, renamed)| FileRename {
+                old_path: original.old_path,
+                new_path: renamed.new_path,
+                new_name: renamed.new_name,
+            })
+            .collect();
+        stages.push(PreviewStage {
+            pattern: pattern.clone(),
+            new_names: result.renames.iter().map(|r| r.new_name.clone()).collect(),
+        });
+    }
+
+    Ok((result, stages))
+}
+
 /// Handles the rename subcommand - generates and displays preview.
 /// 
 /// # Arguments
@@ -34,26 +133,50 @@ struct RenameJsonItem {
 /// # Returns
 /// 
 /// * `Ok(EnginePreviewResult)` - Preview result that can be used by apply command
-/// * `Err(FrenError)` - If preview generation fails
+/// * `Err(FrencliError)` - If preview generation fails or a file would get an empty name
 pub async fn handle_rename_command(
     engine: &RenamingEngine,
     files: Vec<PathBuf>,
     template: String,
     json: bool,
-) -> Result<EnginePreviewResult, FrenError> {
+) -> Result<EnginePreviewResult, FrencliError> {
+    handle_rename_chain_command(engine, files, vec![template], false, json).await
+}
+
+/// Handles the rename subcommand for one or more chained patterns - generates and displays preview.
+/// 
+/// # Arguments
+/// 
+/// * `engine` - The renaming engine
+/// * `files` - List of files to process
+/// * `patterns` - Rename patterns, applied in order (e.g., `["%L%N.%E", "%N_%C3.%E"]`)
+/// * `show_stages` - If true, also show the names produced by each pattern
+/// * `json` - If true, output as JSON; if false, output as human-readable
+/// 
+/// # Returns
+/// 
+/// * `Ok(EnginePreviewResult)` - Final preview result that can be used by apply command
+/// * `Err(FrencliError)` - [`FrencliError::NoFilesMatched`] if `files` is empty,
+///   [`FrencliError::ValidationFailed`] if a file would get an empty name (human-readable
+///   output only; JSON reports it in `has_empty_names`), or the engine's error if
+///   preview generation fails
+pub async fn handle_rename_chain_command(
+    engine: &RenamingEngine,
+    files: Vec<PathBuf>,
+    patterns: Vec<String>,
+    show_stages: bool,
+    json: bool,
+) -> Result<EnginePreviewResult, FrencliError> {
     if files.is_empty() {
-        eprintln!("Error: No files to process.");
-        return Err(FrenError::Pattern("No files provided".into()));
+        return Err(FrencliError::NoFilesMatched("No files to process.".to_string()));
     }
 
     // Generate preview
-    let preview_result = match engine.generate_preview(&files, &template).await {
-        Ok(res) => res,
-        Err(e) => {
-            eprintln!("Error generating rename patterns: {}", e);
-            return Err(e);
-        }
-    };
+    let (preview_result, stages) = generate_chained_preview(engine, &files, &patterns).await
+        .map_err(|e| match FrencliError::from(e) {
+            FrencliError::Usage(message) => FrencliError::Usage(format!("Error generating rename patterns: {}", message)),
+            other => other,
+        })?;
 
     if json {
         // Output as JSON
@@ -65,33 +188,50 @@ pub async fn handle_rename_command(
             }).collect(),
             warnings: preview_result.warnings.clone(),
             has_empty_names: preview_result.has_empty_names,
+            stages: show_stages.then_some(stages),
         };
         let json_str = serde_json::to_string_pretty(&json_output)
-            .map_err(|e| FrenError::Pattern(format!("Failed to serialize JSON: {}", e)))?;
+            .map_err(|e| FrencliError::Io(format!("Failed to serialize JSON: {}", e)))?;
         println!("{}", json_str);
     } else {
+        if show_stages {
+            display_stages(&files, &stages);
+        }
+
         // Display preview
         display_preview(&preview_result.renames);
 
         // Show warnings
         if !preview_result.warnings.is_empty() {
-            println!("\nWARNINGS:");
+            info!("\n{}", style::warning("WARNINGS:"));
             for warning in &preview_result.warnings {
-                println!("  - {}", warning);
+                info!("  - {}", style::warning(warning));
             }
         }
 
         // Block if empty names
         if preview_result.has_empty_names {
-            eprintln!("\nERROR: One or more files would have an empty name. Operation aborted.");
-            eprintln!("Please check your pattern and ensure it generates valid filenames.");
-            std::process::exit(1);
+            return Err(FrencliError::ValidationFailed(EMPTY_NAMES.to_string()));
         }
 
         // rename command only shows preview - use 'apply' to actually rename
-        println!("\nPreview mode. Use 'apply' subcommand to perform the renaming.");
+        info!("\nPreview mode. Use 'apply' subcommand to perform the renaming.");
     }
     
     Ok(preview_result)
 }
 
+
+/// Displays the name each file has after every stage of a chained rename.
+fn display_stages(files: &[PathBuf], stages: &[PreviewStage]) {
+    info!("Stages:");
+    for (i, file) in files.iter().enumerate() {
+        let old = file.file_name().and_then(|n| n.to_str()).unwrap_or("?");
+        info!("  {}", old);
+        for (number, stage) in stages.iter().enumerate() {
+            let name = stage.new_names.get(i).map(|n| n.as_str()).unwrap_or("");
+            info!("    {}. {:<40} {}", number + 1, name, stage.pattern);
+        }
+    }
+    info!();
+}
@@ -0,0 +1,508 @@
+//! Declarative description of the command line.
+//!
+//! Every subcommand, its flags, their arity and their descriptions are
+//! defined once here. The parser in `subcommands.rs` and the help text in
+//! `help.rs` are both generated from this table, so they cannot disagree.
+
+/// How many values a flag takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    /// A switch that takes no value (e.g. `--yes`)
+    Switch,
+    /// Exactly one value (e.g. `--use NAME`)
+    One,
+    /// One or more values, up to the next flag or subcommand (e.g. `--exclude A B`)
+    Many,
+    /// Zero or more values (e.g. `--try [NAME]...`)
+    Optional,
+}
+
+/// What kind of value a flag takes, used by shell completion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueHint {
+    /// No value, or a value that cannot be completed
+    None,
+    /// A file path
+    File,
+    /// A template name
+    TemplateName,
+    /// A subcommand name; the parser reads it as an argument, not as the next subcommand
+    Subcommand,
+    /// One of a fixed set of words
+    Choices(&'static [&'static str]),
+}
+
+/// A flag accepted by a subcommand.
+#[derive(Debug, Clone, Copy)]
+pub struct FlagSpec {
+    /// Flag name without the leading `--`
+    pub name: &'static str,
+    /// Value placeholder shown in help (e.g. `<FILE>`); empty for switches
+    pub value_name: &'static str,
+    pub arity: Arity,
+    /// Kind of value, for shell completion
+    pub hint: ValueHint,
+    /// Help text, one entry per line
+    pub help: &'static [&'static str],
+}
+
+/// A positional argument accepted by a subcommand.
+#[derive(Debug, Clone, Copy)]
+pub struct ArgSpec {
+    /// Argument placeholder (e.g. `<PATTERN>...`)
+    pub name: &'static str,
+    /// Kind of value, for shell completion
+    pub hint: ValueHint,
+    /// Help text, one entry per line
+    pub help: &'static [&'static str],
+}
+
+/// A subcommand with everything needed to parse it and print its help.
+#[derive(Debug, Clone, Copy)]
+pub struct SubcommandSpec {
+    pub name: &'static str,
+    /// One-line summary, shown in the main help
+    pub summary: &'static str,
+    /// Longer description shown under the summary in the subcommand help
+    pub description: &'static [&'static str],
+    /// Alternative usage lines (after `frencli `), in addition to the generated one
+    pub alt_usage: &'static [&'static str],
+    /// Positional arguments; a subcommand without any rejects positional arguments
+    pub args: &'static [ArgSpec],
+    pub flags: &'static [FlagSpec],
+    pub examples: &'static [&'static str],
+}
+
+impl SubcommandSpec {
+    /// Looks up one of this subcommand's flags by name (without the leading `--`).
+    pub fn flag(&self, name: &str) -> Option<&'static FlagSpec> {
+        self.flags.iter().find(|flag| flag.name == name)
+    }
+
+    /// The main usage line (after `frencli `), e.g. `list [OPTIONS] <PATTERN>...`
+    pub fn usage(&self) -> String {
+        let mut usage = format!("{} [OPTIONS]", self.name);
+        for arg in self.args {
+            usage.push(' ');
+            usage.push_str(arg.name);
+        }
+        usage
+    }
+
+    /// Whether this subcommand's first argument is the name of another subcommand.
+    pub fn takes_subcommand_name(&self) -> bool {
+        self.args.first().is_some_and(|arg| arg.hint == ValueHint::Subcommand)
+    }
+
+    /// Whether this subcommand accepts positional arguments.
+    pub fn takes_args(&self) -> bool {
+        !self.args.is_empty()
+    }
+}
+
+/// Builds a switch flag.
+const fn switch(name: &'static str, help: &'static [&'static str]) -> FlagSpec {
+    FlagSpec { name, value_name: "", arity: Arity::Switch, hint: ValueHint::None, help }
+}
+
+/// Builds a flag that takes values.
+const fn value(
+    name: &'static str,
+    value_name: &'static str,
+    arity: Arity,
+    hint: ValueHint,
+    help: &'static [&'static str],
+) -> FlagSpec {
+    FlagSpec { name, value_name, arity, hint, help }
+}
+
+/// One-line description of frencli
+pub const ABOUT: &str = "Batch file renamer with pattern matching";
+
+/// Top-level options, accepted only as the first argument
+pub const TOP_LEVEL_OPTIONS: [(&str, &str); 2] = [
+    ("--help", "Print help"),
+    ("--version", "Print version"),
+];
+
+/// Global options, accepted before the first subcommand
+pub const GLOBAL_FLAGS: &[FlagSpec] = &[
+    value("config", "<FILE>", Arity::One, ValueHint::File, &[
+        "Read default options from FILE instead of the config file",
+        "(can also be set with the FRENCLI_CONFIG environment variable)",
+    ]),
+    switch("no-config", &["Ignore the config file"]),
+    value("chdir", "<DIR>", Arity::One, ValueHint::File, &[
+        "Run as if frencli was started in DIR: patterns, --files-from,",
+        "the undo history and the audit log are relative to DIR",
+    ]),
+    value("color", "<WHEN>", Arity::One, ValueHint::Choices(&["auto", "always", "never"]), &[
+        "Color the output: auto (default; only on a terminal and if NO_COLOR",
+        "is not set), always or never",
+    ]),
+    switch("ascii", &["Print ASCII symbols instead of emoji and other Unicode symbols"]),
+    switch("quiet", &["Print errors only (--json output and prompts are still shown)"]),
+    switch("verbose", &[
+        "Also print per-file decisions, timing, resolved template patterns",
+        "and the config and template files used (on stderr)",
+    ]),
+];
+
+/// Examples shown in the main help
+pub const MAIN_EXAMPLES: [&str; 3] = [
+    "frencli list *.txt",
+    "frencli list *.txt rename \"%N_backup.%E\"",
+    "frencli list *.txt rename \"%N_backup.%E\" apply --yes",
+];
+
+/// The `--help` flag, accepted by every subcommand
+pub const HELP_FLAG: FlagSpec = switch("help", &["Print help"]);
+
+/// All subcommands, in the order they are listed in the main help
+pub const SUBCOMMANDS: &[SubcommandSpec] = &[
+    SubcommandSpec {
+        name: "list",
+        summary: "List files matching patterns",
+        description: &[],
+        alt_usage: &["list [OPTIONS] --files-from <FILE>"],
+        args: &[ArgSpec { name: "<PATTERN>...", hint: ValueHint::File, help: &["Search patterns (glob patterns, e.g., \"*.txt\")"] }],
+        flags: &[
+            value("files-from", "<FILE>", Arity::One, ValueHint::File, &[
+                "Read file paths from FILE (one per line)",
+                "Use \"-\" to read from stdin",
+            ]),
+            switch("recursive", &["Recursively search subdirectories (supports ** glob pattern)"]),
+            switch("no-recursive", &["Do not search subdirectories, even if the config file sets list.recursive"]),
+            value("exclude", "<EXCLUDE>...", Arity::Many, ValueHint::File, &["Exclude files matching these patterns"]),
+            switch("no-exclude", &["Ignore the excludes from the config file"]),
+            value("min-size", "<SIZE>", Arity::One, ValueHint::None, &[
+                "Only list files of at least SIZE: bytes, or a number with a unit",
+                "(K, M, G, T are powers of 1000; KiB, MiB, GiB, TiB of 1024)",
+            ]),
+            value("max-size", "<SIZE>", Arity::One, ValueHint::None, &["Only list files of at most SIZE"]),
+            value("newer", "<TIME>", Arity::One, ValueHint::None, &[
+                "Only list files modified after TIME: a date (2026-01-31,",
+                "\"2026-01-31 18:00\") or a duration ago (30m, 12h, 7d, 2w)",
+            ]),
+            value("older", "<TIME>", Arity::One, ValueHint::None, &["Only list files modified before TIME"]),
+            value("type", "<TYPE>", Arity::One, ValueHint::Choices(&["file", "dir", "symlink"]), &[
+                "Only list regular files, directories or symbolic links",
+            ]),
+            switch("fullpath", &["Display full paths instead of just filenames"]),
+            switch("no-fullpath", &["Display just filenames, even if the config file sets list.fullpath"]),
+            switch("json", &["Output as JSON array"]),
+            switch("no-json", &["Output as text, even if the config file sets list.json"]),
+        ],
+        examples: &[
+            "frencli list \"*.txt\"",
+            "frencli list \"*.txt\" --recursive --exclude \"*.tmp\"",
+            "frencli list \"*.jpg\" --min-size 2MB --newer 7d",
+            "frencli list --files-from files.txt",
+        ],
+    },
+    SubcommandSpec {
+        name: "rename",
+        summary: "Generate rename preview using a pattern",
+        description: &[
+            "Generates a preview of file names using a pattern without applying the rename.",
+            "Operates on files from the last `list` command.",
+            "Use `frencli apply` to actually perform the rename.",
+        ],
+        alt_usage: &[],
+        args: &[ArgSpec { name: "<RENAME_PATTERN>...", hint: ValueHint::None, help: &[
+            "Pattern to generate new file names (e.g., \"%N.%E\", \"%N2-7.%E\").",
+            "Several patterns are applied in order, each to the result of the previous one",
+        ] }],
+        flags: &[
+            switch("stages", &["Show the names produced by each pattern"]),
+            switch("json", &["Output as JSON"]),
+            switch("no-json", &["Output as text, even if the config file sets rename.json"]),
+        ],
+        examples: &[
+            "frencli list *.jpg rename \"%L%N.%E\"",
+            "frencli list *.jpg rename \"%L%N.%E\" \"%N%R/ /_.%E\" \"%N_%C3.%E\" --stages",
+        ],
+    },
+    SubcommandSpec {
+        name: "validate",
+        summary: "Validate a rename pattern",
+        description: &[
+            "Checks the preview from `rename` or `template --use` for invalid names,",
+            "collisions and permission problems before anything is renamed.",
+        ],
+        alt_usage: &[],
+        args: &[],
+        flags: &[
+            switch("skip-invalid", &["Skip invalid files instead of aborting"]),
+        ],
+        examples: &[
+            "frencli list *.txt rename \"%N_backup.%E\" validate",
+            "frencli list *.txt rename \"%N_backup.%E\" validate --skip-invalid apply",
+        ],
+    },
+    SubcommandSpec {
+        name: "apply",
+        summary: "Apply rename operations (performs the rename)",
+        description: &[
+            "Operates on files from the last `list` command.",
+            "Run `frencli list` first to select files, then use `frencli rename` to generate a preview,",
+            "and finally `frencli apply` to perform the rename.",
+        ],
+        alt_usage: &[],
+        args: &[],
+        flags: &[
+            switch("overwrite", &["Overwrite existing files"]),
+            switch("no-overwrite", &["Never overwrite existing files, even if the config file sets apply.overwrite"]),
+            switch("yes", &["Skip confirmation prompt"]),
+            switch("interactive", &["Interactive mode (edit filenames individually)"]),
+            switch("json", &["Output as JSON"]),
+            switch("no-json", &["Output as text, even if the config file sets apply.json"]),
+            switch("no-audit", &["Do not record the operation in the audit log"]),
+            switch("audit", &["Record the operation in the audit log, even if the config file sets apply.audit = false"]),
+            switch("dry-run", &[
+                "Validate and simulate the renames against the current files;",
+                "report what would be renamed, skipped or fail without renaming anything",
+            ]),
+        ],
+        examples: &[
+            "frencli list *.txt rename \"%N_backup.%E\" apply",
+            "frencli list *.txt rename \"%N_backup.%E\" apply --yes",
+            "frencli list *.txt rename \"%N_backup.%E\" apply --dry-run",
+        ],
+    },
+    SubcommandSpec {
+        name: "template",
+        summary: "Manage templates",
+        description: &[],
+        alt_usage: &[],
+        args: &[],
+        flags: &[
+            switch("list", &["List available templates"]),
+            value("try", "[NAME|NUMBER]...", Arity::Optional, ValueHint::TemplateName, &[
+                "Preview templates (all if none given) on files from 'list'",
+            ]),
+            value("category", "<CATEGORY>", Arity::One, ValueHint::None, &[
+                "With --list or --try, only include templates in a category",
+            ]),
+            switch("json", &["With --list, output as JSON"]),
+            value("check", "[NAME|NUMBER]...", Arity::Optional, ValueHint::TemplateName, &[
+                "Check templates (all if none given) for pattern mistakes;",
+                "exits with an error if any are found",
+            ]),
+            value("use", "<NAME>", Arity::One, ValueHint::TemplateName, &["Use a template pattern"]),
+            switch("stages", &["With --use, show the names produced by each pattern of a chained template"]),
+            switch("no-json", &["With --use, output the preview as text even if the config file sets rename.json"]),
+            value("set", "<KEY=VALUE>...", Arity::Many, ValueHint::None, &[
+                "Fill a template parameter ({KEY} in the pattern)",
+                "(with --save, stores parameter defaults)",
+            ]),
+            value("save", "<NAME> <PATTERN>", Arity::Many, ValueHint::None, &["Save a pattern as a user template"]),
+            value("delete", "<NAME>", Arity::One, ValueHint::TemplateName, &["Delete a user template"]),
+            value("rename", "<OLD> <NEW>", Arity::Many, ValueHint::TemplateName, &["Rename a user template"]),
+            switch("force", &["Replace an existing template when saving or renaming"]),
+            value("export", "<FILE> [NAME]...", Arity::Many, ValueHint::File, &[
+                "Write templates to a bundle file ('-' for stdout);",
+                "all user and project templates if no names are given",
+            ]),
+            value("import", "<FILE>", Arity::One, ValueHint::File, &[
+                "Add the templates of a bundle file ('-' for stdin) to the user templates",
+            ]),
+            value("on-conflict", "<POLICY>", Arity::One, ValueHint::Choices(&["skip", "overwrite", "rename-on-import"]), &[
+                "With --import, handle existing names: skip, overwrite or rename-on-import",
+            ]),
+        ],
+        examples: &[
+            "frencli template --list",
+            "frencli template --list --category photo",
+            "frencli list *.jpg template --try",
+            "frencli list *.jpg template --try --category photo",
+            "frencli template --check",
+            "frencli list *.txt template --use photo-date",
+            "frencli template --save podcast \"%T%N_%C3.%E\"",
+            "frencli template --save trip \"{prefix}_%C{width}.%E\" --set width=3",
+            "frencli list *.jpg template --use trip --set prefix=rome",
+            "frencli template --rename podcast episode",
+            "frencli template --delete episode",
+            "frencli template --export team-templates.json podcast trip",
+            "frencli template --import team-templates.json --on-conflict rename-on-import",
+        ],
+    },
+    SubcommandSpec {
+        name: "alias",
+        summary: "Save and list pipeline aliases",
+        description: &[
+            "An alias is a saved sequence of subcommands, run with 'frencli run NAME'.",
+            "Put the subcommands after '--' so they are stored instead of run.",
+        ],
+        alt_usage: &["alias --list", "alias --delete <NAME>"],
+        args: &[ArgSpec { name: "[SUBCOMMAND]...", hint: ValueHint::None, help: &["With --save, the subcommands to store (after '--')"] }],
+        flags: &[
+            value("save", "<NAME>", Arity::One, ValueHint::None, &["Save the subcommands after '--' as an alias"]),
+            switch("list", &["List saved aliases"]),
+            value("delete", "<NAME>", Arity::One, ValueHint::None, &["Delete an alias"]),
+            switch("force", &["Replace an existing alias when saving"]),
+        ],
+        examples: &[
+            "frencli alias --save photos -- list \"*.jpg\" rename \"%N_%C3.%E\" apply --yes",
+            "frencli alias --list",
+            "frencli alias --delete photos",
+        ],
+    },
+    SubcommandSpec {
+        name: "run",
+        summary: "Run a saved pipeline alias",
+        description: &["Extra patterns are appended to the alias's 'list' subcommand."],
+        alt_usage: &[],
+        args: &[
+            ArgSpec { name: "<NAME>", hint: ValueHint::None, help: &["Alias to run"] },
+            ArgSpec { name: "[PATTERN]...", hint: ValueHint::File, help: &["Extra search patterns for 'list'"] },
+        ],
+        flags: &[],
+        examples: &[
+            "frencli run photos",
+            "frencli run photos \"*.png\"",
+        ],
+    },
+    SubcommandSpec {
+        name: "undo",
+        summary: "Undo operations",
+        description: &[],
+        alt_usage: &[],
+        args: &[],
+        flags: &[
+            switch("check", &["Check undo status"]),
+            switch("apply", &["Apply undo"]),
+            switch("yes", &["Skip confirmation prompt (when using --apply)"]),
+        ],
+        examples: &[
+            "frencli undo --check",
+            "frencli undo --apply",
+            "frencli undo --apply --yes",
+        ],
+    },
+    SubcommandSpec {
+        name: "audit",
+        summary: "View audit log",
+        description: &["View audit log of rename operations."],
+        alt_usage: &[],
+        args: &[],
+        flags: &[
+            value("limit", "<N>", Arity::One, ValueHint::None, &["Limit number of entries to show"]),
+            switch("json", &["Output in JSON format"]),
+        ],
+        examples: &[
+            "frencli audit",
+            "frencli audit --limit 10",
+            "frencli audit --json",
+        ],
+    },
+    SubcommandSpec {
+        name: "interactive",
+        summary: "Interactive workflow guide",
+        description: &[
+            "Guides you through the standard frencli workflow step by step:",
+            "  1. Select files to rename",
+            "  2. Define rename pattern",
+            "  3. Preview and validate",
+            "  4. Apply rename",
+        ],
+        alt_usage: &[],
+        args: &[],
+        flags: &[],
+        examples: &["frencli interactive"],
+    },
+    SubcommandSpec {
+        name: "manpage",
+        summary: "Generate man pages",
+        description: &[
+            "Prints the man page for frencli, or for one subcommand, in roff format.",
+            "With --output, writes the pages for frencli and every subcommand to a directory.",
+        ],
+        alt_usage: &["manpage --output <DIR>"],
+        args: &[ArgSpec { name: "[SUBCOMMAND]", hint: ValueHint::Subcommand, help: &["Subcommand to print the page for (default: frencli itself)"] }],
+        flags: &[
+            value("output", "<DIR>", Arity::One, ValueHint::File, &["Write all man pages to DIR"]),
+        ],
+        examples: &[
+            "frencli manpage | man -l -",
+            "frencli manpage rename | man -l -",
+            "frencli manpage --output /usr/local/share/man/man1",
+        ],
+    },
+    SubcommandSpec {
+        name: "completions",
+        summary: "Generate shell completion scripts",
+        description: &[
+            "Prints a completion script for the given shell. Template names are looked up",
+            "when completing, so new user and project templates are offered right away.",
+        ],
+        alt_usage: &[],
+        args: &[ArgSpec { name: "<SHELL>", hint: ValueHint::Choices(&SHELLS), help: &["Shell to generate completions for: bash, zsh or fish"] }],
+        flags: &[
+            switch("template-names", &["Print the names of all templates, one per line (used by the scripts)"]),
+        ],
+        examples: &[
+            "frencli completions bash > ~/.local/share/bash-completion/completions/frencli",
+            "frencli completions zsh > ~/.zfunc/_frencli",
+            "frencli completions fish > ~/.config/fish/completions/frencli.fish",
+        ],
+    },
+];
+
+/// Placeholder tokens of rename patterns, with a description and an example for `file.txt`
+pub const PATTERN_PLACEHOLDERS: &[(&str, &str, &str)] = &[
+    ("%N", "Filename without extension", "file"),
+    ("%E", "Extension without the dot", "txt"),
+    ("%F", "Full filename (name + extension)", "file.txt"),
+    ("%C", "Counter (starts at 1)", "1, 2, ..."),
+    ("%C3", "Counter with padding (3 digits)", "001, 002, ..."),
+    ("%P", "Immediate parent directory name", "Documents"),
+    ("%P1-3", "Substring of parent directory", "Doc"),
+    ("%D", "Current date (YYYY-MM-DD)", "2025-12-18"),
+    ("%H", "Current time (HH-MM-SS)", "14-30-05"),
+    ("%FD", "File modification date", "2025-12-10"),
+    ("%FH", "File modification time", "09-15-00"),
+];
+
+/// Substring selections for `%N` and `%E`
+pub const PATTERN_SUBSTRINGS: &[(&str, &str)] = &[
+    ("%N1-3", "Chars 1 to 3 of the name"),
+    ("%N5-", "Chars from index 5 to the end of the name"),
+    ("%N-5", "Chars from the beginning up to index 5"),
+    ("%N--3", "The name minus the last 3 characters"),
+    ("%N3--4", "Chars starting from index 3 up to the 4th character from the end"),
+    ("%E1-2", "Chars 1 to 2 of the extension"),
+];
+
+/// Modifier tokens of rename patterns; each applies to the result accumulated so far
+pub const PATTERN_MODIFIERS: &[(&str, &str)] = &[
+    ("%L", "Lowercase the entire accumulated result"),
+    ("%U", "Uppercase the entire accumulated result"),
+    ("%T", "Title case the entire accumulated result (capitalizes after spaces, dots, dashes, underscores)"),
+    ("%M", "Trim leading and trailing whitespace from the accumulated result"),
+    ("%R/old/new", "Replace occurrences of old with new; the delimiter can be / | : , or @"),
+    ("%X/pattern/new", "Regex replacement; supports capturing groups and standard regex syntax"),
+];
+
+/// Shells supported by `completions`
+pub const SHELLS: [&str; 3] = ["bash", "zsh", "fish"];
+
+/// Looks up a subcommand by name.
+pub fn find_subcommand(name: &str) -> Option<&'static SubcommandSpec> {
+    SUBCOMMANDS.iter().find(|spec| spec.name == name)
+}
+
+/// Whether `name` is a subcommand name.
+pub fn is_subcommand(name: &str) -> bool {
+    find_subcommand(name).is_some()
+}
+
+/// Names of all subcommands.
+pub fn subcommand_names() -> impl Iterator<Item = &'static str> {
+    SUBCOMMANDS.iter().map(|spec| spec.name)
+}
+
+/// Looks up a global option by name (without the leading `--`).
+pub fn find_global_flag(name: &str) -> Option<&'static FlagSpec> {
+    GLOBAL_FLAGS.iter().find(|flag| flag.name == name)
+}
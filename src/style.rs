@@ -0,0 +1,188 @@
+//! Terminal styling: colors for previews, errors and warnings, and ASCII symbols.
+//!
+//! Output is colored when the global `--color` option is `always`, or when it is
+//! `auto` (the default), stdout is a terminal and the `NO_COLOR` environment
+//! variable is not set. `--ascii` replaces the symbols and emoji used in the
+//! output (e.g. `✓`, `📊`) with plain ASCII.
+//!
+//! The settings are chosen once in `main` with [`init`]; until then, and in
+//! library use, output is plain.
+
+use std::fmt;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static COLOR: AtomicBool = AtomicBool::new(false);
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+const RED: &str = "31";
+const GREEN: &str = "32";
+const YELLOW: &str = "33";
+const BOLD: &str = "1";
+
+/// When to color the output, as given to `--color`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color if stdout is a terminal and `NO_COLOR` is not set
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Parses the value of `--color`.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!("Invalid --color value '{}'. Use auto, always or never.", value)),
+        }
+    }
+
+    /// Decides whether to color the output.
+    ///
+    /// # Arguments
+    ///
+    /// * `no_color` - Whether the `NO_COLOR` environment variable is set to a non-empty value
+    /// * `is_terminal` - Whether stdout is a terminal
+    pub fn enabled(self, no_color: bool, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Auto => !no_color && is_terminal,
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+/// Sets the output style for the rest of the run from `--color` and `--ascii`.
+pub fn init(choice: ColorChoice, ascii: bool) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    set_color(choice.enabled(no_color, io::stdout().is_terminal()));
+    set_ascii(ascii);
+}
+
+/// Turns colored output on or off.
+pub fn set_color(enabled: bool) {
+    COLOR.store(enabled, Ordering::Relaxed);
+}
+
+/// Turns ASCII symbols on or off.
+pub fn set_ascii(enabled: bool) {
+    ASCII.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns true if output is colored.
+pub fn color_enabled() -> bool {
+    COLOR.load(Ordering::Relaxed)
+}
+
+/// Returns true if symbols are printed as ASCII.
+pub fn ascii_enabled() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+/// Wraps `text` in an ANSI style if colors are on.
+fn paint(code: &str, text: &str) -> String {
+    if color_enabled() {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Styles errors and failed renames.
+pub fn error(text: &str) -> String {
+    paint(RED, text)
+}
+
+/// Styles warnings and skipped files.
+pub fn warning(text: &str) -> String {
+    paint(YELLOW, text)
+}
+
+/// Styles successful renames and passed checks.
+pub fn success(text: &str) -> String {
+    paint(GREEN, text)
+}
+
+/// Styles headings.
+pub fn heading(text: &str) -> String {
+    paint(BOLD, text)
+}
+
+/// Highlights the part of `new` that differs from `old`.
+///
+/// The common prefix and suffix of the two names are left plain and the
+/// characters in between are colored, e.g. `photo.jpg` -> `photo_001.jpg`
+/// highlights `_001`.
+pub fn highlight_change(old: &str, new: &str) -> String {
+    if !color_enabled() || old == new {
+        return new.to_string();
+    }
+    let old: Vec<char> = old.chars().collect();
+    let new: Vec<char> = new.chars().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let changed: String = new[prefix..new.len() - suffix].iter().collect();
+    format!(
+        "{}{}{}",
+        new[..prefix].iter().collect::<String>(),
+        paint(GREEN, &changed),
+        new[new.len() - suffix..].iter().collect::<String>(),
+    )
+}
+
+/// Pads styled `text` to `width` columns, counting only the characters of `plain`.
+///
+/// `format!("{:<40}", ...)` would count the invisible ANSI codes.
+pub fn pad(text: &str, plain: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(plain.chars().count())))
+}
+
+/// A symbol in the output, printed as ASCII with `--ascii`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    /// `✓`, or `+`
+    Check,
+    /// `✗`, or `x`
+    Cross,
+    /// `⚠`, or `!`
+    Warning,
+    /// `📊`, or `*`
+    Summary,
+    /// `→`, or `->`
+    Arrow,
+    /// `↔`, or `<->`
+    BothWays,
+}
+
+impl Symbol {
+    /// Returns the symbol as printed with the current settings.
+    pub fn as_str(self) -> &'static str {
+        match (self, ascii_enabled()) {
+            (Symbol::Check, false) => "✓",
+            (Symbol::Check, true) => "+",
+            (Symbol::Cross, false) => "✗",
+            (Symbol::Cross, true) => "x",
+            (Symbol::Warning, false) => "⚠",
+            (Symbol::Warning, true) => "!",
+            (Symbol::Summary, false) => "📊",
+            (Symbol::Summary, true) => "*",
+            (Symbol::Arrow, false) => "→",
+            (Symbol::Arrow, true) => "->",
+            (Symbol::BothWays, false) => "↔",
+            (Symbol::BothWays, true) => "<->",
+        }
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -4,117 +4,330 @@
 //! Order of subcommands doesn't matter - they're executed in logical order.
 //! 
 //! Standalone commands (undo, audit, interactive) must be used alone.
+//! The accepted subcommands and flags are defined in `spec.rs`.
 
 use std::collections::HashMap;
+use crate::spec::{
+    Arity, FlagSpec, SubcommandSpec, GLOBAL_FLAGS, HELP_FLAG, SUBCOMMANDS, find_global_flag, find_subcommand, is_subcommand,
+    subcommand_names,
+};
+use crate::style::Symbol;
+use crate::suggest::{closest_match, did_you_mean};
+
+/// Separator after which all arguments are positional for the current subcommand
+const END_OF_OPTIONS: &str = "--";
 
 #[derive(Debug, Clone)]
 pub struct ParsedSubcommand {
     pub name: String,
     pub args: Vec<String>,
     pub flags: HashMap<String, Vec<String>>,
+    /// Hints about arguments that look like mistyped subcommands, for the caller to print
+    pub warnings: Vec<String>,
+}
+
+/// Global options by name, and the arguments after them
+type GlobalOptions = (HashMap<String, Vec<String>>, Vec<String>);
+
+/// Parses the global options (e.g. `--config <FILE>`) at the start of the command line.
+///
+/// Global options must come before the first subcommand. Parsing stops at the
+/// first argument that is not a global option, so `--help` and `--version`
+/// are left for the caller.
+///
+/// # Returns
+///
+/// * `Ok((flags, rest))` - The global options and the remaining arguments
+/// * `Err(String)` - If a global option is missing its value or is given one it does not take
+pub fn parse_global_options(args: Vec<String>) -> Result<GlobalOptions, String> {
+    let mut flags: HashMap<String, Vec<String>> = HashMap::new();
+    let mut i = 0;
+
+    while i < args.len() {
+        let Some(flag_arg) = args[i].strip_prefix("--") else {
+            break;
+        };
+        let (flag_name, inline_value) = match flag_arg.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (flag_arg, None),
+        };
+        let Some(flag) = find_global_flag(flag_name) else {
+            break;
+        };
+        i += 1;
+
+        if flag.arity == Arity::Switch {
+            if inline_value.is_some() {
+                return Err(format!("'--{}' does not take a value.", flag.name));
+            }
+            flags.insert(flag.name.to_string(), Vec::new());
+            continue;
+        }
+        let value = match inline_value {
+            Some(value) => value.to_string(),
+            None => match args.get(i) {
+                Some(value) if !value.starts_with("--") && !is_subcommand(value) => {
+                    i += 1;
+                    value.clone()
+                }
+                _ => String::new(),
+            },
+        };
+        if value.is_empty() {
+            return Err(format!("'--{}' requires a value: frencli --{} {} <SUBCOMMAND>...", flag.name, flag.name, flag.value_name));
+        }
+        flags.insert(flag.name.to_string(), vec![value]);
+    }
+
+    Ok((flags, args[i..].to_vec()))
 }
 
 /// Parses command line arguments into subcommands.
 /// 
-/// Recognizes subcommands: list, rename, validate, apply, template, undo, audit
-/// Extracts their arguments and flags.
-pub fn parse_multi_subcommand(args: Vec<String>) -> Vec<ParsedSubcommand> {
+/// Subcommands, their flags and how many values each flag takes come from
+/// the command spec in `spec.rs`. A flag can also be written as `--flag=value`,
+/// which gives it exactly one value, and `--` ends option parsing: every
+/// argument after it is a positional argument of the current subcommand.
+/// 
+/// # Returns
+/// 
+/// * `Ok(Vec<ParsedSubcommand>)` - The subcommands in the order given, with
+///   warnings for pattern arguments that look like mistyped subcommands
+/// * `Err(String)` - If a word is not a subcommand, a flag is unknown, or a short
+///   flag is used; the message suggests the closest valid name when there is one
+pub fn parse_multi_subcommand(args: Vec<String>) -> Result<Vec<ParsedSubcommand>, String> {
     let mut subcommands = Vec::new();
     let mut i = 0;
     
-    // Known subcommand names
-    let known_subcommands = ["list", "rename", "validate", "apply", "template", "undo", "audit", "interactive"];
-    
     while i < args.len() {
         let arg = &args[i];
         
         // Check if this is a subcommand
-        if known_subcommands.contains(&arg.as_str()) {
-            let subcommand_name = arg.clone();
-            let mut subcommand_args = Vec::new();
-            let mut flags: HashMap<String, Vec<String>> = HashMap::new();
-            i += 1;
+        let Some(spec) = find_subcommand(arg) else {
+            if arg == END_OF_OPTIONS {
+                return Err("'--' must follow a subcommand, e.g. frencli list -- --draft.txt".to_string());
+            }
+            return Err(match arg.strip_prefix("--") {
+                Some(flag_name) => unknown_flag_error(flag_name),
+                None => format!(
+                    "Unknown subcommand '{}'.{} Run 'frencli --help' to see the available subcommands.",
+                    arg,
+                    did_you_mean(arg, subcommand_names())
+                ),
+            });
+        };
+        
+        let mut subcommand_args = Vec::new();
+        let mut warnings = Vec::new();
+        let mut flags: HashMap<String, Vec<String>> = HashMap::new();
+        i += 1;
+        
+        // Collect arguments until next subcommand or end
+        while i < args.len() {
+            let next_arg = &args[i];
             
-            // Collect arguments until next subcommand or end
-            while i < args.len() {
-                let next_arg = &args[i];
-                
-                // Check if next arg is a subcommand
-                if known_subcommands.contains(&next_arg.as_str()) {
-                    break;
+            // Check if next arg is a subcommand (unless this subcommand takes a subcommand name, e.g. `manpage rename`)
+            if is_subcommand(next_arg) {
+                if spec.takes_subcommand_name() && subcommand_args.is_empty() {
+                    subcommand_args.push(next_arg.clone());
+                    i += 1;
+                    continue;
                 }
+                break;
+            }
+            
+            // Everything after '--' is a positional argument of this subcommand,
+            // even if it looks like a flag or a subcommand name
+            if next_arg == END_OF_OPTIONS {
+                for rest in &args[i + 1..] {
+                    if !spec.takes_args() {
+                        return Err(no_arguments_error(spec, rest));
+                    }
+                    subcommand_args.push(rest.clone());
+                }
+                i = args.len();
+                break;
+            }
+            
+            // Check if it's a flag
+            if let Some(flag_arg) = next_arg.strip_prefix("--") {
+                // `--flag=value` gives the flag exactly one value
+                let (flag_name, inline_value) = match flag_arg.split_once('=') {
+                    Some((name, value)) => (name, Some(value)),
+                    None => (flag_arg, None),
+                };
+                let flag = lookup_flag(spec, flag_name)?;
+                let mut flag_values = Vec::new();
+                i += 1;
                 
-                // Check if it's a flag
-                if next_arg.starts_with("--") {
-                    let flag_name = next_arg[2..].to_string();
-                    let mut flag_values = Vec::new();
-                    i += 1;
-                    
-                    // Boolean flags that don't accept values
-                    let boolean_flags = ["yes", "overwrite", "recursive", "fullpath", "skip-invalid", 
-                                         "interactive", "check", "apply", "json", "no-audit", "help"];
-                    let is_boolean_flag = boolean_flags.contains(&flag_name.as_str());
-                    
-                    if is_boolean_flag {
-                        // Boolean flags don't accept values - just mark the flag as present
-                        flags.insert(flag_name, Vec::new());
-                    } else {
-                        // Collect flag values (until next flag or subcommand)
-                        while i < args.len() {
-                            let val = &args[i];
-                            if val.starts_with("--") || known_subcommands.contains(&val.as_str()) {
-                                break;
-                            }
-                            // For non-boolean flags (like --exclude, --use), allow values starting with '-'
-                            // as they could be filenames/patterns
-                            flag_values.push(val.clone());
-                            i += 1;
-                        }
-                        flags.insert(flag_name, flag_values);
+                if flag.arity == Arity::Switch {
+                    if inline_value.is_some() {
+                        return Err(format!("'--{}' does not take a value.", flag.name));
                     }
-                } else if next_arg.starts_with("-") && !next_arg.starts_with("--") && next_arg.len() > 1 {
-                    // Single dash argument (like -y, -r, etc.)
-                    // Only --<something> is interpreted as flags. Single dash arguments
-                    // are treated as positional arguments (filenames/patterns) for subcommands
-                    // that accept them, or rejected if the subcommand doesn't accept positional args.
-                    let accepts_positional_args = matches!(subcommand_name.as_str(), "list" | "rename");
-                    
-                    if accepts_positional_args {
-                        // This could be a filename or pattern starting with '-', treat as positional arg
-                        subcommand_args.push(next_arg.clone());
-                        i += 1;
-                    } else {
-                        // This subcommand doesn't accept positional args, so -X is clearly a short flag attempt
-                        eprintln!("Error: Short flags (like '{}') are not supported.", next_arg);
-                        eprintln!("Please use the long form instead (e.g., '--yes' instead of '-y').");
-                        eprintln!("\nCommon short flag mappings:");
-                        eprintln!("  -y, -Y  →  --yes");
-                        eprintln!("  -o, -O  →  --overwrite");
-                        eprintln!("  -r, -R  →  --recursive");
-                        eprintln!("  -e, -E  →  --exclude");
-                        eprintln!("  -h, -H  →  --help");
-                        eprintln!("  -f, -F  →  --fullpath");
-                        eprintln!("  -V, -v  →  --version");
-                        std::process::exit(1);
+                    // Switches don't accept values - just mark the flag as present
+                    flags.insert(flag.name.to_string(), Vec::new());
+                } else if let Some(value) = inline_value {
+                    if value.is_empty() && matches!(flag.arity, Arity::One | Arity::Many) {
+                        return Err(missing_value_error(spec, flag));
                     }
+                    flags.entry(flag.name.to_string()).or_default().push(value.to_string());
                 } else {
-                    // Regular argument
+                    // Collect flag values (until next flag or subcommand, or after one value)
+                    while i < args.len() {
+                        let val = &args[i];
+                        if val.starts_with("--") || is_subcommand(val) {
+                            break;
+                        }
+                        if flag.arity == Arity::One && !flag_values.is_empty() {
+                            break;
+                        }
+                        // For flags that take values (like --exclude, --use), allow values starting with '-'
+                        // as they could be filenames/patterns
+                        flag_values.push(val.clone());
+                        i += 1;
+                    }
+                    if flag_values.is_empty() && matches!(flag.arity, Arity::One | Arity::Many) {
+                        return Err(missing_value_error(spec, flag));
+                    }
+                    // Repeated flags (e.g. `--set a=1 --set b=2`) accumulate their values
+                    flags.entry(flag.name.to_string()).or_default().extend(flag_values);
+                }
+            } else if next_arg.starts_with("-") && !next_arg.starts_with("--") && next_arg.len() > 1 {
+                // Single dash argument (like -y, -r, etc.)
+                // Only --<something> is interpreted as flags. Single dash arguments
+                // are treated as positional arguments (filenames/patterns) for subcommands
+                // that accept them, or rejected if the subcommand doesn't accept positional args.
+                if spec.takes_args() {
+                    // This could be a filename or pattern starting with '-', treat as positional arg
                     subcommand_args.push(next_arg.clone());
                     i += 1;
+                } else {
+                    // This subcommand doesn't accept positional args, so -X is clearly a short flag attempt
+                    return Err(format!(
+                        "Short flags (like '{}') are not supported.\n\
+                         Please use the long form instead (e.g., '--yes' instead of '-y').\n\n\
+                         Common short flag mappings:\n\
+                         \x20 -y, -Y  {arrow}  --yes\n\
+                         \x20 -o, -O  {arrow}  --overwrite\n\
+                         \x20 -r, -R  {arrow}  --recursive\n\
+                         \x20 -e, -E  {arrow}  --exclude\n\
+                         \x20 -h, -H  {arrow}  --help\n\
+                         \x20 -f, -F  {arrow}  --fullpath\n\
+                         \x20 -V, -v  {arrow}  --version",
+                        next_arg,
+                        arrow = Symbol::Arrow
+                    ));
                 }
+            } else {
+                // Regular argument
+                warnings.extend(check_positional_arg(spec, next_arg)?);
+                subcommand_args.push(next_arg.clone());
+                i += 1;
             }
-            
-            subcommands.push(ParsedSubcommand {
-                name: subcommand_name,
-                args: subcommand_args,
-                flags,
-            });
-        } else {
-            i += 1;
         }
+        
+        subcommands.push(ParsedSubcommand {
+            name: spec.name.to_string(),
+            args: subcommand_args,
+            flags,
+            warnings,
+        });
     }
     
-    subcommands
+    Ok(subcommands)
+}
+
+/// Looks up a flag (without the leading `--`) for a subcommand.
+///
+/// # Returns
+///
+/// * `Ok(&FlagSpec)` - The flag, if the subcommand accepts it (every subcommand accepts `--help`)
+/// * `Err(String)` - If the flag belongs to other subcommands only, or to none;
+///   the message suggests the closest flag of this subcommand when there is one
+fn lookup_flag(spec: &SubcommandSpec, flag_name: &str) -> Result<&'static FlagSpec, String> {
+    if let Some(flag) = spec.flag(flag_name) {
+        return Ok(flag);
+    }
+    if flag_name == HELP_FLAG.name {
+        return Ok(&HELP_FLAG);
+    }
+
+    if let Some(flag) = find_global_flag(flag_name) {
+        let usage = format!("--{} {}", flag.name, flag.value_name);
+        return Err(format!(
+            "'--{}' is a global option and must come before the first subcommand: frencli {} {} ...",
+            flag.name, usage.trim_end(), spec.name
+        ));
+    }
+
+    let owners: Vec<String> = SUBCOMMANDS.iter()
+        .filter(|other| other.flag(flag_name).is_some())
+        .map(|other| format!("'{}'", other.name))
+        .collect();
+    let hint = closest_match(flag_name, spec.flags.iter().map(|flag| flag.name))
+        .map(|flag| format!(" Did you mean '--{}'?", flag))
+        .unwrap_or_default();
+    let message = if owners.is_empty() {
+        format!("Unknown flag '--{}' for '{}'.", flag_name, spec.name)
+    } else {
+        format!("'--{}' is not a flag of '{}' (it belongs to {}).", flag_name, spec.name, owners.join(", "))
+    };
+    Err(format!("{}{} Run 'frencli {} --help' to see its flags.", message, hint, spec.name))
+}
+
+/// Builds the error for a value flag given without a value.
+fn missing_value_error(spec: &SubcommandSpec, flag: &FlagSpec) -> String {
+    format!("'--{}' requires a value: {} --{} {}", flag.name, spec.name, flag.name, flag.value_name)
+}
+
+/// Builds the error for a positional argument given to a subcommand that takes none.
+fn no_arguments_error(spec: &SubcommandSpec, arg: &str) -> String {
+    format!("'{}' does not take arguments (got '{}').", spec.name, arg)
+}
+
+/// Builds the error for an unknown flag, suggesting the closest known flag.
+fn unknown_flag_error(flag_name: &str) -> String {
+    let known = SUBCOMMANDS.iter()
+        .flat_map(|spec| spec.flags.iter())
+        .chain(GLOBAL_FLAGS.iter())
+        .chain(std::iter::once(&HELP_FLAG))
+        .map(|flag| flag.name);
+    let hint = closest_match(flag_name, known)
+        .map(|flag| format!(" Did you mean '--{}'?", flag))
+        .unwrap_or_default();
+    format!("Unknown flag '--{}'.{}", flag_name, hint)
+}
+
+/// Checks a positional argument that may be a mistyped subcommand.
+///
+/// Subcommands such as `apply` take no positional arguments at all, so a word
+/// close to a subcommand name (e.g. `aply`) is rejected as a typo. A `list` or
+/// `rename` pattern can be any word, so one without glob characters or `%`
+/// tokens that is close to a subcommand name is kept with a warning instead.
+/// Arguments after `--` are not checked, which silences the warning.
+///
+/// # Returns
+///
+/// * `Ok(Some(String))` - A warning for the caller to show
+/// * `Ok(None)` - If the argument does not look like a subcommand
+/// * `Err(String)` - If the subcommand takes no arguments
+fn check_positional_arg(spec: &SubcommandSpec, arg: &str) -> Result<Option<String>, String> {
+    let suggestion = closest_match(arg, subcommand_names());
+    if !spec.takes_args() {
+        return Err(match suggestion {
+            Some(name) => format!("Unknown subcommand '{}'. Did you mean '{}'?", arg, name),
+            None => no_arguments_error(spec, arg),
+        });
+    }
+    let kind = match spec.name {
+        "list" if !arg.contains(['*', '?', '[']) => "search",
+        "rename" if !arg.contains('%') => "rename",
+        _ => return Ok(None),
+    };
+    Ok(suggestion.map(|name| format!(
+        "'{}' is used as a {} pattern. Did you mean '{}'? (Put it after '--' to use it as a pattern without this warning.)",
+        arg, kind, name
+    )))
 }
 
 /// Gets a flag value, returning the first value if multiple exist
@@ -0,0 +1,74 @@
+//! "Did you mean ...?" suggestions for mistyped names.
+//!
+//! Used for template names, subcommand names and flag names, so a typo like
+//! `photo-dat` or `aply` gets a hint instead of a confusing failure.
+
+/// Computes the edit distance between two strings, case-insensitively.
+///
+/// Counts insertions, deletions, substitutions and swaps of two adjacent
+/// characters (so `aply` -> `apply` and `yse` -> `yes` are both one edit).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in distances[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+/// Finds the candidate closest to `input`, if any is close enough to be a likely typo.
+///
+/// A candidate is close enough when it is at most a third of the input's
+/// length (and at least one) edits away. Ties go to the earliest candidate.
+///
+/// # Arguments
+///
+/// * `input` - The mistyped name
+/// * `candidates` - The valid names
+///
+/// # Returns
+///
+/// * `Some(&str)` - The closest candidate
+/// * `None` - If no candidate is close enough
+pub fn closest_match<'a, I>(input: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let max_distance = (input.chars().count() / 3).max(1);
+    candidates.into_iter()
+        .map(|candidate| (edit_distance(input, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Formats a " Did you mean '...'?" hint to append to an error message.
+///
+/// # Returns
+///
+/// * `String` - The hint (with a leading space), or an empty string if no candidate is close
+pub fn did_you_mean<'a, I>(input: &str, candidates: I) -> String
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    closest_match(input, candidates)
+        .map(|candidate| format!(" Did you mean '{}'?", candidate))
+        .unwrap_or_default()
+}
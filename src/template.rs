@@ -1,9 +1,26 @@
-//! Template subcommand for listing and using template patterns.
+//! Template subcommand for listing, using and managing template patterns.
 //! 
 //! This module handles the `fren template` command which can list available
-//! templates or output a template pattern for use in rename operations.
+//! templates, output a template pattern for use in rename operations, preview
+//! templates against selected files, check templates for mistakes, save,
+//! delete or rename user templates in the user template file, and share
+//! templates as versioned bundles.
 
-use crate::templates::TemplateRegistry;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use freneng::RenamingEngine;
+use crate::rename::generate_chained_preview;
+use crate::lint::lint_template;
+use crate::suggest::did_you_mean;
+use crate::style;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use crate::templates::{Template, TemplateRegistry, TemplateSource, is_builtin_template};
+use crate::template_store::{
+    read_raw_templates, write_raw_templates, validate_template_name,
+    template_to_value, bundle_to_string, parse_bundle,
+};
 
 /// Handles the template subcommand.
 /// 
@@ -11,51 +28,636 @@ use crate::templates::TemplateRegistry;
 /// 
 /// * `template_registry` - The template registry
 /// * `list` - If true, list all available templates
-/// * `use_template` - If Some, output the pattern for the specified template
+/// * `use_template` - If Some, output the patterns of the specified template (name or index)
 /// 
 /// # Returns
 /// 
-/// * `Ok(Option<String>)` - If `use_template` is Some, returns the pattern; otherwise None
-/// * `Err(String)` - If template not found
+/// * `Ok(Option<Vec<String>>)` - If `use_template` is Some, returns the template's
+///   patterns in order, with parameters filled from their defaults; otherwise None
+/// * `Err(String)` - If the template is not found or has parameters without defaults
 pub fn handle_template_command(
     template_registry: &TemplateRegistry,
     list: bool,
     use_template: Option<String>,
-) -> Result<Option<String>, String> {
+) -> Result<Option<Vec<String>>, String> {
     if list {
-        // List all templates
-        let templates = template_registry.list();
-        println!("Available template patterns:\n");
-        for (i, (name, pattern)) in templates.iter().enumerate() {
-            println!("  {:2}. {:<25} -> {}", i + 1, name, pattern);
-        }
+        handle_template_list(template_registry, None, false)?;
         Ok(None)
     } else if let Some(template_name) = use_template {
-        // Use a specific template
-        // Check if it's a number (index)
-        if let Ok(index) = template_name.parse::<usize>() {
-            let templates = template_registry.list();
-            if index == 0 || index > templates.len() {
-                return Err(format!("Template index {} out of range (1-{})", index, templates.len()));
-            }
-            let (_, pattern) = templates[index - 1];
-            println!("{}", pattern);
-            Ok(Some(pattern.clone()))
-        } else {
-            // It's a template name
-            match template_registry.get(&template_name) {
-                Some(pattern) => {
-                    println!("{}", pattern);
-                    Ok(Some(pattern.clone()))
-                }
-                None => {
-                    Err(format!("Unknown template '{}'. Use 'template --list' to see all available templates.", template_name))
-                }
-            }
+        let (name, template) = template_registry.find(&template_name)?;
+        let patterns = template.fill(&[])
+            .map_err(|e| format!("Template '{}': {}", name, e))?;
+        for pattern in &patterns {
+            info!("{}", pattern);
         }
+        Ok(Some(patterns))
     } else {
         // No action specified
         Err("Template command requires either --list or --use <NAME|NUMBER>".to_string())
     }
 }
 
+#[derive(Serialize)]
+struct TemplateJsonItem<'a> {
+    index: usize,
+    name: &'a str,
+    pattern: &'a str,
+    chain: &'a [String],
+    source: &'static str,
+    category: Option<&'a str>,
+    description: Option<&'a str>,
+    example: Option<&'a str>,
+    params: BTreeMap<String, Option<&'a str>>,
+}
+
+/// Handles `template --list` - prints the available templates with their metadata.
+///
+/// Templates keep their position in the full list when filtered, so the printed
+/// numbers can always be passed to `template --use`.
+///
+/// # Arguments
+///
+/// * `template_registry` - The template registry
+/// * `category` - If Some, only list templates in this category (case-insensitive)
+/// * `json` - If true, output as JSON; if false, output as human-readable
+///
+/// # Returns
+///
+/// * `Ok(())` - Templates listed
+/// * `Err(String)` - If no template is in the requested category
+pub fn handle_template_list(
+    template_registry: &TemplateRegistry,
+    category: Option<&str>,
+    json: bool,
+) -> Result<(), String> {
+    let templates: Vec<(usize, &String, &Template)> = template_registry.templates().into_iter()
+        .enumerate()
+        .map(|(i, (name, template))| (i + 1, name, template))
+        .filter(|(_, _, template)| category.is_none_or(|category| template.in_category(category)))
+        .collect();
+
+    if let Some(category) = category
+        && templates.is_empty() {
+        return Err(format!(
+            "No templates in category '{}'. Available categories: {}",
+            category,
+            template_registry.categories().join(", ")
+        ));
+    }
+
+    if json {
+        let items: Vec<TemplateJsonItem> = templates.iter().map(|(index, name, template)| TemplateJsonItem {
+            index: *index,
+            name,
+            pattern: &template.pattern,
+            chain: &template.chain,
+            source: template.source.label(),
+            category: template.category.as_deref(),
+            description: template.description.as_deref(),
+            example: template.example.as_deref(),
+            params: template.parameters().into_iter()
+                .map(|param| {
+                    let default = template.defaults.get(&param).map(|d| d.as_str());
+                    (param, default)
+                })
+                .collect(),
+        }).collect();
+        let json_str = serde_json::to_string_pretty(&items)
+            .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+        println!("{}", json_str);
+        return Ok(());
+    }
+
+    // List templates with the layer and category each one comes from
+    info!("Available template patterns:\n");
+    for (index, name, template) in &templates {
+        let layer = format!("[{}]", template.source.label());
+        let category = template.category.as_deref().unwrap_or("-");
+        info!("  {:2}. {:<25} {:<10} {:<10} -> {}", index, name, layer, category, template.display_pattern());
+        match (&template.description, &template.example) {
+            (Some(description), Some(example)) => info!("      {} (e.g. {})", description, example),
+            (Some(description), None) => info!("      {}", description),
+            (None, Some(example)) => info!("      e.g. {}", example),
+            (None, None) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Handles `template --check` - lints templates and reports any problems.
+///
+/// # Arguments
+///
+/// * `template_registry` - The template registry
+/// * `names` - Template names or indices to check; empty for all templates
+///
+/// # Returns
+///
+/// * `Ok(())` - No problems found
+/// * `Err(String)` - If a name is unknown or any checked template has problems
+pub fn handle_template_check(template_registry: &TemplateRegistry, names: &[String]) -> Result<(), String> {
+    let templates: Vec<(&String, &Template)> = if names.is_empty() {
+        template_registry.templates()
+    } else {
+        names.iter()
+            .map(|name| template_registry.find(name))
+            .collect::<Result<_, _>>()?
+    };
+
+    // A template file that failed to load is a problem whichever templates are checked
+    let load_errors = template_registry.load_errors();
+    for error in load_errors {
+        info!("{}", error);
+    }
+    let mut failed = 0;
+    for (name, template) in &templates {
+        let problems = lint_template(template);
+        if problems.is_empty() {
+            continue;
+        }
+        failed += 1;
+        info!("{} [{}] -> {}", name, template.source.label(), template.display_pattern());
+        for problem in problems {
+            info!("  - {}", problem);
+        }
+    }
+
+    if !load_errors.is_empty() {
+        return Err(format!(
+            "{} template file(s) could not be loaded and {} of {} template(s) have problems.",
+            load_errors.len(), failed, templates.len()
+        ));
+    }
+    if failed > 0 {
+        return Err(format!("{} of {} template(s) have problems.", failed, templates.len()));
+    }
+    info!("Checked {} template(s): no problems found.", templates.len());
+    Ok(())
+}
+
+/// Prints a warning for each template file that could not be loaded, and for
+/// each user or project template that fails the lint checks.
+///
+/// Called after loading templates so mistakes in template files surface
+/// without running `template --check`.
+pub fn warn_template_problems(template_registry: &TemplateRegistry) {
+    for error in template_registry.load_errors() {
+        warning!("{}", style::warning(&format!("Warning: {}. Its templates were skipped.", error)));
+    }
+    for (name, template) in template_registry.templates() {
+        if template.source == TemplateSource::Builtin {
+            continue;
+        }
+        let problems = lint_template(template);
+        if let Some(first) = problems.first() {
+            let more = if problems.len() > 1 {
+                format!(" (and {} more)", problems.len() - 1)
+            } else {
+                String::new()
+            };
+            warning!("{}", style::warning(&format!(
+                "Warning: {} template '{}': {}{}. Run 'frencli template --check {}' for details.",
+                template.source.label(), name, first, more, name
+            )));
+        }
+    }
+}
+
+/// Number of selected files shown as sample columns by `template --try`
+pub const TRY_SAMPLE_FILES: usize = 3;
+
+/// Maximum width of a column in the `template --try` matrix
+const TRY_COLUMN_WIDTH: usize = 28;
+
+/// Handles `template --try` - previews templates against the selected files.
+///
+/// Each template is run through the renaming engine on the first
+/// `TRY_SAMPLE_FILES` files, and the new names are printed as one row per
+/// template. Templates with parameters that have no default are listed but
+/// not previewed.
+///
+/// # Arguments
+///
+/// * `engine` - The renaming engine
+/// * `template_registry` - The template registry
+/// * `files` - Files selected by `list`
+/// * `names` - Template names or indices to preview; empty for all templates
+/// * `category` - If Some, only preview templates in this category
+///
+/// # Returns
+///
+/// * `Ok(())` - Previews printed
+/// * `Err(String)` - If a named template is unknown or no template matches
+pub async fn handle_template_try(
+    engine: &RenamingEngine,
+    template_registry: &TemplateRegistry,
+    files: &[PathBuf],
+    names: &[String],
+    category: Option<&str>,
+) -> Result<(), String> {
+    let candidates: Vec<(&String, &Template)> = if names.is_empty() {
+        template_registry.templates()
+    } else {
+        names.iter()
+            .map(|name| template_registry.find(name))
+            .collect::<Result<_, _>>()?
+    };
+    let candidates: Vec<_> = candidates.into_iter()
+        .filter(|(_, template)| category.is_none_or(|category| template.in_category(category)))
+        .collect();
+    if candidates.is_empty() {
+        return Err(match category {
+            Some(category) => format!(
+                "No templates to try in category '{}'. Available categories: {}",
+                category,
+                template_registry.categories().join(", ")
+            ),
+            None => "No templates to try.".to_string(),
+        });
+    }
+
+    let samples = &files[..files.len().min(TRY_SAMPLE_FILES)];
+    // One row per template: sample outputs, plus a note for warnings or
+    // templates that could not be previewed
+    let mut rows: Vec<(String, Vec<String>, Option<String>)> = Vec::new();
+    for (name, template) in candidates {
+        let (cells, note) = match template.fill(&[]) {
+            Ok(patterns) => match generate_chained_preview(engine, samples, &patterns).await {
+                Ok((preview, _)) => {
+                    let cells = preview.renames.iter()
+                        .map(|rename| if rename.new_name.trim().is_empty() {
+                            "(empty)".to_string()
+                        } else {
+                            rename.new_name.clone()
+                        })
+                        .collect();
+                    let note = (!preview.warnings.is_empty())
+                        .then(|| format!("({} warning(s))", preview.warnings.len()));
+                    (cells, note)
+                }
+                Err(e) => (Vec::new(), Some(format!("(error: {})", e))),
+            },
+            Err(e) => {
+                let required: Vec<String> = template.parameters().into_iter()
+                    .filter(|param| !template.defaults.contains_key(param))
+                    .collect();
+                let note = if required.is_empty() {
+                    format!("(error: {})", e)
+                } else {
+                    format!("(needs --set {})", required.join(", "))
+                };
+                (Vec::new(), Some(note))
+            }
+        };
+        rows.push((name.clone(), cells, note));
+    }
+
+    let header: Vec<String> = samples.iter()
+        .map(|file| file.file_name().and_then(|n| n.to_str()).unwrap_or("?").to_string())
+        .collect();
+    let name_width = rows.iter()
+        .map(|(name, _, _)| name.chars().count())
+        .chain(std::iter::once("Template".len()))
+        .max()
+        .unwrap_or(0);
+    let column_widths: Vec<usize> = header.iter().enumerate()
+        .map(|(column, title)| {
+            rows.iter()
+                .filter_map(|(_, cells, _)| cells.get(column))
+                .chain(std::iter::once(title))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+                .min(TRY_COLUMN_WIDTH)
+        })
+        .collect();
+
+    info!("Previewing {} template(s) on {} of {} file(s):\n", rows.len(), samples.len(), files.len());
+    print_try_row("Template", &header, None, name_width, &column_widths);
+    let separator_width = name_width + column_widths.iter().map(|w| w + 2).sum::<usize>();
+    info!("  {:-<width$}", "", width = separator_width);
+    for (name, cells, note) in &rows {
+        print_try_row(name, cells, note.as_deref(), name_width, &column_widths);
+    }
+    info!("\nUse one with: template --use <NAME|NUMBER>");
+    Ok(())
+}
+
+/// Prints one row of the `template --try` matrix, truncating long cells.
+fn print_try_row(name: &str, cells: &[String], note: Option<&str>, name_width: usize, column_widths: &[usize]) {
+    let mut line = format!("  {:<width$}", name, width = name_width);
+    for (cell, &width) in cells.iter().zip(column_widths) {
+        line.push_str(&format!("  {:<width$}", truncate_cell(cell, width), width = width));
+    }
+    if let Some(note) = note {
+        line.push_str("  ");
+        line.push_str(note);
+    }
+    info!("{}", line.trim_end());
+}
+
+/// Shortens `text` to at most `width` characters, marking the cut with `...`.
+fn truncate_cell(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let keep = width.saturating_sub(3);
+    format!("{}...", text.chars().take(keep).collect::<String>())
+}
+
+/// Handles `template --save` - stores a pattern as a user template.
+/// 
+/// # Arguments
+/// 
+/// * `store_path` - Path of the user template file
+/// * `name` - Name to save the pattern under
+/// * `pattern` - The rename pattern, optionally with `{name}` parameters
+/// * `defaults` - Default values for parameters in `pattern` (from `--set`)
+/// * `force` - Replace an existing user template or override a built-in one
+/// 
+/// # Returns
+/// 
+/// * `Ok(())` - Template saved
+/// * `Err(String)` - If the name, pattern or defaults are invalid, the name is already taken (without `force`), or the file cannot be written
+pub fn handle_template_save(
+    store_path: &Path,
+    name: &str,
+    pattern: &str,
+    defaults: &[(String, String)],
+    force: bool,
+) -> Result<(), String> {
+    validate_template_name(name)?;
+    if pattern.trim().is_empty() {
+        return Err("Template pattern cannot be empty.".to_string());
+    }
+    let parameters = Template::new(pattern, TemplateSource::User).parameters();
+    if let Some((key, _)) = defaults.iter().find(|(key, _)| !parameters.contains(key)) {
+        return Err(format!("Parameter '{}' is not used in pattern '{}'.", key, pattern));
+    }
+
+    let mut templates = read_raw_templates(store_path)?;
+    if !force {
+        check_name_available(&templates, name)?;
+    }
+
+    let entry = if defaults.is_empty() {
+        Value::String(pattern.to_string())
+    } else {
+        let params: Map<String, Value> = defaults.iter()
+            .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+            .collect();
+        let mut fields = Map::new();
+        fields.insert("pattern".to_string(), Value::String(pattern.to_string()));
+        fields.insert("params".to_string(), Value::Object(params));
+        Value::Object(fields)
+    };
+    templates.insert(name.to_string(), entry);
+    write_raw_templates(store_path, templates)?;
+
+    info!("Saved template '{}' -> {}", name, pattern);
+    Ok(())
+}
+
+/// Handles `template --delete` - removes a user template.
+/// 
+/// # Arguments
+/// 
+/// * `store_path` - Path of the user template file
+/// * `name` - Name of the user template to delete
+/// 
+/// # Returns
+/// 
+/// * `Ok(())` - Template deleted
+/// * `Err(String)` - If no user template has that name or the file cannot be written
+pub fn handle_template_delete(store_path: &Path, name: &str) -> Result<(), String> {
+    let mut templates = read_raw_templates(store_path)?;
+    if templates.remove(name).is_none() {
+        return Err(not_a_user_template(&templates, name, "deleted"));
+    }
+    write_raw_templates(store_path, templates)?;
+
+    info!("Deleted template '{}'.", name);
+    if is_builtin_template(name) {
+        info!("The built-in template '{}' is available again.", name);
+    }
+    Ok(())
+}
+
+/// Handles `template --rename` - renames a user template.
+/// 
+/// # Arguments
+/// 
+/// * `store_path` - Path of the user template file
+/// * `old_name` - Current name of the user template
+/// * `new_name` - New name for the template
+/// * `force` - Replace an existing user template or override a built-in one named `new_name`
+/// 
+/// # Returns
+/// 
+/// * `Ok(())` - Template renamed
+/// * `Err(String)` - If the template does not exist, the new name is taken (without `force`), or the file cannot be written
+pub fn handle_template_rename(
+    store_path: &Path,
+    old_name: &str,
+    new_name: &str,
+    force: bool,
+) -> Result<(), String> {
+    validate_template_name(new_name)?;
+
+    let mut templates = read_raw_templates(store_path)?;
+    if !templates.contains_key(old_name) {
+        return Err(not_a_user_template(&templates, old_name, "renamed"));
+    }
+    if old_name == new_name {
+        return Err(format!("Template '{}' already has that name.", old_name));
+    }
+    if !force {
+        check_name_available(&templates, new_name)?;
+    }
+
+    let entry = templates.remove(old_name).unwrap_or(Value::Null);
+    templates.insert(new_name.to_string(), entry);
+    write_raw_templates(store_path, templates)?;
+
+    info!("Renamed template '{}' -> '{}'.", old_name, new_name);
+    Ok(())
+}
+
+/// Errors if `name` is already used by a user template or a built-in template.
+fn check_name_available(templates: &serde_json::Map<String, Value>, name: &str) -> Result<(), String> {
+    if templates.contains_key(name) {
+        return Err(format!("User template '{}' already exists. Use --force to replace it.", name));
+    }
+    if is_builtin_template(name) {
+        return Err(format!("'{}' is a built-in template. Use --force to override it with a user template.", name));
+    }
+    Ok(())
+}
+
+/// Builds the error for an attempt to modify a template that is not in the user template file.
+fn not_a_user_template(user_templates: &Map<String, Value>, name: &str, action: &str) -> String {
+    if is_builtin_template(name) {
+        format!("'{}' is a built-in template and cannot be {}.", name, action)
+    } else {
+        format!(
+            "No user template named '{}'.{}",
+            name,
+            did_you_mean(name, user_templates.keys().map(|k| k.as_str()))
+        )
+    }
+}
+
+/// How `template --import` treats bundle templates whose name is already taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Keep the existing template and ignore the imported one
+    Skip,
+    /// Replace the existing template with the imported one
+    Overwrite,
+    /// Import under a free name such as `name-2`
+    Rename,
+}
+
+impl ConflictPolicy {
+    /// Parses the value of `--on-conflict`.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "skip" => Ok(ConflictPolicy::Skip),
+            "overwrite" => Ok(ConflictPolicy::Overwrite),
+            // Not plain "rename", which the parser would take for the subcommand
+            "rename-on-import" => Ok(ConflictPolicy::Rename),
+            _ => Err(format!("Invalid --on-conflict value '{}'. Use skip, overwrite or rename-on-import.", value)),
+        }
+    }
+}
+
+/// Handles `template --export` - writes templates to a bundle file.
+/// 
+/// # Arguments
+/// 
+/// * `template_registry` - The template registry
+/// * `bundle_path` - File to write, or `-` for stdout
+/// * `names` - Template names or indices to export; empty for all user and project templates
+/// 
+/// # Returns
+/// 
+/// * `Ok(usize)` - Number of exported templates
+/// * `Err(String)` - If a name is unknown, there is nothing to export, or the file cannot be written
+pub fn handle_template_export(
+    template_registry: &TemplateRegistry,
+    bundle_path: &str,
+    names: &[String],
+) -> Result<usize, String> {
+    let selected: Vec<(&String, &Template)> = if names.is_empty() {
+        template_registry.templates().into_iter()
+            .filter(|(_, template)| template.source != TemplateSource::Builtin)
+            .collect()
+    } else {
+        names.iter()
+            .map(|name| template_registry.find(name))
+            .collect::<Result<_, _>>()?
+    };
+    if selected.is_empty() {
+        return Err("No user or project templates to export. Name the templates to export, e.g. 'template --export FILE photo-date'.".to_string());
+    }
+
+    let templates: Map<String, Value> = selected.iter()
+        .map(|(name, template)| (name.to_string(), template_to_value(template)))
+        .collect();
+    let count = templates.len();
+    let bundle = bundle_to_string(templates)?;
+
+    if bundle_path == "-" {
+        print!("{}", bundle);
+    } else {
+        fs::write(bundle_path, bundle)
+            .map_err(|e| format!("Failed to write bundle '{}': {}", bundle_path, e))?;
+        info!("Exported {} template(s) to {}", count, bundle_path);
+    }
+    Ok(count)
+}
+
+/// Handles `template --import` - adds the templates of a bundle to the user template file.
+/// 
+/// A bundle template conflicts with an existing one when any layer (built-in,
+/// user or project) already has a template with that name. Without a policy,
+/// conflicts abort the import before anything is written.
+/// 
+/// # Arguments
+/// 
+/// * `template_registry` - The template registry, used to detect conflicts
+/// * `store_path` - Path of the user template file
+/// * `bundle_path` - Bundle file to read, or `-` for stdin
+/// * `on_conflict` - How to treat conflicting names, if given
+/// 
+/// # Returns
+/// 
+/// * `Ok(usize)` - Number of imported templates
+/// * `Err(String)` - If the bundle is invalid, names conflict without a policy, or the file cannot be written
+pub fn handle_template_import(
+    template_registry: &TemplateRegistry,
+    store_path: &Path,
+    bundle_path: &str,
+    on_conflict: Option<ConflictPolicy>,
+) -> Result<usize, String> {
+    let content = if bundle_path == "-" {
+        std::io::read_to_string(std::io::stdin())
+            .map_err(|e| format!("Failed to read bundle from stdin: {}", e))?
+    } else {
+        fs::read_to_string(bundle_path)
+            .map_err(|e| format!("Failed to read bundle '{}': {}", bundle_path, e))?
+    };
+    let bundle = parse_bundle(&content)
+        .map_err(|e| format!("Invalid template bundle '{}': {}", bundle_path, e))?;
+
+    let mut templates = read_raw_templates(store_path)?;
+    let is_taken = |templates: &Map<String, Value>, name: &str| {
+        templates.contains_key(name) || template_registry.get_template(name).is_some()
+    };
+
+    let conflicts: Vec<&String> = bundle.keys().filter(|name| is_taken(&templates, name)).collect();
+    let policy = match on_conflict {
+        Some(policy) => policy,
+        None if conflicts.is_empty() => ConflictPolicy::Skip,
+        None => return Err(format!(
+            "{} template(s) in the bundle already exist: {}.\nUse --on-conflict skip|overwrite|rename-on-import to choose how to import them.",
+            conflicts.len(),
+            conflicts.iter().map(|name| name.as_str()).collect::<Vec<_>>().join(", ")
+        )),
+    };
+
+    let mut imported = 0;
+    for (name, entry) in bundle {
+        if !is_taken(&templates, &name) {
+            info!("  + {}", name);
+            templates.insert(name, entry);
+            imported += 1;
+            continue;
+        }
+        match policy {
+            ConflictPolicy::Skip => info!("  = {} (skipped, already exists)", name),
+            ConflictPolicy::Overwrite => {
+                if template_registry.get_template(&name).is_some_and(|t| t.source == TemplateSource::Project) {
+                    info!("  ~ {} (overwritten; the project template still takes precedence here)", name);
+                } else {
+                    info!("  ~ {} (overwritten)", name);
+                }
+                templates.insert(name, entry);
+                imported += 1;
+            }
+            ConflictPolicy::Rename => {
+                let new_name = (2..)
+                    .map(|n| format!("{}-{}", name, n))
+                    .find(|candidate| !is_taken(&templates, candidate))
+                    .unwrap_or_default();
+                info!("  + {} (renamed from {})", new_name, name);
+                templates.insert(new_name, entry);
+                imported += 1;
+            }
+        }
+    }
+
+    write_raw_templates(store_path, templates)?;
+    info!("Imported {} template(s) into {}", imported, store_path.display());
+    Ok(imported)
+}
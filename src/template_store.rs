@@ -0,0 +1,463 @@
+//! Persistent storage for user-defined and project-local templates.
+//!
+//! User templates live in `$XDG_CONFIG_HOME/frencli/templates.json` (falling
+//! back to `~/.config/frencli/templates.json`), or in `templates.toml` in the
+//! same directory when that file exists. Project templates live in a
+//! `.frentemplates` file in the working directory or one of its ancestors,
+//! written in JSON or TOML (the format is detected from the content).
+//! Both files share the same layout: each entry maps a template name to a
+//! pattern string, to a list of patterns applied in sequence (a chained
+//! template), or to an object with a `pattern` field (a string or a list), optional
+//! parameter defaults (`null` marks a parameter that must be set) and optional
+//! `description`, `category` and `example` strings shown by `template --list`:
+//!
+//! ```json
+//! {
+//!   "templates": {
+//!     "podcast": "%T%N_%C3.%E",
+//!     "clean": ["%L%N.%E", "%N%R/ /_.%E", "%N_%C3.%E"],
+//!     "trip": {
+//!       "pattern": "{prefix}_%C{width}.%E",
+//!       "params": { "prefix": null, "width": "3" },
+//!       "description": "Number trip photos",
+//!       "category": "photo",
+//!       "example": "IMG_0042.jpg -> rome_001.jpg"
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! A TOML file has the same layout. TOML has no `null`, so a parameter that
+//! must be set is simply left out of `params`:
+//!
+//! ```toml
+//! [templates]
+//! podcast = "%T%N_%C3.%E"
+//! clean = ["%L%N.%E", "%N%R/ /_.%E", "%N_%C3.%E"]
+//!
+//! [templates.trip]
+//! pattern = "{prefix}_%C{width}.%E"
+//! params = { width = "3" }
+//! description = "Number trip photos"
+//! ```
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::{Map, Value};
+use crate::templates::{Template, TemplateSource};
+
+/// File name of the user template file inside the frencli config directory
+pub const USER_TEMPLATES_FILE: &str = "templates.json";
+
+/// File name of the TOML user template file, used instead of [`USER_TEMPLATES_FILE`] when it exists
+pub const USER_TEMPLATES_TOML_FILE: &str = "templates.toml";
+
+/// File name of a project template file, looked up from the working directory upward
+pub const PROJECT_TEMPLATES_FILE: &str = ".frentemplates";
+
+/// Value of the `format` field that identifies a template bundle
+pub const BUNDLE_FORMAT: &str = "frencli-templates";
+
+/// Newest template bundle version this build can read (and the version it writes)
+pub const BUNDLE_VERSION: u64 = 1;
+
+/// Returns the frencli configuration directory.
+///
+/// Uses `$XDG_CONFIG_HOME/frencli` when set, otherwise `~/.config/frencli`
+/// (or `%APPDATA%\frencli` on systems without `HOME`).
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(xdg) = env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(xdg).join("frencli"));
+    }
+    if let Some(home) = env::var_os("HOME").filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(home).join(".config").join("frencli"));
+    }
+    env::var_os("APPDATA")
+        .filter(|v| !v.is_empty())
+        .map(|appdata| PathBuf::from(appdata).join("frencli"))
+}
+
+/// Returns the path of the user template file, if a config directory is known.
+///
+/// This is `templates.toml` if it exists, otherwise `templates.json` (which
+/// need not exist yet).
+pub fn user_templates_path() -> Option<PathBuf> {
+    config_dir().map(|dir| {
+        let toml_path = dir.join(USER_TEMPLATES_TOML_FILE);
+        if toml_path.is_file() {
+            toml_path
+        } else {
+            dir.join(USER_TEMPLATES_FILE)
+        }
+    })
+}
+
+/// Format of a template file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateFormat {
+    Json,
+    Toml,
+}
+
+impl TemplateFormat {
+    /// Chooses the format from the file extension: `.toml` files are TOML and
+    /// `.json` files JSON. Files without either extension (such as
+    /// `.frentemplates`) return `None`; their format is detected from the content.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Some(TemplateFormat::Toml),
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Some(TemplateFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// Parses the contents of a template file in `format`, or detects the format if it is `None`.
+    ///
+    /// Detection tries JSON first, then TOML. If neither parses, the error is
+    /// reported for JSON when the content starts with `{`, and for TOML otherwise.
+    fn parse_as(format: Option<Self>, content: &str) -> Result<Value, String> {
+        match format {
+            Some(format) => format.parse(content),
+            None => TemplateFormat::Json.parse(content).or_else(|json_error| {
+                TemplateFormat::Toml.parse(content).map_err(|toml_error| {
+                    if content.trim_start().starts_with('{') { json_error } else { toml_error }
+                })
+            }),
+        }
+    }
+
+    /// Parses the contents of a template file into a JSON value.
+    fn parse(self, content: &str) -> Result<Value, String> {
+        match self {
+            TemplateFormat::Json => serde_json::from_str(content)
+                .map_err(|e| format!("invalid JSON: {}", e)),
+            TemplateFormat::Toml => {
+                let table: toml::Table = toml::from_str(content).map_err(|e| match e.span() {
+                    Some(span) => {
+                        let line = content[..span.start].matches('\n').count() + 1;
+                        format!("invalid TOML: line {}: {}", line, e.message().trim_end())
+                    }
+                    None => format!("invalid TOML: {}", e.message().trim_end()),
+                })?;
+                serde_json::to_value(table).map_err(|e| format!("invalid TOML: {}", e))
+            }
+        }
+    }
+
+    /// Formats a template file document in this format.
+    fn format(self, mut root: Value) -> Result<String, String> {
+        match self {
+            TemplateFormat::Json => serde_json::to_string_pretty(&root)
+                .map(|json| json + "\n")
+                .map_err(|e| format!("Failed to serialize templates: {}", e)),
+            TemplateFormat::Toml => {
+                // TOML has no null; a parameter without a default is the same as a missing one
+                remove_nulls(&mut root);
+                toml::to_string_pretty(&root)
+                    .map_err(|e| format!("Failed to serialize templates: {}", e))
+            }
+        }
+    }
+}
+
+/// Removes `null` values from the objects in `value`, recursively.
+fn remove_nulls(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            fields.retain(|_, field| !field.is_null());
+            fields.values_mut().for_each(remove_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(remove_nulls),
+        _ => {}
+    }
+}
+
+/// Finds the nearest project template file, walking from `start` up to the filesystem root.
+///
+/// # Arguments
+///
+/// * `start` - Directory to start searching from (usually the working directory)
+///
+/// # Returns
+///
+/// * `Some(PathBuf)` - Path of the closest `.frentemplates` file
+/// * `None` - If no ancestor directory contains one
+pub fn find_project_templates(start: &Path) -> Option<PathBuf> {
+    start.ancestors()
+        .map(|dir| dir.join(PROJECT_TEMPLATES_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Checks that a template name can be stored and referenced on the command line.
+///
+/// Names must be non-empty, contain no whitespace, and must not be plain numbers
+/// (numbers select templates by their index in `template --list`).
+pub fn validate_template_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("template name cannot be empty".to_string());
+    }
+    if name.chars().any(|c| c.is_whitespace()) {
+        return Err(format!("template name '{}' cannot contain whitespace", name));
+    }
+    if name.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("template name '{}' cannot be a number (numbers select templates by index)", name));
+    }
+    Ok(())
+}
+
+/// Parses the contents of a template file.
+///
+/// # Arguments
+///
+/// * `content` - JSON or TOML contents of the file
+/// * `source` - Source recorded on the parsed templates
+///
+/// # Returns
+///
+/// * `Ok(Vec<(String, Template)>)` - Template names and definitions, sorted by name
+/// * `Err(String)` - Description of the first malformed entry
+pub fn parse_template_file(content: &str, source: TemplateSource) -> Result<Vec<(String, Template)>, String> {
+    parse_templates(&TemplateFormat::parse_as(None, content)?, source)
+}
+
+/// Parses the templates of a parsed template file.
+fn parse_templates(root: &Value, source: TemplateSource) -> Result<Vec<(String, Template)>, String> {
+    let templates = root.get("templates")
+        .ok_or("expected a top-level \"templates\" object")?
+        .as_object()
+        .ok_or("\"templates\" must be an object mapping names to patterns")?;
+
+    let mut entries = Vec::new();
+    for (name, value) in templates {
+        validate_template_name(name)?;
+        let template = parse_entry(value, source)
+            .map_err(|e| format!("template '{}': {}", name, e))?;
+        entries.push((name.clone(), template));
+    }
+
+    Ok(entries)
+}
+
+/// Reads and parses a template file from disk.
+///
+/// # Arguments
+///
+/// * `path` - Path to the template file; its extension chooses the format (see [`TemplateFormat::from_path`])
+/// * `source` - Source recorded on the loaded templates
+///
+/// # Returns
+///
+/// * `Ok(Vec<(String, Template)>)` - Template names and definitions
+/// * `Err(String)` - If the file cannot be read or contains malformed entries
+pub fn read_template_file(path: &Path, source: TemplateSource) -> Result<Vec<(String, Template)>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read template file '{}': {}", path.display(), e))?;
+    TemplateFormat::parse_as(TemplateFormat::from_path(path), &content)
+        .and_then(|root| parse_templates(&root, source))
+        .map_err(|e| format!("Invalid template file '{}': {}", path.display(), e))
+}
+
+/// Reads the raw template entries of a template file for editing.
+///
+/// A missing file yields an empty map so the first saved template creates it.
+/// Entries are returned unparsed so that fields frencli does not interpret are
+/// preserved when the file is written back, and so that a malformed entry can
+/// still be deleted or replaced. Only a file whose JSON or TOML layout cannot
+/// be read is refused, since writing it back would drop its contents.
+pub fn read_raw_templates(path: &Path) -> Result<Map<String, Value>, String> {
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read template file '{}': {}", path.display(), e))?;
+
+    let root = TemplateFormat::parse_as(TemplateFormat::from_path(path), &content)
+        .map_err(|e| format!("Invalid template file '{}': {}", path.display(), e))?;
+    match root.get("templates") {
+        Some(Value::Object(templates)) => Ok(templates.clone()),
+        _ => Err(format!(
+            "Invalid template file '{}': expected a top-level \"templates\" object",
+            path.display()
+        )),
+    }
+}
+
+/// Writes template entries to a template file, creating its directory if needed.
+///
+/// The file is written as TOML if its extension is `.toml`, and as JSON otherwise.
+pub fn write_raw_templates(path: &Path, templates: Map<String, Value>) -> Result<(), String> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory '{}': {}", parent.display(), e))?;
+    }
+
+    let mut root = Map::new();
+    root.insert("templates".to_string(), Value::Object(templates));
+    let format = TemplateFormat::from_path(path).unwrap_or(TemplateFormat::Json);
+    let content = format.format(Value::Object(root))?;
+    fs::write(path, content)
+        .map_err(|e| format!("Failed to write template file '{}': {}", path.display(), e))
+}
+
+/// Parses a single template entry.
+fn parse_entry(value: &Value, source: TemplateSource) -> Result<Template, String> {
+    let empty = Map::new();
+    let (pattern, fields) = match value {
+        Value::Object(fields) => match fields.get("pattern") {
+            Some(pattern) => (pattern, fields),
+            None => return Err("missing \"pattern\" field".to_string()),
+        },
+        _ => (value, &empty),
+    };
+
+    let mut patterns = parse_patterns(pattern)?.into_iter();
+    let mut template = Template::new(patterns.next().unwrap_or_default(), source);
+    template.chain = patterns.collect();
+    let parameters = template.parameters();
+
+    match fields.get("params") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(params)) => {
+            template.defaults = parse_param_defaults(params, &parameters)?;
+        }
+        Some(_) => return Err("\"params\" must be an object mapping parameter names to default values".to_string()),
+    }
+
+    template.description = optional_string(fields, "description")?;
+    template.category = optional_string(fields, "category")?;
+    template.example = optional_string(fields, "example")?;
+
+    Ok(template)
+}
+
+/// Reads an optional string field of a template entry.
+fn optional_string(fields: &Map<String, Value>, key: &str) -> Result<Option<String>, String> {
+    match fields.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(_) => Err(format!("\"{}\" must be a string", key)),
+    }
+}
+
+/// Parses a pattern value: a single pattern string or a list of patterns applied in order.
+fn parse_patterns(value: &Value) -> Result<Vec<String>, String> {
+    let patterns = match value {
+        Value::String(pattern) => vec![pattern.clone()],
+        Value::Array(stages) if !stages.is_empty() => stages.iter()
+            .map(|stage| stage.as_str()
+                .map(|s| s.to_string())
+                .ok_or_else(|| "every pattern in a chain must be a string".to_string()))
+            .collect::<Result<_, _>>()?,
+        Value::Array(_) => return Err("pattern list cannot be empty".to_string()),
+        _ => return Err("expected a pattern string, a list of patterns, or an object with a \"pattern\" field".to_string()),
+    };
+
+    if patterns.iter().any(|pattern| pattern.trim().is_empty()) {
+        return Err("pattern cannot be empty".to_string());
+    }
+    Ok(patterns)
+}
+
+/// Parses the `params` object of a template entry into default values.
+fn parse_param_defaults(params: &Map<String, Value>, parameters: &[String]) -> Result<BTreeMap<String, String>, String> {
+    let mut defaults = BTreeMap::new();
+    for (name, value) in params {
+        if !parameters.contains(name) {
+            return Err(format!("parameter '{}' is not used in the pattern", name));
+        }
+        match value {
+            Value::Null => {}
+            Value::String(default) => {
+                defaults.insert(name.clone(), default.clone());
+            }
+            Value::Number(default) => {
+                defaults.insert(name.clone(), default.to_string());
+            }
+            _ => return Err(format!("default for parameter '{}' must be a string, a number or null", name)),
+        }
+    }
+    Ok(defaults)
+}
+
+/// Converts a template back into a template file entry.
+///
+/// Plain templates become a pattern string; templates with a chain,
+/// parameters or metadata become an object.
+pub fn template_to_value(template: &Template) -> Value {
+    let pattern = if template.chain.is_empty() {
+        Value::String(template.pattern.clone())
+    } else {
+        Value::Array(template.patterns().into_iter().map(|p| Value::String(p.to_string())).collect())
+    };
+
+    let parameters = template.parameters();
+    let has_metadata = template.description.is_some() || template.category.is_some() || template.example.is_some();
+    if parameters.is_empty() && !has_metadata {
+        return pattern;
+    }
+
+    let mut fields = Map::new();
+    fields.insert("pattern".to_string(), pattern);
+    if !parameters.is_empty() {
+        let params: Map<String, Value> = parameters.into_iter()
+            .map(|param| {
+                let default = template.defaults.get(&param)
+                    .map(|d| Value::String(d.clone()))
+                    .unwrap_or(Value::Null);
+                (param, default)
+            })
+            .collect();
+        fields.insert("params".to_string(), Value::Object(params));
+    }
+    for (key, value) in [("description", &template.description), ("category", &template.category), ("example", &template.example)] {
+        if let Some(value) = value {
+            fields.insert(key.to_string(), Value::String(value.clone()));
+        }
+    }
+    Value::Object(fields)
+}
+
+/// Serializes template entries as a versioned template bundle.
+pub fn bundle_to_string(templates: Map<String, Value>) -> Result<String, String> {
+    let mut root = Map::new();
+    root.insert("format".to_string(), Value::String(BUNDLE_FORMAT.to_string()));
+    root.insert("version".to_string(), Value::from(BUNDLE_VERSION));
+    root.insert("templates".to_string(), Value::Object(templates));
+    serde_json::to_string_pretty(&Value::Object(root))
+        .map(|json| json + "\n")
+        .map_err(|e| format!("Failed to serialize templates: {}", e))
+}
+
+/// Parses a template bundle, checking its format marker, version and entries.
+///
+/// # Returns
+///
+/// * `Ok(Map<String, Value>)` - The bundle's raw template entries, keyed by name
+/// * `Err(String)` - If the content is not a bundle, is from a newer version, or has malformed entries
+pub fn parse_bundle(content: &str) -> Result<Map<String, Value>, String> {
+    let root: Value = serde_json::from_str(content)
+        .map_err(|e| format!("invalid JSON: {}", e))?;
+
+    match root.get("format") {
+        Some(Value::String(format)) if format == BUNDLE_FORMAT => {}
+        _ => return Err(format!("not a template bundle (expected \"format\": \"{}\")", BUNDLE_FORMAT)),
+    }
+    match root.get("version").and_then(Value::as_u64) {
+        Some(version) if (1..=BUNDLE_VERSION).contains(&version) => {}
+        Some(version) => return Err(format!(
+            "bundle version {} is not supported (this frencli reads up to version {}); upgrade frencli to import it",
+            version, BUNDLE_VERSION
+        )),
+        None => return Err("missing or invalid \"version\" field".to_string()),
+    }
+
+    // Same entry rules as template files
+    parse_template_file(content, TemplateSource::User)?;
+    match root.get("templates") {
+        Some(Value::Object(templates)) => Ok(templates.clone()),
+        _ => Ok(Map::new()),
+    }
+}
@@ -1,67 +1,486 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use crate::suggest::did_you_mean;
+use crate::template_store::{read_template_file, user_templates_path, find_project_templates};
+use crate::workdir::absolute_dir;
+
+/// A template shipped with frencli, with the metadata shown by `template --list`
+struct BuiltinTemplate {
+    name: &'static str,
+    pattern: &'static str,
+    category: &'static str,
+    description: &'static str,
+    example: &'static str,
+}
+
+const fn builtin(
+    name: &'static str,
+    pattern: &'static str,
+    category: &'static str,
+    description: &'static str,
+    example: &'static str,
+) -> BuiltinTemplate {
+    BuiltinTemplate { name, pattern, category, description, example }
+}
+
+/// Built-in templates, grouped by category
+const BUILTIN_TEMPLATES: &[BuiltinTemplate] = &[
+    // Photo/Image templates
+    builtin("photo-date", "%N_%D.%E", "photo",
+        "Append today's date to the photo name",
+        "IMG_0042.jpg -> IMG_0042_2026-01-03.jpg"),
+    builtin("photo-counter", "photo_%C3.%E", "photo",
+        "Number photos sequentially",
+        "IMG_0042.jpg -> photo_001.jpg"),
+    builtin("photo-datetime", "%N_%FD_%FH.%E", "photo",
+        "Append the file's modification date and time",
+        "IMG_0042.jpg -> IMG_0042_2026-01-03_14-30-00.jpg"),
+
+    // Document templates
+    builtin("doc-date", "%N_%D.%E", "document",
+        "Append today's date to the document name",
+        "report.pdf -> report_2026-01-03.pdf"),
+    builtin("doc-counter", "document_%C2.%E", "document",
+        "Number documents sequentially",
+        "report.pdf -> document_01.pdf"),
+
+    // Case templates: the plain ones change the whole name, the -name ones only
+    // the part before the extension. Case modifiers also apply to everything
+    // after them, so the -name templates append the original extension with %X
+    builtin("lowercase", "%L%N.%E", "case",
+        "Lowercase the whole file name, extension included",
+        "My Photo.JPG -> my photo.jpg"),
+    builtin("lowercase-name", "%N%L.%X/$/%E", "case",
+        "Lowercase the name only, keeping the extension as it is",
+        "My Photo.JPG -> my photo.JPG"),
+
+    builtin("uppercase", "%U%N.%E", "case",
+        "Uppercase the whole file name, extension included",
+        "My Photo.jpg -> MY PHOTO.JPG"),
+    builtin("uppercase-name", "%N%U.%X/$/%E", "case",
+        "Uppercase the name only, keeping the extension as it is",
+        "My Photo.jpg -> MY PHOTO.jpg"),
+
+    builtin("title-case", "%T%N.%E", "case",
+        "Title-case the whole file name, extension included",
+        "my holiday photo.JPG -> My Holiday Photo.Jpg"),
+    builtin("title-case-name", "%N%T.%X/$/%E", "case",
+        "Title-case the name only, keeping the extension as it is",
+        "my holiday photo.JPG -> My Holiday Photo.JPG"),
+
+    // Parent directory templates
+    builtin("parent-prefix", "%P_%N.%E", "parent",
+        "Prefix the name with the parent directory name",
+        "holiday/IMG_0042.jpg -> holiday_IMG_0042.jpg"),
+    builtin("parent-suffix", "%N_%P.%E", "parent",
+        "Suffix the name with the parent directory name",
+        "holiday/IMG_0042.jpg -> IMG_0042_holiday.jpg"),
+
+    // Counter templates
+    builtin("counter-2", "%C2.%E", "counter",
+        "Replace the name with a 2-digit counter",
+        "notes.txt -> 01.txt"),
+    builtin("counter-3", "%C3.%E", "counter",
+        "Replace the name with a 3-digit counter",
+        "notes.txt -> 001.txt"),
+    builtin("counter-4", "%C4.%E", "counter",
+        "Replace the name with a 4-digit counter",
+        "notes.txt -> 0001.txt"),
+    builtin("counter-prefix", "%C3_%N.%E", "counter",
+        "Prefix the name with a 3-digit counter",
+        "notes.txt -> 001_notes.txt"),
+    builtin("counter-suffix", "%N_%C3.%E", "counter",
+        "Suffix the name with a 3-digit counter",
+        "notes.txt -> notes_001.txt"),
+
+    // Date/time templates
+    builtin("date-suffix", "%N_%D.%E", "date",
+        "Append today's date",
+        "notes.txt -> notes_2026-01-03.txt"),
+    builtin("date-prefix", "%D_%N.%E", "date",
+        "Prefix the name with today's date",
+        "notes.txt -> 2026-01-03_notes.txt"),
+    builtin("datetime-suffix", "%N_%D_%H.%E", "date",
+        "Append the current date and time",
+        "notes.txt -> notes_2026-01-03_14-30-00.txt"),
+
+    // Cleanup templates
+    builtin("trim-spaces", "%N%M.%E", "cleanup",
+        "Trim leading and trailing whitespace from the name",
+        " draft notes .txt -> draft notes.txt"),
+    builtin("underscore-to-dash", "%N%R/_/-.%E", "cleanup",
+        "Replace underscores in the name with dashes",
+        "annual_report.pdf -> annual-report.pdf"),
+    builtin("dash-to-underscore", "%N%R/-/_.%E", "cleanup",
+        "Replace dashes in the name with underscores",
+        "my-notes-v2.txt -> my_notes_v2.txt"),
+];
+
+/// Returns true if `name` is one of the templates shipped with frencli.
+pub fn is_builtin_template(name: &str) -> bool {
+    BUILTIN_TEMPLATES.iter().any(|builtin| builtin.name == name)
+}
+
+/// Where a template definition came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateSource {
+    /// Shipped with frencli
+    Builtin,
+    /// Loaded from the user template file
+    User,
+    /// Loaded from a `.frentemplates` file in the working directory or an ancestor
+    Project,
+}
+
+impl TemplateSource {
+    /// Short name of the layer, as shown by `template --list`
+    pub fn label(&self) -> &'static str {
+        match self {
+            TemplateSource::Builtin => "builtin",
+            TemplateSource::User => "user",
+            TemplateSource::Project => "project",
+        }
+    }
+}
+
+/// A named rename pattern together with its origin and descriptive metadata.
+///
+/// Patterns may contain named parameters written as `{name}` (use `{{` and `}}`
+/// for literal braces). Parameters are filled from `--set name=value` or from
+/// `defaults`; a parameter without a default must be set explicitly.
+///
+/// A chained template applies `pattern` and then each pattern in `chain` in
+/// order, each one to the names produced by the previous stage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    pub pattern: String,
+    /// Patterns applied after `pattern`, in order (empty for single-pattern templates)
+    pub chain: Vec<String>,
+    pub source: TemplateSource,
+    /// Default values for parameters in `pattern`
+    pub defaults: BTreeMap<String, String>,
+    /// One-line description of what the template does
+    pub description: Option<String>,
+    /// Category used to group and filter templates (e.g. `photo`, `cleanup`)
+    pub category: Option<String>,
+    /// Example of the template's effect, e.g. `IMG_0042.jpg -> photo_001.jpg`
+    pub example: Option<String>,
+}
+
+impl Template {
+    /// Creates a template without parameter defaults or metadata.
+    pub fn new(pattern: impl Into<String>, source: TemplateSource) -> Self {
+        Self {
+            pattern: pattern.into(),
+            chain: Vec::new(),
+            source,
+            defaults: BTreeMap::new(),
+            description: None,
+            category: None,
+            example: None,
+        }
+    }
+
+    /// Returns every stage of the template, starting with `pattern`.
+    pub fn patterns(&self) -> Vec<&str> {
+        std::iter::once(self.pattern.as_str())
+            .chain(self.chain.iter().map(|p| p.as_str()))
+            .collect()
+    }
+
+    /// Formats the template's stages for display, e.g. `%L%N.%E => %N_%C3.%E`.
+    pub fn display_pattern(&self) -> String {
+        self.patterns().join(" => ")
+    }
+
+    /// Returns true if the template's category matches `category` (case-insensitive).
+    pub fn in_category(&self, category: &str) -> bool {
+        self.category.as_deref()
+            .is_some_and(|own| own.eq_ignore_ascii_case(category))
+    }
+
+    /// Lists the parameter names used in the template's patterns, in order of first appearance.
+    pub fn parameters(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        for pattern in self.patterns() {
+            for part in split_pattern_params(pattern) {
+                if let PatternPart::Param(name) = part
+                    && !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Substitutes parameter values into every stage of the template.
+    ///
+    /// Values from `values` take precedence over `defaults`; if a key is given
+    /// more than once, the last value wins.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - One pattern per stage, with every parameter filled in
+    /// * `Err(String)` - If a value is given for an unknown parameter, or a
+    ///   parameter without a default is not set
+    pub fn fill(&self, values: &[(String, String)]) -> Result<Vec<String>, String> {
+        let parameters = self.parameters();
+
+        for (key, _) in values {
+            if !parameters.contains(key) {
+                return Err(if parameters.is_empty() {
+                    format!("unknown parameter '{}' (this template takes no parameters)", key)
+                } else {
+                    format!("unknown parameter '{}' (available: {})", key, parameters.join(", "))
+                });
+            }
+        }
+
+        let mut stages = Vec::new();
+        let mut missing: Vec<String> = Vec::new();
+        for pattern in self.patterns() {
+            let mut result = String::new();
+            for part in split_pattern_params(pattern) {
+                match part {
+                    PatternPart::Literal(text) => result.push_str(&text),
+                    PatternPart::Param(name) => {
+                        let value = values.iter().rev()
+                            .find(|(key, _)| *key == name)
+                            .map(|(_, value)| value)
+                            .or_else(|| self.defaults.get(&name));
+                        match value {
+                            Some(value) => result.push_str(value),
+                            None if !missing.contains(&name) => missing.push(name),
+                            None => {}
+                        }
+                    }
+                }
+            }
+            stages.push(result);
+        }
+
+        if !missing.is_empty() {
+            let example = missing.iter()
+                .map(|name| format!("--set {}=VALUE", name))
+                .collect::<Vec<_>>()
+                .join(" ");
+            return Err(format!("missing value for parameter(s): {}. Use {}", missing.join(", "), example));
+        }
+        Ok(stages)
+    }
+}
+
+/// A piece of a template pattern: literal text or a `{name}` parameter reference.
+enum PatternPart {
+    Literal(String),
+    Param(String),
+}
+
+/// Returns true if `name` can be used as a parameter name (`[A-Za-z_][A-Za-z0-9_]*`).
+pub fn is_param_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Splits a pattern into literal text and parameter references.
+///
+/// Only `{name}` with an identifier `name` is a parameter; any other brace
+/// (such as a regex quantifier like `\d{4}` inside `%X`) is kept literally.
+fn split_pattern_params(pattern: &str) -> Vec<PatternPart> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut rest = pattern;
+
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            literal.push(c);
+            rest = &rest[2..];
+            continue;
+        }
+        if c == '{'
+            && let Some((name, after)) = rest[1..].split_once('}')
+            && is_param_name(name) {
+            if !literal.is_empty() {
+                parts.push(PatternPart::Literal(std::mem::take(&mut literal)));
+            }
+            parts.push(PatternPart::Param(name.to_string()));
+            rest = after;
+            continue;
+        }
+        literal.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    if !literal.is_empty() {
+        parts.push(PatternPart::Literal(literal));
+    }
+    parts
+}
+
+/// Parses `--set` values of the form `KEY=VALUE`.
+///
+/// # Returns
+///
+/// * `Ok(Vec<(String, String)>)` - Key/value pairs in the order given
+/// * `Err(String)` - If a value has no `=` or an empty key
+pub fn parse_param_assignments(values: &[String]) -> Result<Vec<(String, String)>, String> {
+    values.iter()
+        .map(|value| match value.split_once('=') {
+            Some((key, val)) if !key.trim().is_empty() => Ok((key.trim().to_string(), val.to_string())),
+            _ => Err(format!("Invalid --set value '{}'. Expected KEY=VALUE, e.g. --set prefix=trip", value)),
+        })
+        .collect()
+}
 
 pub struct TemplateRegistry {
-    templates: HashMap<String, String>,
+    templates: HashMap<String, Template>,
+    /// Template files that could not be loaded, as error messages naming the file
+    load_errors: Vec<String>,
 }
 
 impl TemplateRegistry {
+    /// Creates a registry containing only the built-in templates.
     pub fn new() -> Self {
-        let mut templates = HashMap::new();
-        
-        // Photo/Image templates
-        templates.insert("photo-date".to_string(), "%N_%D.%E".to_string());
-        templates.insert("photo-counter".to_string(), "photo_%C3.%E".to_string());
-        templates.insert("photo-datetime".to_string(), "%N_%FD_%FH.%E".to_string());
-        
-        // Document templates
-        templates.insert("doc-date".to_string(), "%N_%D.%E".to_string());
-        templates.insert("doc-counter".to_string(), "document_%C2.%E".to_string());
-        
-        // Lowercase templates
-        templates.insert("lowercase".to_string(), "%L%N.%E".to_string());
-        templates.insert("lowercase-name".to_string(), "%N%L.%E".to_string());
-        
-        // Uppercase templates
-        templates.insert("uppercase".to_string(), "%U%N.%E".to_string());
-        templates.insert("uppercase-name".to_string(), "%N%U.%E".to_string());
-        
-        // Title case templates
-        templates.insert("title-case".to_string(), "%T%N.%E".to_string());
-        templates.insert("title-case-name".to_string(), "%N%T.%E".to_string());
-        
-        // Parent directory templates
-        templates.insert("parent-prefix".to_string(), "%P_%N.%E".to_string());
-        templates.insert("parent-suffix".to_string(), "%N_%P.%E".to_string());
-        
-        // Counter templates
-        templates.insert("counter-2".to_string(), "%C2.%E".to_string());
-        templates.insert("counter-3".to_string(), "%C3.%E".to_string());
-        templates.insert("counter-4".to_string(), "%C4.%E".to_string());
-        templates.insert("counter-prefix".to_string(), "%C3_%N.%E".to_string());
-        templates.insert("counter-suffix".to_string(), "%N_%C3.%E".to_string());
-        
-        // Date/time templates
-        templates.insert("date-suffix".to_string(), "%N_%D.%E".to_string());
-        templates.insert("date-prefix".to_string(), "%D_%N.%E".to_string());
-        templates.insert("datetime-suffix".to_string(), "%N_%D_%H.%E".to_string());
-        
-        // Cleanup templates
-        templates.insert("trim-spaces".to_string(), "%M%N.%E".to_string());
-        templates.insert("underscore-to-dash".to_string(), "%N%R/_/-.%E".to_string());
-        templates.insert("dash-to-underscore".to_string(), "%N%R/-/_.%E".to_string());
-        
-        Self { templates }
-    }
-    
+        let templates = BUILTIN_TEMPLATES.iter()
+            .map(|builtin| {
+                let mut template = Template::new(builtin.pattern, TemplateSource::Builtin);
+                template.description = Some(builtin.description.to_string());
+                template.category = Some(builtin.category.to_string());
+                template.example = Some(builtin.example.to_string());
+                (builtin.name.to_string(), template)
+            })
+            .collect();
+
+        Self { templates, load_errors: Vec::new() }
+    }
+
+    /// Creates a registry layering project, user and built-in templates.
+    ///
+    /// The user template file is merged over the built-ins, then the nearest
+    /// `.frentemplates` file (searched from the current directory upward) is
+    /// merged over both, so project templates take precedence.
+    ///
+    /// A template file that cannot be read or is malformed is skipped and
+    /// recorded in [`TemplateRegistry::load_errors`], so a broken file does
+    /// not stop commands that never use templates.
+    pub fn load() -> Self {
+        Self::load_in(Path::new(""))
+    }
+
+    /// Like [`TemplateRegistry::load`], but searches for the `.frentemplates`
+    /// file from `base_dir` upward instead of the current directory.
+    pub fn load_in(base_dir: &Path) -> Self {
+        let mut registry = Self::new();
+        if let Some(path) = user_templates_path()
+            && path.exists() {
+            registry.merge_layer(&path, TemplateSource::User);
+        }
+        if let Some(path) = find_project_templates(&absolute_dir(base_dir)) {
+            registry.merge_layer(&path, TemplateSource::Project);
+        }
+        registry
+    }
+
+    /// Merges a template file layer, recording the error if it cannot be loaded.
+    fn merge_layer(&mut self, path: &Path, source: TemplateSource) {
+        match self.merge_file(path, source) {
+            Ok(count) => verbose!("Loaded {} {} template(s) from '{}'", count, source.label(), path.display()),
+            Err(e) => self.load_errors.push(e),
+        }
+    }
+
+    /// Returns the errors of template files that were skipped while loading.
+    pub fn load_errors(&self) -> &[String] {
+        &self.load_errors
+    }
+
+    /// Merges templates from a template file over the current entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the template file
+    /// * `source` - Source recorded for the loaded templates
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Number of templates loaded from the file
+    /// * `Err(String)` - If the file cannot be read or is malformed
+    pub fn merge_file(&mut self, path: &Path, source: TemplateSource) -> Result<usize, String> {
+        let entries = read_template_file(path, source)?;
+        let count = entries.len();
+        for (name, template) in entries {
+            self.insert(name, template);
+        }
+        Ok(count)
+    }
+
+    /// Adds a template, replacing any existing template with the same name.
+    pub fn insert(&mut self, name: String, template: Template) {
+        self.templates.insert(name, template);
+    }
+
+    /// Gets a template's pattern (the first stage, for chained templates).
     pub fn get(&self, name: &str) -> Option<&String> {
+        self.templates.get(name).map(|t| &t.pattern)
+    }
+
+    /// Gets a template together with its metadata.
+    pub fn get_template(&self, name: &str) -> Option<&Template> {
         self.templates.get(name)
     }
-    
+
+    /// Looks up a template by name or by its 1-based index in `template --list`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((&String, &Template))` - The template's name and definition
+    /// * `Err(String)` - If the index is out of range or the name is unknown
+    pub fn find(&self, name_or_index: &str) -> Result<(&String, &Template), String> {
+        if let Ok(index) = name_or_index.parse::<usize>() {
+            let templates = self.templates();
+            if index == 0 || index > templates.len() {
+                return Err(format!("Template index {} out of range (1-{})", index, templates.len()));
+            }
+            return Ok(templates[index - 1]);
+        }
+        self.templates.get_key_value(name_or_index)
+            .ok_or_else(|| self.unknown_template_error(name_or_index))
+    }
+
+    /// Builds the error for a template name that does not exist, suggesting
+    /// the closest known name when there is one.
+    pub fn unknown_template_error(&self, name: &str) -> String {
+        format!(
+            "Unknown template '{}'.{} Use 'template --list' to see all available templates.",
+            name,
+            did_you_mean(name, self.templates.keys().map(|k| k.as_str()))
+        )
+    }
+
+    /// Lists template names and patterns (the first stage, for chained templates), sorted by name.
     pub fn list(&self) -> Vec<(&String, &String)> {
+        self.templates().into_iter()
+            .map(|(name, template)| (name, &template.pattern))
+            .collect()
+    }
+
+    /// Lists all templates with their metadata, sorted by name.
+    pub fn templates(&self) -> Vec<(&String, &Template)> {
         let mut items: Vec<_> = self.templates.iter().collect();
         items.sort_by_key(|(k, _)| *k);
         items
     }
+
+    /// Lists the distinct template categories, sorted and lowercased.
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = self.templates.values()
+            .filter_map(|t| t.category.as_ref().map(|c| c.to_lowercase()))
+            .collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
 }
 
 impl Default for TemplateRegistry {
@@ -69,4 +488,3 @@ impl Default for TemplateRegistry {
         Self::new()
     }
 }
-
@@ -1,18 +1,20 @@
 use std::io::{self, Write};
 use freneng::FileRename;
+use crate::style;
 
 pub fn display_preview(renames: &[FileRename]) {
-    println!("{:<40} -> {:<40}", "Old Name", "New Name");
-    println!("{:-<40}----{:-<40}", "", "");
+    info!("{} -> {}", style::pad(&style::heading("Old Name"), "Old Name", 40), style::pad(&style::heading("New Name"), "New Name", 40));
+    info!("{:-<40}----{:-<40}", "", "");
     
     for rename in renames {
         let old = rename.old_path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
         let new = &rename.new_name;
         
         if new.trim().is_empty() {
-            println!("{:<40} -> {:<40}", old, "[ERROR: EMPTY NAME]");
+            let empty = "[ERROR: EMPTY NAME]";
+            info!("{:<40} -> {}", old, style::pad(&style::error(empty), empty, 40));
         } else {
-            println!("{:<40} -> {:<40}", old, new);
+            info!("{:<40} -> {}", old, style::pad(&style::highlight_change(old, new), new, 40));
         }
     }
 }
@@ -37,36 +39,30 @@ pub fn interactive_edit(renames: &mut [FileRename]) -> bool {
         let old = rename.old_path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
         let current_new = &rename.new_name;
         
-        loop {
-            print!("\n[{}] {} -> [{}] ", i + 1, old, current_new);
-            io::stdout().flush().unwrap();
-            
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).unwrap();
-            let input = input.trim();
-            
-            if input.is_empty() {
-                // Keep current name
-                break;
-            } else if input == "q" || input == "quit" {
-                println!("Cancelled.");
-                return false;
-            } else if input == "s" || input == "skip" {
-                // Skip this file by keeping old name
-                rename.new_name = old.to_string();
-                rename.new_path = rename.old_path.clone();
-                break;
-            } else if input == "a" || input == "apply" {
-                // Apply all remaining
-                apply_all = true;
-                break;
-            } else {
-                // New name provided
-                rename.new_name = input.to_string();
-                if let Some(parent) = rename.old_path.parent() {
-                    rename.new_path = parent.join(&rename.new_name);
-                }
-                break;
+        print!("\n[{}] {} -> [{}] ", i + 1, old, current_new);
+        io::stdout().flush().unwrap();
+        
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+        let input = input.trim();
+        
+        if input.is_empty() {
+            // Keep current name
+        } else if input == "q" || input == "quit" {
+            println!("Cancelled.");
+            return false;
+        } else if input == "s" || input == "skip" {
+            // Skip this file by keeping old name
+            rename.new_name = old.to_string();
+            rename.new_path = rename.old_path.clone();
+        } else if input == "a" || input == "apply" {
+            // Apply all remaining
+            apply_all = true;
+        } else {
+            // New name provided
+            rename.new_name = input.to_string();
+            if let Some(parent) = rename.old_path.parent() {
+                rename.new_path = parent.join(&rename.new_name);
             }
         }
         
@@ -3,8 +3,11 @@
 //! This module handles the `fren undo` command which can check undo status
 //! or apply undo operations to reverse previous renames.
 
+use std::path::Path;
 use freneng::RenamingEngine;
-use freneng::history::{load_history, clear_history};
+use crate::error::FrencliError;
+use crate::workdir::{load_history, clear_history};
+use crate::style;
 use crate::ui::confirm_undo_conflicts;
 
 /// Handles the undo --check subcommand - checks what can be safely undone.
@@ -12,41 +15,38 @@ use crate::ui::confirm_undo_conflicts;
 /// # Arguments
 /// 
 /// * `engine` - The renaming engine
+/// * `base_dir` - Directory whose undo history is checked
 /// 
 /// # Returns
 /// 
-/// * Exits with code 0 on success, 1 on error
-pub async fn handle_undo_check(engine: &RenamingEngine) {
-    match load_history().await {
-        Ok(Some(history)) => {
-            println!("Checking undo state for {} renames from {}...", 
-                history.actions.len(), 
-                history.timestamp.format("%Y-%m-%d %H:%M:%S"));
+/// * `Ok(usize)` - Number of renames that can be safely undone (0 if there is no history)
+/// * `Err(FrencliError)` - [`FrencliError::Io`] if the history cannot be loaded
+pub async fn handle_undo_check(engine: &RenamingEngine, base_dir: &Path) -> Result<usize, FrencliError> {
+    let Some(history) = load_history(base_dir).await.map_err(history_error)? else {
+        info!("No rename history found in this directory.");
+        return Ok(0);
+    };
 
-            let (safe_actions, conflicts) = engine.check_undo(&history).await;
+    info!("Checking undo state for {} renames from {}...", 
+        history.actions.len(), 
+        history.timestamp.format("%Y-%m-%d %H:%M:%S"));
 
-            if !conflicts.is_empty() {
-                println!("\nFound {} conflict(s) that prevent a full undo:", conflicts.len());
-                for conflict in &conflicts {
-                    println!("  - {}", conflict);
-                }
-            }
+    let (safe_actions, conflicts) = engine.check_undo(&history).await;
 
-            let safe_count = safe_actions.len();
-            if safe_count == 0 {
-                println!("\nAll files in this batch have conflicts. Cannot proceed with undo.");
-            } else {
-                println!("\n{} file(s) can be safely undone.", safe_count);
-            }
-        }
-        Ok(None) => {
-            println!("No rename history found in this directory.");
-        }
-        Err(e) => {
-            eprintln!("Error loading history: {}", e);
-            std::process::exit(1);
+    if !conflicts.is_empty() {
+        info!("\n{}", style::warning(&format!("Found {} conflict(s) that prevent a full undo:", conflicts.len())));
+        for conflict in &conflicts {
+            info!("  - {}", style::warning(conflict));
         }
     }
+
+    let safe_count = safe_actions.len();
+    if safe_count == 0 {
+        info!("\n{}", style::error("All files in this batch have conflicts. Cannot proceed with undo."));
+    } else {
+        info!("\n{}", style::success(&format!("{} file(s) can be safely undone.", safe_count)));
+    }
+    Ok(safe_count)
 }
 
 /// Handles the undo --apply subcommand - actually performs the undo operation.
@@ -54,57 +54,56 @@ pub async fn handle_undo_check(engine: &RenamingEngine) {
 /// # Arguments
 /// 
 /// * `engine` - The renaming engine
+/// * `base_dir` - Directory whose last batch of renames is undone
 /// * `yes` - Skip confirmation prompt
 /// 
 /// # Returns
 /// 
-/// * Exits with code 0 on success, 1 on error
-pub async fn handle_undo_apply(engine: &RenamingEngine, yes: bool) {
-    match load_history().await {
-        Ok(Some(history)) => {
-            println!("Checking undo state for {} renames from {}...", 
-                history.actions.len(), 
-                history.timestamp.format("%Y-%m-%d %H:%M:%S"));
-
-            let (safe_actions, conflicts) = engine.check_undo(&history).await;
-
-            if !conflicts.is_empty() {
-                println!("\nFound {} conflict(s) that prevent a full undo:", conflicts.len());
-                for conflict in &conflicts {
-                    println!("  - {}", conflict);
-                }
+/// * `Ok(usize)` - Number of renames reversed (0 if there is no history)
+/// * `Err(FrencliError)` - [`FrencliError::ValidationFailed`] if every rename has a conflict,
+///   [`FrencliError::Aborted`] if the user declined the prompt, or
+///   [`FrencliError::Io`] if the history cannot be loaded or the undo fails
+pub async fn handle_undo_apply(engine: &RenamingEngine, base_dir: &Path, yes: bool) -> Result<usize, FrencliError> {
+    let Some(history) = load_history(base_dir).await.map_err(history_error)? else {
+        info!("No rename history found in this directory.");
+        return Ok(0);
+    };
 
-                let safe_count = safe_actions.len();
-                if safe_count == 0 {
-                    println!("\nAll files in this batch have conflicts. Cannot proceed with undo.");
-                    println!("Undo operation cancelled.");
-                    std::process::exit(1);
-                }
+    info!("Checking undo state for {} renames from {}...", 
+        history.actions.len(), 
+        history.timestamp.format("%Y-%m-%d %H:%M:%S"));
 
-                if !yes && !confirm_undo_conflicts(safe_count) {
-                    println!("Undo operation cancelled.");
-                    std::process::exit(0);
-                }
-            }
+    let (safe_actions, conflicts) = engine.check_undo(&history).await;
 
-            match engine.apply_undo(safe_actions).await {
-                Ok(count) => {
-                    println!("Successfully reversed {} renames.", count);
-                    let _ = clear_history().await;
-                }
-                Err(e) => {
-                    eprintln!("Error during undo: {}", e);
-                    std::process::exit(1);
-                }
-            }
+    if !conflicts.is_empty() {
+        info!("\n{}", style::warning(&format!("Found {} conflict(s) that prevent a full undo:", conflicts.len())));
+        for conflict in &conflicts {
+            info!("  - {}", style::warning(conflict));
         }
-        Ok(None) => {
-            println!("No rename history found in this directory.");
+
+        let safe_count = safe_actions.len();
+        if safe_count == 0 {
+            return Err(FrencliError::ValidationFailed(
+                "All files in this batch have conflicts. Undo operation cancelled.".to_string()
+            ));
         }
-        Err(e) => {
-            eprintln!("Error loading history: {}", e);
-            std::process::exit(1);
+
+        if !yes && !confirm_undo_conflicts(safe_count) {
+            return Err(FrencliError::Aborted("Undo operation cancelled.".to_string()));
         }
     }
+
+    for action in &safe_actions {
+        verbose!("Restoring {} -> {}", action.new_path.display(), action.old_path.display());
+    }
+    let count = engine.apply_undo(safe_actions).await
+        .map_err(|e| FrencliError::Io(format!("Error during undo: {}", e)))?;
+    info!("{}", style::success(&format!("Successfully reversed {} renames.", count)));
+    let _ = clear_history(base_dir).await;
+    Ok(count)
 }
 
+/// Builds the error for a history file that cannot be loaded.
+fn history_error(error: String) -> FrencliError {
+    FrencliError::Io(format!("Error loading history: {}", error))
+}
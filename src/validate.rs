@@ -7,6 +7,9 @@
 use freneng::{RenamingEngine, EnginePreviewResult, ValidationIssue, ValidationResult};
 use std::path::PathBuf;
 use std::collections::HashMap;
+use std::time::Instant;
+use crate::error::FrencliError;
+use crate::style::{self, Symbol};
 
 /// Handles the validate subcommand - performs comprehensive validation on a preview.
 /// 
@@ -18,8 +21,9 @@ use std::collections::HashMap;
 /// 
 /// # Returns
 /// 
-/// * `Ok(())` - Validation completed (may have issues if skip_invalid=true)
-/// * Exits with code 1 if validation fails and skip_invalid=false
+/// * `Ok(ValidationResult)` - Validation completed (may have issues if skip_invalid=true)
+/// * `Err(FrencliError)` - [`FrencliError::ValidationFailed`] if a file would get an
+///   empty name or validation found issues, and skip_invalid=false
 /// 
 /// Note: Validation always checks for existing target files (overwrite=false).
 /// The `--overwrite` flag is only applicable to the `apply` subcommand.
@@ -27,7 +31,7 @@ pub async fn handle_validate_command(
     engine: &RenamingEngine,
     preview_result: &EnginePreviewResult,
     skip_invalid: bool,
-) {
+) -> Result<ValidationResult, FrencliError> {
     // First check preview-level issues (empty names, warnings)
     if preview_result.has_empty_names {
         let empty_count = preview_result.renames.iter()
@@ -35,26 +39,30 @@ pub async fn handle_validate_command(
             .count();
         
         if skip_invalid {
-            println!("⚠ WARNING: {} file(s) would have empty names (skipped)", empty_count);
+            info!("{}", style::warning(&format!("{} WARNING: {} file(s) would have empty names (skipped)", Symbol::Warning, empty_count)));
         } else {
-            eprintln!("❌ ERROR: Pattern would generate {} empty filename(s).", empty_count);
-            eprintln!("Please check your pattern and ensure it generates valid filenames.");
-            std::process::exit(1);
+            return Err(FrencliError::ValidationFailed(format!(
+                "Pattern would generate {} empty filename(s).\n\
+                 Please check your pattern and ensure it generates valid filenames.",
+                empty_count
+            )));
         }
     }
     
     if !preview_result.warnings.is_empty() {
-        println!("\n⚠ Pattern Warnings:");
+        info!("\n{}", style::warning(&format!("{} Pattern Warnings:", Symbol::Warning)));
         for warning in &preview_result.warnings {
-            println!("  - {}", warning);
+            info!("  - {}", style::warning(warning));
         }
         if !skip_invalid {
-            println!("\nUse --skip-invalid to continue despite warnings.");
+            info!("\nUse --skip-invalid to continue despite warnings.");
         }
     }
     
     // Run comprehensive validation (always check for existing files, overwrite=false)
+    let started = Instant::now();
     let validation_result = engine.validate(&preview_result.renames, false).await;
+    verbose!("Validated {} rename(s) in {:.2?}", preview_result.renames.len(), started.elapsed());
     
     // Display validation results
     display_validation_results(&validation_result);
@@ -64,26 +72,36 @@ pub async fn handle_validate_command(
     let valid_count = validation_result.valid.len();
     let issue_count = validation_result.issues.len();
     
-    println!("\n📊 Validation Summary:");
-    println!("  Total files: {}", total);
-    println!("  ✓ Valid: {}", valid_count);
-    println!("  ✗ Issues: {}", issue_count);
+    info!("\n{}", style::heading(&format!("{} Validation Summary:", Symbol::Summary)));
+    info!("  Total files: {}", total);
+    info!("  {}", style::success(&format!("{} Valid: {}", Symbol::Check, valid_count)));
+    let issues = format!("{} Issues: {}", Symbol::Cross, issue_count);
+    info!("  {}", if issue_count > 0 { style::error(&issues) } else { issues });
     
-    // Exit with error if issues found and not skipping
+    // Fail if issues found and not skipping
     if !validation_result.issues.is_empty() && !skip_invalid {
-        eprintln!("\n❌ Validation failed. Use --skip-invalid to continue despite issues.");
-        std::process::exit(1);
+        return Err(validation_failed(issue_count));
     }
     
     if validation_result.issues.is_empty() && !preview_result.has_empty_names {
-        println!("\n✓ All files passed validation!");
+        info!("\n{}", style::success(&format!("{} All files passed validation!", Symbol::Check)));
     }
+    
+    Ok(validation_result)
+}
+
+/// Builds the error for validation that found `issue_count` issues.
+pub fn validation_failed(issue_count: usize) -> FrencliError {
+    FrencliError::ValidationFailed(format!(
+        "Validation failed: {} issue(s) found. Use --skip-invalid to continue despite issues.",
+        issue_count
+    ))
 }
 
 /// Displays validation results in a clear, organized format.
 fn display_validation_results(result: &ValidationResult) {
     if result.valid.is_empty() && result.issues.is_empty() {
-        println!("\nNo files to validate.");
+        info!("\nNo files to validate.");
         return;
     }
     
@@ -106,56 +124,57 @@ fn display_validation_results(result: &ValidationResult) {
         
         issues_by_type
             .entry(issue_type)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push((path.clone(), issue.clone()));
     }
     
     // Display valid renames
     if !result.valid.is_empty() {
-        println!("\n✓ Valid Renames ({}):", result.valid.len());
+        info!("\n{}", style::success(&format!("{} Valid Renames ({}):", Symbol::Check, result.valid.len())));
         for rename in &result.valid {
-            println!("  {} → {}", 
-                rename.old_path.file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("?"),
-                rename.new_name);
+            let old = rename.old_path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("?");
+            info!("  {} {} {}", old, Symbol::Arrow, style::highlight_change(old, &rename.new_name));
         }
     }
     
     // Display issues grouped by type
     if !result.issues.is_empty() {
-        println!("\n✗ Validation Issues ({}):", result.issues.len());
+        info!("\n{}", style::error(&format!("{} Validation Issues ({}):", Symbol::Cross, result.issues.len())));
         
         for (issue_type, issues) in issues_by_type.iter() {
-            println!("\n  {} ({} file(s)):", issue_type, issues.len());
+            info!("\n  {} ({} file(s)):", issue_type, issues.len());
             
             for (path, issue) in issues {
                 let file_name = path.file_name()
                     .and_then(|n| n.to_str())
                     .unwrap_or("?");
                 
-                let details = match issue {
-                    ValidationIssue::InvalidCharacters(msg) => format!("{}", msg),
-                    ValidationIssue::ReservedFilename(msg) => format!("{}", msg),
-                    ValidationIssue::PathTooLong { path, max_length } => {
-                        format!("Path length {} exceeds maximum {} characters", path.len(), max_length)
-                    },
-                    ValidationIssue::SourceNotFound(_) => "Source file does not exist".to_string(),
-                    ValidationIssue::SourceNotReadable(_) => "Source file is not readable".to_string(),
-                    ValidationIssue::ParentNotWritable(_) => "Parent directory is not writable".to_string(),
-                    ValidationIssue::TargetExists(_) => {
-                        "Target file already exists".to_string()
-                    },
-                    ValidationIssue::CircularRename { file1, file2 } => {
-                        format!("Circular dependency: {} ↔ {}", file1, file2)
-                    },
-                    ValidationIssue::InvalidFormat(msg) => format!("{}", msg),
-                    ValidationIssue::EmptyFilename => "Generated filename is empty".to_string(),
-                };
-                
-                println!("    {}: {}", file_name, details);
+                info!("    {}: {}", file_name, style::error(&describe_issue(issue)));
             }
         }
     }
 }
 
+/// Describes a validation issue in one line, e.g. "Target file already exists".
+pub fn describe_issue(issue: &ValidationIssue) -> String {
+    match issue {
+        ValidationIssue::InvalidCharacters(msg) => msg.clone(),
+        ValidationIssue::ReservedFilename(msg) => msg.clone(),
+        ValidationIssue::PathTooLong { path, max_length } => {
+            format!("Path length {} exceeds maximum {} characters", path.len(), max_length)
+        },
+        ValidationIssue::SourceNotFound(_) => "Source file does not exist".to_string(),
+        ValidationIssue::SourceNotReadable(_) => "Source file is not readable".to_string(),
+        ValidationIssue::ParentNotWritable(_) => "Parent directory is not writable".to_string(),
+        ValidationIssue::TargetExists(_) => {
+            "Target file already exists".to_string()
+        },
+        ValidationIssue::CircularRename { file1, file2 } => {
+            format!("Circular dependency: {} {} {}", file1, Symbol::BothWays, file2)
+        },
+        ValidationIssue::InvalidFormat(msg) => msg.clone(),
+        ValidationIssue::EmptyFilename => "Generated filename is empty".to_string(),
+    }
+}
@@ -0,0 +1,175 @@
+//! The base directory frencli works in, and the undo history and audit log kept there.
+//!
+//! frencli works relative to a base directory: the directory given with the
+//! global `--chdir DIR` option, or the current directory. Relative `list`
+//! patterns and `--files-from` paths are resolved against it, and the undo
+//! history (`.fren_history.json`) and audit log (`.fren_audit.log`) are read
+//! and written there.
+//!
+//! freneng reads and writes these files in the process's current directory
+//! (and prints debug output when writing the audit log); the functions here
+//! take the base directory explicitly instead, so nothing depends on (or
+//! changes) the process's working directory. An empty base directory means
+//! the current directory.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use chrono::Local;
+use freneng::{AuditEntry, RenameExecutionResult};
+use freneng::history::{History, RenameAction};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// File name of the undo history inside the base directory
+pub const HISTORY_FILE: &str = ".fren_history.json";
+
+/// File name of the audit log inside the base directory
+pub const AUDIT_FILE: &str = ".fren_audit.log";
+
+/// Resolves a path against the base directory; absolute paths are returned unchanged.
+pub fn resolve(base_dir: &Path, path: impl AsRef<Path>) -> PathBuf {
+    base_dir.join(path)
+}
+
+/// Resolves a `list` pattern or path against the base directory.
+///
+/// The pattern is kept as a string, since it may contain glob characters.
+pub fn resolve_pattern(base_dir: &Path, pattern: &str) -> String {
+    resolve(base_dir, pattern).to_string_lossy().to_string()
+}
+
+/// Returns the base directory as an absolute path, for recording in the audit log.
+pub fn absolute_dir(base_dir: &Path) -> PathBuf {
+    if base_dir.is_absolute() {
+        return base_dir.to_path_buf();
+    }
+    env::current_dir()
+        .map(|cwd| cwd.join(base_dir))
+        .unwrap_or_else(|_| base_dir.to_path_buf())
+}
+
+/// Checks that a `--chdir` directory exists.
+///
+/// # Returns
+///
+/// * `Ok(PathBuf)` - The directory, as given
+/// * `Err(String)` - If it does not exist or is not a directory
+pub fn check_base_dir(dir: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(dir);
+    match std::fs::metadata(&path) {
+        Ok(metadata) if metadata.is_dir() => Ok(path),
+        Ok(_) => Err(format!("Cannot change to '{}': not a directory.", dir)),
+        Err(e) => Err(format!("Cannot change to '{}': {}", dir, e)),
+    }
+}
+
+/// Saves the renames of an `apply` as the undo history of the base directory.
+///
+/// If `actions` is empty, the existing history is kept.
+pub async fn save_history(base_dir: &Path, actions: Vec<RenameAction>) -> Result<(), String> {
+    if actions.is_empty() {
+        return Ok(());
+    }
+    let history = History {
+        timestamp: Local::now(),
+        actions,
+    };
+    let json = serde_json::to_string_pretty(&history)
+        .map_err(|e| format!("Failed to serialize history: {}", e))?;
+    let path = resolve(base_dir, HISTORY_FILE);
+    fs::write(&path, json).await
+        .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+}
+
+/// Loads the undo history of the base directory.
+///
+/// # Returns
+///
+/// * `Ok(Some(History))` - The last batch of renames
+/// * `Ok(None)` - If there is no history file
+/// * `Err(String)` - If the history file cannot be read or is malformed
+pub async fn load_history(base_dir: &Path) -> Result<Option<History>, String> {
+    let path = resolve(base_dir, HISTORY_FILE);
+    if fs::metadata(&path).await.is_err() {
+        return Ok(None);
+    }
+    let json = fs::read_to_string(&path).await
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+    let history = serde_json::from_str(&json)
+        .map_err(|e| format!("Invalid history file '{}': {}", path.display(), e))?;
+    Ok(Some(history))
+}
+
+/// Deletes the undo history of the base directory, if there is one.
+pub async fn clear_history(base_dir: &Path) -> Result<(), String> {
+    let path = resolve(base_dir, HISTORY_FILE);
+    if fs::metadata(&path).await.is_ok() {
+        fs::remove_file(&path).await
+            .map_err(|e| format!("Failed to delete '{}': {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Appends the result of an `apply` to the audit log of the base directory.
+///
+/// Entries are JSON lines in the same format freneng writes and reads.
+pub async fn log_audit(
+    base_dir: &Path,
+    command: &str,
+    pattern: Option<String>,
+    execution: &RenameExecutionResult,
+) -> Result<(), String> {
+    let entry = AuditEntry {
+        timestamp: Local::now(),
+        user: env::var("USER").or_else(|_| env::var("USERNAME")).ok(),
+        working_directory: absolute_dir(base_dir),
+        command: command.to_string(),
+        pattern,
+        successful_count: execution.successful.len(),
+        skipped_count: execution.skipped.len(),
+        error_count: execution.errors.len(),
+        successful: execution.successful.iter()
+            .map(|action| (action.old_path.clone(), action.new_path.clone()))
+            .collect(),
+        skipped: execution.skipped.clone(),
+        errors: execution.errors.clone(),
+    };
+    let mut line = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize audit entry: {}", e))?;
+    line.push('\n');
+
+    let path = resolve(base_dir, AUDIT_FILE);
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .await
+        .map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+    file.write_all(line.as_bytes()).await
+        .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))?;
+    file.sync_all().await
+        .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+}
+
+/// Reads the audit log of the base directory, most recent entry first.
+///
+/// # Returns
+///
+/// * `Ok(Vec<AuditEntry>)` - The entries (empty if there is no audit log)
+/// * `Err(String)` - If the audit log cannot be read or an entry is malformed
+pub async fn read_audit_log(base_dir: &Path) -> Result<Vec<AuditEntry>, String> {
+    let path = resolve(base_dir, AUDIT_FILE);
+    if fs::metadata(&path).await.is_err() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).await
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+
+    let mut entries = content.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line)
+            .map_err(|e| format!("Invalid audit log '{}': {}", path.display(), e)))
+        .collect::<Result<Vec<AuditEntry>, String>>()?;
+    entries.reverse();
+    Ok(entries)
+}
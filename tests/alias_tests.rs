@@ -0,0 +1,135 @@
+//! Tests for the alias module.
+//! 
+//! These tests verify saving, listing and expanding pipeline aliases.
+
+use frencli::alias::{
+    read_aliases, write_aliases, parse_alias_pipeline, expand_alias, handle_alias_save, handle_alias_delete,
+    format_command,
+};
+use frencli::subcommands::has_flag;
+use std::collections::BTreeMap;
+use tempfile::TempDir;
+
+fn args(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+fn sample_aliases() -> BTreeMap<String, Vec<String>> {
+    let mut aliases = BTreeMap::new();
+    aliases.insert("photos".to_string(), args(&["list", "*.jpg", "rename", "%N_%C3.%E", "apply", "--yes"]));
+    aliases.insert("tidy".to_string(), args(&["rename", "%L%N.%E"]));
+    aliases
+}
+
+#[test]
+fn test_read_aliases_missing_file() {
+    let dir = TempDir::new().unwrap();
+    let aliases = read_aliases(&dir.path().join("aliases.json")).unwrap();
+    assert!(aliases.is_empty());
+}
+
+#[test]
+fn test_write_and_read_aliases() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("frencli").join("aliases.json");
+    
+    write_aliases(&path, sample_aliases()).unwrap();
+    assert_eq!(read_aliases(&path).unwrap(), sample_aliases());
+}
+
+#[test]
+fn test_read_aliases_invalid_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("aliases.json");
+    std::fs::write(&path, r#"{"aliases": {"photos": "list *.jpg"}}"#).unwrap();
+    
+    let err = read_aliases(&path).unwrap_err();
+    assert!(err.starts_with("Invalid alias file"), "got: {}", err);
+}
+
+#[test]
+fn test_parse_alias_pipeline() {
+    let subcommands = parse_alias_pipeline(&args(&["list", "*.jpg", "rename", "%N.%E", "apply", "--yes"])).unwrap();
+    assert_eq!(subcommands.len(), 3);
+    assert_eq!(subcommands[0].args, vec!["*.jpg"]);
+    assert!(has_flag(&subcommands[2].flags, "yes"));
+}
+
+#[test]
+fn test_parse_alias_pipeline_rejects_nested_aliases() {
+    assert_eq!(parse_alias_pipeline(&args(&["run", "photos"])).unwrap_err(), "An alias cannot contain 'run'.");
+    assert!(parse_alias_pipeline(&args(&[])).is_err());
+    assert!(parse_alias_pipeline(&args(&["list", "--help"])).is_err());
+    assert!(parse_alias_pipeline(&args(&["list", "--yes"])).is_err());
+}
+
+#[test]
+fn test_expand_alias_appends_patterns_to_list() {
+    let subcommands = expand_alias(&sample_aliases(), "photos", &args(&["*.png", "*.gif"])).unwrap();
+    
+    assert_eq!(subcommands[0].name, "list");
+    assert_eq!(subcommands[0].args, vec!["*.jpg", "*.png", "*.gif"]);
+    assert_eq!(subcommands[1].args, vec!["%N_%C3.%E"]);
+}
+
+#[test]
+fn test_expand_alias_without_list() {
+    assert!(expand_alias(&sample_aliases(), "tidy", &[]).is_ok());
+    
+    let err = expand_alias(&sample_aliases(), "tidy", &args(&["*.txt"])).unwrap_err();
+    assert_eq!(err, "Alias 'tidy' has no 'list' subcommand to add '*.txt' to.");
+}
+
+#[test]
+fn test_expand_alias_unknown_name() {
+    let err = expand_alias(&sample_aliases(), "photo", &[]).unwrap_err();
+    assert!(err.starts_with("Unknown alias 'photo'. Did you mean 'photos'?"), "got: {}", err);
+}
+
+#[test]
+fn test_expand_alias_invalid_entry() {
+    let mut aliases = BTreeMap::new();
+    aliases.insert("broken".to_string(), args(&["list", "--bogus"]));
+    
+    let err = expand_alias(&aliases, "broken", &[]).unwrap_err();
+    assert!(err.starts_with("Alias 'broken' is invalid: Unknown flag '--bogus'"), "got: {}", err);
+}
+
+#[test]
+fn test_handle_alias_save_and_delete() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("aliases.json");
+    let pipeline = args(&["list", "*.jpg", "rename", "%N.%E"]);
+    
+    handle_alias_save(&path, "photos", &pipeline, false).unwrap();
+    assert_eq!(read_aliases(&path).unwrap()["photos"], pipeline);
+    
+    // Saving over an existing alias requires force
+    let err = handle_alias_save(&path, "photos", &args(&["list", "*.png"]), false).unwrap_err();
+    assert!(err.contains("already exists"));
+    handle_alias_save(&path, "photos", &args(&["list", "*.png"]), true).unwrap();
+    assert_eq!(read_aliases(&path).unwrap()["photos"], args(&["list", "*.png"]));
+    
+    handle_alias_delete(&path, "photos").unwrap();
+    assert!(read_aliases(&path).unwrap().is_empty());
+    assert!(handle_alias_delete(&path, "photos").is_err());
+}
+
+#[test]
+fn test_handle_alias_save_invalid() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("aliases.json");
+    
+    assert!(handle_alias_save(&path, "photos", &[], false).is_err());
+    assert!(handle_alias_save(&path, "my photos", &args(&["list", "*.jpg"]), false).is_err());
+    assert!(handle_alias_save(&path, "--photos", &args(&["list", "*.jpg"]), false).is_err());
+    assert!(handle_alias_save(&path, "photos", &args(&["list", "*.jpg", "rename", "%N.%E", "template", "--use", "x"]), false).is_err());
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_format_command_quotes_special_arguments() {
+    assert_eq!(format_command(&args(&["list", "*.jpg", "rename", "%N_%C3.%E"])), "list '*.jpg' rename %N_%C3.%E");
+    assert_eq!(format_command(&args(&["list", "it's here"])), "list 'it'\\''s here'");
+    assert_eq!(format_command(&args(&["list", ""])), "list ''");
+}
@@ -3,12 +3,11 @@
 //! These tests verify apply command functionality including file operations.
 //! All tests use isolated temp directories.
 
-use frencli::apply::handle_apply_command;
-use freneng::{EnginePreviewResult, FileRename};
+use frencli::apply::{handle_apply_command, handle_apply_dry_run, simulate_renames};
+use frencli::error::{FrencliError, EXIT_PARTIAL_APPLY, EXIT_VALIDATION_FAILED};
+use freneng::{EnginePreviewResult, FileRename, RenamingEngine};
 use tempfile::TempDir;
 use tokio::fs;
-mod test_utils;
-use test_utils::DirGuard;
 
 #[tokio::test]
 async fn test_handle_apply_with_yes_flag() {
@@ -27,7 +26,7 @@ async fn test_handle_apply_with_yes_flag() {
     };
     
     // With yes=true, should rename without prompting
-    let result = handle_apply_command(preview, false, true, false, "test command".to_string(), None, true, false).await;
+    let result = handle_apply_command(temp_dir.path(), preview, false, true, false, "test command".to_string(), None, true, false).await;
     assert!(result.is_ok());
     
     // Verify file was renamed
@@ -54,7 +53,7 @@ async fn test_handle_apply_with_overwrite() {
     };
     
     // With overwrite=true, should overwrite existing file
-    let result = handle_apply_command(preview, true, true, false, "test command".to_string(), None, true, false).await;
+    let result = handle_apply_command(temp_dir.path(), preview, true, true, false, "test command".to_string(), None, true, false).await;
     assert!(result.is_ok());
     
     // Verify old file is gone and new file exists
@@ -68,7 +67,7 @@ async fn test_handle_apply_with_empty_names_blocks() {
     let file = temp_dir.path().join("test.txt");
     fs::write(&file, "content").await.unwrap();
     
-    let _preview = EnginePreviewResult {
+    let preview = EnginePreviewResult {
         renames: vec![FileRename {
             old_path: file.clone(),
             new_path: temp_dir.path().join(""),
@@ -78,8 +77,12 @@ async fn test_handle_apply_with_empty_names_blocks() {
         has_empty_names: true,
     };
     
-    // Should exit with code 1, so we can't easily test
-    // But we verify the function exists and compiles
+    let result = handle_apply_command(temp_dir.path(), preview, false, true, false, "test command".to_string(), None, false, false).await;
+    
+    let err = result.unwrap_err();
+    assert_eq!(err.exit_code(), EXIT_VALIDATION_FAILED);
+    assert!(err.message().contains("empty name"), "got: {}", err);
+    assert!(file.exists());
 }
 
 #[tokio::test]
@@ -99,7 +102,7 @@ async fn test_handle_apply_with_warnings() {
     };
     
     // Should display warnings but continue
-    let result = handle_apply_command(preview, false, true, false, "test command".to_string(), None, true, false).await;
+    let result = handle_apply_command(temp_dir.path(), preview, false, true, false, "test command".to_string(), None, true, false).await;
     assert!(result.is_ok());
 }
 
@@ -132,7 +135,7 @@ async fn test_handle_apply_multiple_files() {
         has_empty_names: false,
     };
     
-    let result = handle_apply_command(preview, false, true, false, "test command".to_string(), None, true, false).await;
+    let result = handle_apply_command(temp_dir.path(), preview, false, true, false, "test command".to_string(), None, true, false).await;
     assert!(result.is_ok());
     
     // Verify all files renamed
@@ -148,9 +151,6 @@ async fn test_handle_apply_saves_history() {
     let file = temp_dir.path().join("old.txt");
     fs::write(&file, "content").await.unwrap();
     
-    // Change to temp directory for history (save_history writes to current directory)
-    let _guard = DirGuard::new(temp_dir.path()).unwrap();
-    
     let preview = EnginePreviewResult {
         renames: vec![FileRename {
             old_path: file.clone(),
@@ -161,10 +161,185 @@ async fn test_handle_apply_saves_history() {
         has_empty_names: false,
     };
     
-    let result = handle_apply_command(preview, false, true, false, "test command".to_string(), None, true, false).await;
+    let result = handle_apply_command(temp_dir.path(), preview, false, true, false, "test command".to_string(), None, true, false).await;
     assert!(result.is_ok());
     
-    // History should be saved (we can't easily verify without loading it)
-    // But the function should complete without error
+    // History and audit log are written to the base directory
+    assert!(temp_dir.path().join(".fren_history.json").exists());
+    assert!(temp_dir.path().join(".fren_audit.log").exists());
+}
+
+
+#[tokio::test]
+async fn test_handle_apply_partial_failure() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("old.txt");
+    fs::write(&file, "content").await.unwrap();
+    
+    let preview = EnginePreviewResult {
+        renames: vec![
+            FileRename {
+                old_path: file.clone(),
+                new_path: temp_dir.path().join("new.txt"),
+                new_name: "new.txt".to_string(),
+            },
+            // The source does not exist, so this rename fails
+            FileRename {
+                old_path: temp_dir.path().join("missing.txt"),
+                new_path: temp_dir.path().join("found.txt"),
+                new_name: "found.txt".to_string(),
+            },
+        ],
+        warnings: vec![],
+        has_empty_names: false,
+    };
+    
+    let result = handle_apply_command(temp_dir.path(), preview, false, true, false, "test command".to_string(), None, false, false).await;
+    let err = result.unwrap_err();
+    assert!(matches!(err, FrencliError::PartialApply(_)));
+    assert_eq!(err.message(), "1 of 2 file(s) could not be renamed.");
+    assert_eq!(err.exit_code(), EXIT_PARTIAL_APPLY);
+    
+    // The other file is still renamed
+    assert!(temp_dir.path().join("new.txt").exists());
+}
+
+/// Builds a rename of `old` to `new` inside `dir`.
+fn file_rename(dir: &std::path::Path, old: &str, new: &str) -> FileRename {
+    FileRename {
+        old_path: dir.join(old),
+        new_path: dir.join(new),
+        new_name: new.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_simulate_renames() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    for name in ["a.txt", "b.txt", "c.txt", "taken.txt", "same.txt"] {
+        fs::write(dir.join(name), "content").await.unwrap();
+    }
+    
+    let renames = vec![
+        file_rename(dir, "a.txt", "a_new.txt"),
+        file_rename(dir, "b.txt", "taken.txt"),
+        file_rename(dir, "same.txt", "same.txt"),
+        file_rename(dir, "missing.txt", "found.txt"),
+        file_rename(dir, "c.txt", "sub/c.txt"),
+    ];
+    let result = simulate_renames(&renames, false).await;
+    
+    assert_eq!(result.successful.len(), 1);
+    assert_eq!(result.successful[0].new_path, dir.join("a_new.txt"));
+    let skipped: Vec<_> = result.skipped.iter().map(|(_, reason)| reason.as_str()).collect();
+    assert_eq!(skipped, vec!["Target file already exists", "New name is identical to old name"]);
+    assert_eq!(result.errors.len(), 2);
+    assert!(result.errors[0].1.contains("does not exist"));
+    assert!(result.errors[1].1.contains("Target directory"));
+    
+    // Nothing was renamed
+    assert!(dir.join("a.txt").exists());
+    assert!(!dir.join("a_new.txt").exists());
 }
 
+#[tokio::test]
+async fn test_simulate_renames_sees_files_before_the_batch() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    fs::write(dir.join("a.txt"), "content").await.unwrap();
+    fs::write(dir.join("a_new.txt"), "content").await.unwrap();
+    
+    // a_new.txt is moved away first, but its old name is still taken when the targets are checked
+    let renames = vec![
+        file_rename(dir, "a_new.txt", "a_new_new.txt"),
+        file_rename(dir, "a.txt", "a_new.txt"),
+    ];
+    let result = simulate_renames(&renames, false).await;
+    assert_eq!(result.successful.len(), 1);
+    assert_eq!(result.skipped.len(), 1);
+    
+    // With overwrite, the existing target is replaced
+    let result = simulate_renames(&renames, true).await;
+    assert_eq!(result.successful.len(), 2);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_simulate_renames_matches_apply_for_dangling_symlink_target() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    fs::write(dir.join("a.txt"), "content").await.unwrap();
+    std::os::unix::fs::symlink(dir.join("nowhere.txt"), dir.join("link.txt")).unwrap();
+    
+    let renames = vec![file_rename(dir, "a.txt", "link.txt")];
+    let simulated = simulate_renames(&renames, false).await;
+    assert_eq!(simulated.successful.len(), 1);
+    assert!(simulated.skipped.is_empty());
+    
+    // The real apply goes ahead too
+    let preview = EnginePreviewResult { renames, warnings: vec![], has_empty_names: false };
+    handle_apply_command(dir, preview, false, true, false, "test command".to_string(), None, false, false).await.unwrap();
+    assert!(!dir.join("a.txt").exists());
+    assert_eq!(fs::read_to_string(dir.join("link.txt")).await.unwrap(), "content");
+}
+
+#[tokio::test]
+async fn test_apply_and_dry_run_reject_duplicate_targets() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    fs::write(dir.join("a.txt"), "a").await.unwrap();
+    fs::write(dir.join("b.txt"), "b").await.unwrap();
+    
+    let preview = EnginePreviewResult {
+        renames: vec![file_rename(dir, "a.txt", "same.txt"), file_rename(dir, "b.txt", "same.txt")],
+        warnings: vec![],
+        has_empty_names: false,
+    };
+    let err = handle_apply_dry_run(&RenamingEngine, &preview, false, false).await.unwrap_err();
+    assert_eq!(err.exit_code(), EXIT_VALIDATION_FAILED);
+    assert!(err.message().contains("same name"), "got: {}", err);
+    
+    let err = handle_apply_command(dir, preview, false, true, false, "test command".to_string(), None, false, false).await.unwrap_err();
+    assert_eq!(err.exit_code(), EXIT_VALIDATION_FAILED);
+    
+    // Nothing was renamed
+    assert!(dir.join("a.txt").exists());
+    assert!(dir.join("b.txt").exists());
+    assert!(!dir.join("same.txt").exists());
+}
+
+#[tokio::test]
+async fn test_handle_apply_dry_run() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    fs::write(dir.join("old.txt"), "content").await.unwrap();
+    
+    let preview = EnginePreviewResult {
+        renames: vec![file_rename(dir, "old.txt", "new.txt")],
+        warnings: vec![],
+        has_empty_names: false,
+    };
+    let result = handle_apply_dry_run(&RenamingEngine, &preview, false, true).await.unwrap();
+    
+    assert_eq!(result.successful.len(), 1);
+    assert!(dir.join("old.txt").exists());
+    assert!(!dir.join("new.txt").exists());
+    assert!(!dir.join(".fren_history.json").exists());
+}
+
+#[tokio::test]
+async fn test_handle_apply_dry_run_reports_failures() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    
+    let preview = EnginePreviewResult {
+        renames: vec![file_rename(dir, "missing.txt", "new.txt")],
+        warnings: vec![],
+        has_empty_names: false,
+    };
+    let err = handle_apply_dry_run(&RenamingEngine, &preview, false, false).await.unwrap_err();
+    
+    assert_eq!(err.exit_code(), EXIT_PARTIAL_APPLY);
+    assert!(err.message().contains("would fail"), "got: {}", err);
+}
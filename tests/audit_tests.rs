@@ -7,19 +7,14 @@ use frencli::audit::handle_audit_command;
 use freneng::audit::log_audit_entry;
 use std::path::PathBuf;
 use tempfile::TempDir;
-mod test_utils;
-use test_utils::DirGuard;
 
 #[tokio::test]
 async fn test_handle_audit_no_entries() {
     let temp_dir = TempDir::new().unwrap();
     let _keep_alive = &temp_dir;
     
-    // Change to temp dir for read_audit_log (which reads from current directory)
-    let _guard = DirGuard::new(temp_dir.path()).unwrap();
-    
     // Should not panic with no entries
-    let result = handle_audit_command(None, false).await;
+    let result = handle_audit_command(temp_dir.path(), None, false).await;
     assert!(result.is_ok());
 }
 
@@ -63,11 +58,8 @@ async fn test_handle_audit_with_entries() {
     }
     assert!(audit_file.exists(), "Audit file should exist at: {}", audit_file.display());
     
-    // Change to temp dir for read_audit_log (which reads from current directory)
-    let _guard = DirGuard::new(temp_dir.path()).unwrap();
-    
     // Should display entries
-    let result = handle_audit_command(None, false).await;
+    let result = handle_audit_command(temp_dir.path(), None, false).await;
     assert!(result.is_ok(), "Failed to handle audit command: {:?}", result);
 }
 
@@ -93,11 +85,8 @@ async fn test_handle_audit_with_limit() {
     let audit_file = temp_dir.path().join(".fren_audit.log");
     assert!(audit_file.exists());
     
-    // Change to temp dir for read_audit_log (which reads from current directory)
-    let _guard = DirGuard::new(temp_dir.path()).unwrap();
-    
     // Should limit to 5 most recent
-    let result = handle_audit_command(Some(5), false).await;
+    let result = handle_audit_command(temp_dir.path(), Some(5), false).await;
     assert!(result.is_ok());
 }
 
@@ -121,10 +110,7 @@ async fn test_handle_audit_json_output() {
     let audit_file = temp_dir.path().join(".fren_audit.log");
     assert!(audit_file.exists());
     
-    // Change to temp dir for read_audit_log (which reads from current directory)
-    let _guard = DirGuard::new(temp_dir.path()).unwrap();
-    
     // Should output as JSON
-    let result = handle_audit_command(None, true).await;
+    let result = handle_audit_command(temp_dir.path(), None, true).await;
     assert!(result.is_ok());
 }
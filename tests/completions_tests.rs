@@ -0,0 +1,56 @@
+//! Tests for the completions module.
+//! 
+//! These tests verify that completion scripts cover the command spec.
+
+use frencli::completions::generate_completions;
+use frencli::spec::SUBCOMMANDS;
+
+#[test]
+fn test_generate_completions_all_shells_cover_spec() {
+    for shell in ["bash", "zsh", "fish"] {
+        let script = generate_completions(shell).unwrap();
+        for spec in SUBCOMMANDS {
+            assert!(script.contains(spec.name), "{} script is missing '{}'", shell, spec.name);
+            for flag in spec.flags {
+                assert!(script.contains(flag.name), "{} script is missing '{} --{}'", shell, spec.name, flag.name);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_generate_completions_template_names_are_dynamic() {
+    for shell in ["bash", "zsh", "fish"] {
+        let script = generate_completions(shell).unwrap();
+        assert!(script.contains("frencli completions --template-names"), "{} script", shell);
+        // Built-in template names are looked up at completion time, not embedded
+        assert!(!script.contains("photo-date"), "{} script", shell);
+    }
+}
+
+#[test]
+fn test_generate_completions_bash_template_use() {
+    let script = generate_completions("bash").unwrap();
+    assert!(script.contains("template:--use)"));
+    assert!(script.contains("template:--on-conflict)"));
+    assert!(script.contains("complete -o default -F _frencli frencli"));
+}
+
+#[test]
+fn test_generate_completions_zsh_header() {
+    let script = generate_completions("zsh").unwrap();
+    assert!(script.starts_with("#compdef frencli"));
+}
+
+#[test]
+fn test_generate_completions_fish_flags() {
+    let script = generate_completions("fish").unwrap();
+    assert!(script.contains("complete -c frencli -n '__frencli_using audit' -l limit -x -d 'Limit number of entries to show'"));
+    assert!(script.contains("complete -c frencli -n '__frencli_using undo' -l check -d 'Check undo status'"));
+}
+
+#[test]
+fn test_generate_completions_unsupported_shell() {
+    let result = generate_completions("powershell");
+    assert_eq!(result.unwrap_err(), "Unsupported shell 'powershell'. Use one of: bash, zsh, fish.");
+}
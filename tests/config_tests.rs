@@ -0,0 +1,103 @@
+//! Tests for the config module.
+//!
+//! These tests verify parsing and loading of the config file with default options.
+
+use frencli::config::Config;
+use tempfile::TempDir;
+
+#[test]
+fn test_parse_empty_config_uses_defaults() {
+    let config = Config::parse("").unwrap();
+
+    assert_eq!(config, Config::default());
+    assert!(!config.list.recursive);
+    assert!(config.list.exclude.is_empty());
+    assert!(config.apply.audit);
+    assert!(!config.apply.overwrite);
+}
+
+#[test]
+fn test_parse_config_sections() {
+    let content = r#"
+[list]
+recursive = true
+exclude = [".git", "*.tmp"]
+fullpath = true
+
+[rename]
+json = true
+
+[apply]
+overwrite = true
+audit = false
+json = true
+"#;
+    let config = Config::parse(content).unwrap();
+
+    assert!(config.list.recursive);
+    assert_eq!(config.list.exclude, vec![".git".to_string(), "*.tmp".to_string()]);
+    assert!(config.list.fullpath);
+    assert!(!config.list.json);
+    assert!(config.rename.json);
+    assert!(config.apply.overwrite);
+    assert!(!config.apply.audit);
+    assert!(config.apply.json);
+}
+
+#[test]
+fn test_parse_config_partial_section_keeps_other_defaults() {
+    let config = Config::parse("[apply]\noverwrite = true\n").unwrap();
+
+    assert!(config.apply.overwrite);
+    assert!(config.apply.audit);
+}
+
+#[test]
+fn test_parse_config_unknown_field() {
+    let err = Config::parse("[list]\nrecurse = true\n").unwrap_err();
+
+    assert!(err.starts_with("line 2:"), "got: {}", err);
+    assert!(err.contains("recurse"), "got: {}", err);
+}
+
+#[test]
+fn test_parse_config_unknown_section() {
+    let err = Config::parse("[undo]\nyes = true\n").unwrap_err();
+    assert!(err.contains("undo"), "got: {}", err);
+}
+
+#[test]
+fn test_parse_config_wrong_type() {
+    let err = Config::parse("[apply]\naudit = \"no\"\n").unwrap_err();
+    assert!(err.contains("expected a boolean"), "got: {}", err);
+}
+
+#[test]
+fn test_load_explicit_path() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("frencli.toml");
+    std::fs::write(&path, "[list]\nrecursive = true\n").unwrap();
+
+    let config = Config::load(Some(path.to_str().unwrap())).unwrap().unwrap();
+    assert!(config.list.recursive);
+}
+
+#[test]
+fn test_load_explicit_path_missing() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("missing.toml");
+
+    let err = Config::load(Some(path.to_str().unwrap())).unwrap_err();
+    assert!(err.starts_with("Failed to read config file"), "got: {}", err);
+}
+
+#[test]
+fn test_load_explicit_path_invalid() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("frencli.toml");
+    std::fs::write(&path, "[list\n").unwrap();
+
+    let err = Config::load(Some(path.to_str().unwrap())).unwrap_err();
+    assert!(err.starts_with("Invalid config file"), "got: {}", err);
+    assert!(err.contains("frencli.toml': line 1:"), "got: {}", err);
+}
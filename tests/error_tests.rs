@@ -0,0 +1,50 @@
+//! Tests for the error module.
+//! 
+//! These tests verify that each kind of failure has its own exit code.
+
+use frencli::error::{
+    FrencliError, EXIT_USAGE, EXIT_NO_FILES_MATCHED, EXIT_VALIDATION_FAILED, EXIT_PARTIAL_APPLY, EXIT_ABORTED, EXIT_IO,
+};
+use freneng::FrenError;
+use std::collections::HashSet;
+
+fn all_kinds() -> Vec<FrencliError> {
+    vec![
+        FrencliError::Usage("usage".to_string()),
+        FrencliError::NoFilesMatched("no files".to_string()),
+        FrencliError::ValidationFailed("invalid".to_string()),
+        FrencliError::PartialApply("partial".to_string()),
+        FrencliError::Aborted("aborted".to_string()),
+        FrencliError::Io("io".to_string()),
+    ]
+}
+
+#[test]
+fn test_exit_codes_are_distinct_and_nonzero() {
+    let codes: HashSet<i32> = all_kinds().iter().map(FrencliError::exit_code).collect();
+    assert_eq!(codes.len(), all_kinds().len());
+    assert!(!codes.contains(&0));
+    assert!(!codes.contains(&1));
+}
+
+#[test]
+fn test_exit_code_constants() {
+    let codes: Vec<i32> = all_kinds().iter().map(FrencliError::exit_code).collect();
+    assert_eq!(codes, vec![EXIT_USAGE, EXIT_NO_FILES_MATCHED, EXIT_VALIDATION_FAILED, EXIT_PARTIAL_APPLY, EXIT_ABORTED, EXIT_IO]);
+}
+
+#[test]
+fn test_display_shows_message() {
+    let err = FrencliError::NoFilesMatched("No files matched *.jpg.".to_string());
+    assert_eq!(err.to_string(), "No files matched *.jpg.");
+    assert_eq!(err.message(), "No files matched *.jpg.");
+}
+
+#[test]
+fn test_from_engine_error() {
+    let io = FrenError::Io(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied"));
+    assert!(matches!(FrencliError::from(io), FrencliError::Io(_)));
+    
+    let pattern = FrencliError::from(FrenError::Pattern("[".to_string()));
+    assert_eq!(pattern, FrencliError::Usage("Invalid glob pattern: [".to_string()));
+}
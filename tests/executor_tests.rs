@@ -11,13 +11,17 @@ use frencli::executor::{
     handle_standalone_commands,
     validate_subcommand_combinations,
     extract_config,
+    extract_config_with_defaults,
     resolve_template_pattern,
     get_audit_pattern,
 };
 use frencli::subcommands::ParsedSubcommand;
+use frencli::config::Config;
+use frencli::list::FileType;
 use freneng::RenamingEngine;
-use frencli::templates::TemplateRegistry;
+use frencli::templates::{Template, TemplateRegistry, TemplateSource};
 use std::collections::HashMap;
+use std::path::Path;
 
 // Helper to create a ParsedSubcommand
 fn create_subcommand(name: &str, args: Vec<String>, flags: HashMap<String, Vec<String>>) -> ParsedSubcommand {
@@ -25,6 +29,7 @@ fn create_subcommand(name: &str, args: Vec<String>, flags: HashMap<String, Vec<S
         name: name.to_string(),
         args,
         flags,
+        warnings: Vec::new(),
     }
 }
 
@@ -66,6 +71,22 @@ fn test_validate_subcommand_combinations_rename_and_template_use() {
     assert!(result.unwrap_err().contains("Cannot use both 'rename' and 'template --use'"));
 }
 
+#[test]
+fn test_validate_subcommand_combinations_template_try() {
+    let list = create_subcommand("list", vec!["*.txt".to_string()], HashMap::new());
+    let try_all = create_subcommand("template", vec![], create_flags("try", None));
+    assert!(validate_subcommand_combinations(&[list.clone(), try_all.clone()]).is_ok());
+    
+    // --try only previews, so it cannot feed rename, validate or apply
+    for other in ["apply", "validate"] {
+        let subcommands = vec![list.clone(), try_all.clone(), create_subcommand(other, vec![], HashMap::new())];
+        let error = validate_subcommand_combinations(&subcommands).unwrap_err();
+        assert!(error.contains("'template --try' only previews templates"));
+    }
+    let rename = create_subcommand("rename", vec!["%N.%E".to_string()], HashMap::new());
+    assert!(validate_subcommand_combinations(&[list, try_all, rename]).is_err());
+}
+
 #[test]
 fn test_validate_subcommand_combinations_template_without_use() {
     let subcommands = vec![
@@ -100,6 +121,130 @@ fn test_extract_config_list() {
     assert!(config.list_json);
 }
 
+#[test]
+fn test_extract_config_list_filters() {
+    let mut flags = HashMap::new();
+    flags.insert("min-size".to_string(), vec!["1K".to_string()]);
+    flags.insert("max-size".to_string(), vec!["2MiB".to_string()]);
+    flags.insert("newer".to_string(), vec!["7d".to_string()]);
+    flags.insert("type".to_string(), vec!["dir".to_string()]);
+    let subcommands = vec![
+        create_subcommand("list", vec!["*".to_string()], flags),
+    ];
+
+    let config = extract_config(&subcommands).unwrap();
+    assert_eq!(config.list_filters.min_size, Some(1_000));
+    assert_eq!(config.list_filters.max_size, Some(2 * 1024 * 1024));
+    assert!(config.list_filters.newer.is_some());
+    assert!(config.list_filters.older.is_none());
+    assert_eq!(config.list_filters.file_type, Some(FileType::Dir));
+}
+
+#[test]
+fn test_extract_config_list_filters_invalid() {
+    let mut flags = HashMap::new();
+    flags.insert("min-size".to_string(), vec!["2M".to_string()]);
+    flags.insert("max-size".to_string(), vec!["1M".to_string()]);
+    let subcommands = vec![
+        create_subcommand("list", vec!["*".to_string()], flags),
+    ];
+    assert_eq!(
+        extract_config(&subcommands).unwrap_err(),
+        "'--min-size' is larger than '--max-size', so no file can match."
+    );
+
+    let subcommands = vec![
+        create_subcommand("list", vec!["*".to_string()], create_flags("older", Some("soon"))),
+    ];
+    assert!(extract_config(&subcommands).unwrap_err().starts_with("Invalid time 'soon'"));
+}
+
+#[test]
+fn test_extract_config_with_defaults_from_config_file() {
+    let defaults = Config::parse("[list]\nrecursive = true\nexclude = [\".git\"]\n[apply]\naudit = false\noverwrite = true\n").unwrap();
+    let subcommands = vec![
+        create_subcommand("list", vec!["*.txt".to_string()], HashMap::new()),
+        create_subcommand("rename", vec!["%N.%E".to_string()], HashMap::new()),
+        create_subcommand("apply", vec![], HashMap::new()),
+    ];
+    
+    let config = extract_config_with_defaults(&subcommands, &defaults).unwrap();
+    assert!(config.list_recursive);
+    assert_eq!(config.list_exclude, vec![".git".to_string()]);
+    assert!(config.apply_overwrite);
+    assert!(config.apply_no_audit);
+    assert!(!config.apply_json);
+}
+
+#[test]
+fn test_extract_config_with_defaults_flags_take_precedence() {
+    let defaults = Config::parse("[list]\nexclude = [\".git\"]\n").unwrap();
+    let subcommands = vec![
+        create_subcommand("list", vec!["*.txt".to_string()], create_flags("exclude", Some("*.tmp"))),
+        create_subcommand("apply", vec![], create_flags("no-audit", None)),
+    ];
+    
+    let config = extract_config_with_defaults(&subcommands, &defaults).unwrap();
+    // --exclude replaces the config file excludes
+    assert_eq!(config.list_exclude, vec!["*.tmp".to_string()]);
+    assert!(config.apply_no_audit);
+}
+
+#[test]
+fn test_extract_config_with_defaults_negating_flags() {
+    let defaults = Config::parse("[list]\nrecursive = true\nfullpath = true\njson = true\nexclude = [\".git\"]\n[rename]\njson = true\n[apply]\naudit = false\noverwrite = true\njson = true\n").unwrap();
+    let flags = |names: &[&str]| names.iter().map(|name| (name.to_string(), vec![])).collect::<HashMap<_, _>>();
+    let subcommands = vec![
+        create_subcommand("list", vec!["*.txt".to_string()], flags(&["no-recursive", "no-fullpath", "no-json", "no-exclude"])),
+        create_subcommand("rename", vec!["%N.%E".to_string()], flags(&["no-json"])),
+        create_subcommand("apply", vec![], flags(&["no-overwrite", "no-json", "audit"])),
+    ];
+    
+    let config = extract_config_with_defaults(&subcommands, &defaults).unwrap();
+    assert!(!config.list_recursive);
+    assert!(!config.list_fullpath);
+    assert!(!config.list_json);
+    assert!(config.list_exclude.is_empty());
+    assert!(!config.rename_json);
+    assert!(!config.apply_overwrite);
+    assert!(!config.apply_json);
+    assert!(!config.apply_no_audit);
+}
+
+#[test]
+fn test_extract_config_rejects_switch_and_its_negation() {
+    let mut flags = create_flags("overwrite", None);
+    flags.insert("no-overwrite".to_string(), vec![]);
+    let subcommands = vec![create_subcommand("apply", vec![], flags)];
+    
+    let error = extract_config(&subcommands).unwrap_err();
+    assert!(error.contains("Cannot use both '--overwrite' and '--no-overwrite'"));
+}
+
+#[test]
+fn test_extract_config_template_no_json() {
+    let defaults = Config::parse("[rename]\njson = true\n").unwrap();
+    let mut flags = create_flags("use", Some("lowercase"));
+    flags.insert("no-json".to_string(), vec![]);
+    let subcommands = vec![create_subcommand("template", vec![], flags)];
+    
+    let config = extract_config_with_defaults(&subcommands, &defaults).unwrap();
+    assert!(!config.rename_json);
+    
+    let subcommands = vec![create_subcommand("template", vec![], create_flags("no-json", None))];
+    assert!(extract_config(&subcommands).unwrap_err().contains("requires '--use"));
+}
+
+#[test]
+fn test_extract_config_without_config_file_records_audit() {
+    let subcommands = vec![
+        create_subcommand("apply", vec![], HashMap::new()),
+    ];
+    
+    let config = extract_config(&subcommands).unwrap();
+    assert!(!config.apply_no_audit);
+}
+
 #[test]
 fn test_extract_config_list_empty_patterns() {
     let subcommands = vec![
@@ -194,6 +339,85 @@ fn test_extract_config_template_use() {
     assert_eq!(config.template_use, Some("photo-date".to_string()));
 }
 
+#[test]
+fn test_extract_config_template_set() {
+    let mut flags = HashMap::new();
+    flags.insert("use".to_string(), vec!["trip".to_string()]);
+    flags.insert("set".to_string(), vec!["prefix=rome".to_string(), "width=2".to_string()]);
+    
+    let subcommands = vec![
+        create_subcommand("template", vec![], flags),
+    ];
+    
+    let config = extract_config(&subcommands).unwrap();
+    assert_eq!(config.template_set, vec![
+        ("prefix".to_string(), "rome".to_string()),
+        ("width".to_string(), "2".to_string()),
+    ]);
+}
+
+#[test]
+fn test_extract_config_template_set_invalid() {
+    let mut flags = HashMap::new();
+    flags.insert("use".to_string(), vec!["trip".to_string()]);
+    flags.insert("set".to_string(), vec!["prefix".to_string()]);
+    let result = extract_config(&[create_subcommand("template", vec![], flags)]);
+    assert!(result.unwrap_err().contains("Expected KEY=VALUE"));
+    
+    // --set without --use
+    let result = extract_config(&[create_subcommand("template", vec![], create_flags("set", Some("a=b")))]);
+    assert!(result.unwrap_err().contains("requires '--use"));
+}
+
+#[test]
+fn test_extract_config_rename_chain() {
+    let mut flags = HashMap::new();
+    flags.insert("stages".to_string(), vec![]);
+    let subcommands = vec![
+        create_subcommand("rename", vec!["%L%N.%E".to_string(), "%N_%C3.%E".to_string()], flags),
+    ];
+    
+    let config = extract_config(&subcommands).unwrap();
+    assert_eq!(config.rename_pattern, Some("%L%N.%E".to_string()));
+    assert_eq!(config.rename_chain, vec!["%N_%C3.%E".to_string()]);
+    assert_eq!(config.rename_patterns(), vec!["%L%N.%E".to_string(), "%N_%C3.%E".to_string()]);
+    assert!(config.show_stages);
+}
+
+#[test]
+fn test_extract_config_template_stages_requires_use() {
+    let result = extract_config(&[create_subcommand("template", vec![], create_flags("stages", None))]);
+    assert!(result.unwrap_err().contains("'template --stages' requires '--use"));
+    
+    let mut flags = create_flags("use", Some("lowercase"));
+    flags.insert("stages".to_string(), vec![]);
+    let config = extract_config(&[create_subcommand("template", vec![], flags)]).unwrap();
+    assert!(config.show_stages);
+}
+
+#[test]
+fn test_extract_config_template_try() {
+    let mut flags = HashMap::new();
+    flags.insert("try".to_string(), vec!["lowercase".to_string(), "3".to_string()]);
+    flags.insert("category".to_string(), vec!["case".to_string()]);
+    
+    let config = extract_config(&[create_subcommand("template", vec![], flags)]).unwrap();
+    assert_eq!(config.template_try, Some(vec!["lowercase".to_string(), "3".to_string()]));
+    assert_eq!(config.template_category, Some("case".to_string()));
+    
+    let config = extract_config(&[create_subcommand("template", vec![], create_flags("try", None))]).unwrap();
+    assert_eq!(config.template_try, Some(vec![]));
+}
+
+#[test]
+fn test_extract_config_template_category_requires_list() {
+    let mut flags = create_flags("use", Some("lowercase"));
+    flags.insert("category".to_string(), vec!["photo".to_string()]);
+    
+    let result = extract_config(&[create_subcommand("template", vec![], flags)]);
+    assert!(result.unwrap_err().contains("only be used with 'template --list'"));
+}
+
 #[test]
 fn test_extract_config_validate() {
     let mut flags = HashMap::new();
@@ -226,6 +450,28 @@ fn test_extract_config_apply() {
     assert!(config.apply_json);
 }
 
+#[test]
+fn test_extract_config_apply_dry_run() {
+    let subcommands = vec![
+        create_subcommand("apply", vec![], create_flags("dry-run", None)),
+    ];
+    
+    let config = extract_config(&subcommands).unwrap();
+    assert!(config.apply_dry_run);
+}
+
+#[test]
+fn test_extract_config_apply_dry_run_with_interactive() {
+    let mut flags = create_flags("dry-run", None);
+    flags.insert("interactive".to_string(), vec![]);
+    let subcommands = vec![
+        create_subcommand("apply", vec![], flags),
+    ];
+    
+    let err = extract_config(&subcommands).unwrap_err();
+    assert!(err.contains("--dry-run"), "got: {}", err);
+}
+
 #[test]
 fn test_extract_config_multiple_subcommands() {
     let mut list_flags = HashMap::new();
@@ -261,7 +507,7 @@ fn test_resolve_template_pattern_by_name() {
     
     // Add a test template if registry supports it, or use existing
     // For now, test with a template that might exist
-    let result = resolve_template_pattern(&registry, "photo-date");
+    let result = resolve_template_pattern(&registry, "photo-date", &[]);
     
     // This might succeed or fail depending on what templates exist
     // We just verify the function works
@@ -283,20 +529,20 @@ fn test_resolve_template_pattern_by_index() {
     
     if templates.is_empty() {
         // No templates, test error case
-        let result = resolve_template_pattern(&registry, "1");
+        let result = resolve_template_pattern(&registry, "1", &[]);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("out of range"));
     } else {
         // Test with valid index
-        let result = resolve_template_pattern(&registry, "1");
+        let result = resolve_template_pattern(&registry, "1", &[]);
         assert!(result.is_ok());
         
         // Test with invalid index (0)
-        let result = resolve_template_pattern(&registry, "0");
+        let result = resolve_template_pattern(&registry, "0", &[]);
         assert!(result.is_err());
         
         // Test with out of range index
-        let result = resolve_template_pattern(&registry, &(templates.len() + 1).to_string());
+        let result = resolve_template_pattern(&registry, &(templates.len() + 1).to_string(), &[]);
         assert!(result.is_err());
     }
 }
@@ -304,10 +550,55 @@ fn test_resolve_template_pattern_by_index() {
 #[test]
 fn test_resolve_template_pattern_invalid_index() {
     let registry = TemplateRegistry::new();
-    let result = resolve_template_pattern(&registry, "999");
+    let result = resolve_template_pattern(&registry, "999", &[]);
     assert!(result.is_err());
 }
 
+fn registry_with_param_template() -> TemplateRegistry {
+    let mut registry = TemplateRegistry::new();
+    let mut template = Template::new("{prefix}_%C{width}.%E", TemplateSource::User);
+    template.defaults.insert("width".to_string(), "3".to_string());
+    registry.insert("trip".to_string(), template);
+    registry
+}
+
+#[test]
+fn test_resolve_template_pattern_with_params() {
+    let registry = registry_with_param_template();
+    let params = vec![("prefix".to_string(), "rome".to_string())];
+    
+    let result = resolve_template_pattern(&registry, "trip", &params);
+    assert_eq!(result, Ok(vec!["rome_%C3.%E".to_string()]));
+    
+    // Explicit values override defaults
+    let params = vec![("prefix".to_string(), "rome".to_string()), ("width".to_string(), "2".to_string())];
+    let result = resolve_template_pattern(&registry, "trip", &params);
+    assert_eq!(result, Ok(vec!["rome_%C2.%E".to_string()]));
+}
+
+#[test]
+fn test_resolve_template_pattern_missing_param() {
+    let registry = registry_with_param_template();
+    
+    let error = resolve_template_pattern(&registry, "trip", &[]).unwrap_err();
+    assert!(error.contains("Template 'trip'"));
+    assert!(error.contains("missing value for parameter(s): prefix"));
+    assert!(error.contains("--set prefix=VALUE"));
+}
+
+#[test]
+fn test_resolve_template_pattern_unknown_param() {
+    let registry = registry_with_param_template();
+    let params = vec![("prefix".to_string(), "a".to_string()), ("size".to_string(), "2".to_string())];
+    
+    let error = resolve_template_pattern(&registry, "trip", &params).unwrap_err();
+    assert!(error.contains("unknown parameter 'size'"));
+    assert!(error.contains("available: prefix, width"));
+    
+    let error = resolve_template_pattern(&registry, "lowercase", &params).unwrap_err();
+    assert!(error.contains("takes no parameters"));
+}
+
 // ============================================================================
 // get_audit_pattern tests
 // ============================================================================
@@ -315,22 +606,22 @@ fn test_resolve_template_pattern_invalid_index() {
 #[test]
 fn test_get_audit_pattern_from_rename_pattern() {
     let registry = TemplateRegistry::new();
-    let rename_pattern = Some("%N_backup.%E".to_string());
+    let rename_patterns = vec!["%N_backup.%E".to_string()];
     let template_use = None;
     
-    let result = get_audit_pattern(&rename_pattern, &template_use, &registry);
+    let result = get_audit_pattern(&rename_patterns, &template_use, &[], &registry);
     assert_eq!(result, Some("%N_backup.%E".to_string()));
 }
 
 #[test]
 fn test_get_audit_pattern_from_template() {
     let registry = TemplateRegistry::new();
-    let rename_pattern = None;
+    let rename_patterns: Vec<String> = Vec::new();
     let templates = registry.list();
     
     if !templates.is_empty() {
         let template_use = Some("1".to_string());
-        let result = get_audit_pattern(&rename_pattern, &template_use, &registry);
+        let result = get_audit_pattern(&rename_patterns, &template_use, &[], &registry);
         // Should resolve to the template pattern
         assert!(result.is_some());
     }
@@ -339,24 +630,39 @@ fn test_get_audit_pattern_from_template() {
 #[test]
 fn test_get_audit_pattern_none() {
     let registry = TemplateRegistry::new();
-    let rename_pattern = None;
+    let rename_patterns: Vec<String> = Vec::new();
     let template_use = None;
     
-    let result = get_audit_pattern(&rename_pattern, &template_use, &registry);
+    let result = get_audit_pattern(&rename_patterns, &template_use, &[], &registry);
     assert_eq!(result, None);
 }
 
 #[test]
 fn test_get_audit_pattern_prefers_rename_over_template() {
     let registry = TemplateRegistry::new();
-    let rename_pattern = Some("%N.%E".to_string());
+    let rename_patterns = vec!["%N.%E".to_string()];
     let template_use = Some("1".to_string());
     
-    let result = get_audit_pattern(&rename_pattern, &template_use, &registry);
-    // Should prefer rename_pattern
+    let result = get_audit_pattern(&rename_patterns, &template_use, &[], &registry);
+    // Should prefer rename patterns
     assert_eq!(result, Some("%N.%E".to_string()));
 }
 
+#[test]
+fn test_get_audit_pattern_chained() {
+    let mut registry = TemplateRegistry::new();
+    let mut template = Template::new("%L%N.%E", TemplateSource::User);
+    template.chain = vec!["%N_%C3.%E".to_string()];
+    registry.insert("clean".to_string(), template);
+    
+    let rename_patterns = vec!["%L%N.%E".to_string(), "%N%R/ /_.%E".to_string()];
+    let result = get_audit_pattern(&rename_patterns, &None, &[], &registry);
+    assert_eq!(result, Some("%L%N.%E => %N%R/ /_.%E".to_string()));
+    
+    let result = get_audit_pattern(&[], &Some("clean".to_string()), &[], &registry);
+    assert_eq!(result, Some("%L%N.%E => %N_%C3.%E".to_string()));
+}
+
 // ============================================================================
 // handle_standalone_commands tests
 // ============================================================================
@@ -370,7 +676,7 @@ async fn test_handle_standalone_commands_no_standalone() {
         create_subcommand("list", vec!["*.txt".to_string()], HashMap::new()),
     ];
     
-    let result = handle_standalone_commands(&subcommands, &engine, &registry).await;
+    let result = handle_standalone_commands(&subcommands, &engine, &registry, Path::new("")).await;
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), None);
 }
@@ -385,9 +691,36 @@ async fn test_handle_standalone_commands_undo_with_others() {
         create_subcommand("list", vec!["*.txt".to_string()], HashMap::new()),
     ];
     
-    let result = handle_standalone_commands(&subcommands, &engine, &registry).await;
+    let result = handle_standalone_commands(&subcommands, &engine, &registry, Path::new("")).await;
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("cannot be used with other subcommands"));
+    assert!(result.unwrap_err().message().contains("cannot be used with other subcommands"));
+}
+
+#[tokio::test]
+async fn test_handle_standalone_commands_template_import_with_others() {
+    let engine = RenamingEngine;
+    let registry = TemplateRegistry::new();
+    
+    let subcommands = vec![
+        create_subcommand("template", vec![], create_flags("import", Some("bundle.json"))),
+        create_subcommand("list", vec!["*.txt".to_string()], HashMap::new()),
+    ];
+    
+    let result = handle_standalone_commands(&subcommands, &engine, &registry, Path::new("")).await;
+    assert!(result.unwrap_err().message().contains("'--export' and '--import' cannot be used with other subcommands"));
+}
+
+#[tokio::test]
+async fn test_handle_standalone_commands_on_conflict_requires_import() {
+    let engine = RenamingEngine;
+    let registry = TemplateRegistry::new();
+    let mut flags = create_flags("export", Some("bundle.json"));
+    flags.insert("on-conflict".to_string(), vec!["skip".to_string()]);
+    
+    let subcommands = vec![create_subcommand("template", vec![], flags)];
+    
+    let result = handle_standalone_commands(&subcommands, &engine, &registry, Path::new("")).await;
+    assert!(result.unwrap_err().message().contains("'--on-conflict' can only be used with 'template --import'"));
 }
 
 #[tokio::test]
@@ -400,9 +733,9 @@ async fn test_handle_standalone_commands_audit_with_others() {
         create_subcommand("list", vec!["*.txt".to_string()], HashMap::new()),
     ];
     
-    let result = handle_standalone_commands(&subcommands, &engine, &registry).await;
+    let result = handle_standalone_commands(&subcommands, &engine, &registry, Path::new("")).await;
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("cannot be used with other subcommands"));
+    assert!(result.unwrap_err().message().contains("cannot be used with other subcommands"));
 }
 
 #[tokio::test]
@@ -418,9 +751,9 @@ async fn test_handle_standalone_commands_undo_both_flags() {
         create_subcommand("undo", vec![], flags),
     ];
     
-    let result = handle_standalone_commands(&subcommands, &engine, &registry).await;
+    let result = handle_standalone_commands(&subcommands, &engine, &registry, Path::new("")).await;
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("Cannot use both 'undo --check' and 'undo --apply'"));
+    assert!(result.unwrap_err().message().contains("Cannot use both 'undo --check' and 'undo --apply'"));
 }
 
 #[tokio::test]
@@ -432,9 +765,9 @@ async fn test_handle_standalone_commands_undo_no_flags() {
         create_subcommand("undo", vec![], HashMap::new()),
     ];
     
-    let result = handle_standalone_commands(&subcommands, &engine, &registry).await;
+    let result = handle_standalone_commands(&subcommands, &engine, &registry, Path::new("")).await;
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("requires either '--check' or '--apply'"));
+    assert!(result.unwrap_err().message().contains("requires either '--check' or '--apply'"));
 }
 
 #[tokio::test]
@@ -447,8 +780,26 @@ async fn test_handle_standalone_commands_template_list() {
     ];
     
     // template --list should execute and return Ok(Some(()))
-    let result = handle_standalone_commands(&subcommands, &engine, &registry).await;
+    let result = handle_standalone_commands(&subcommands, &engine, &registry, Path::new("")).await;
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), Some(()));
 }
 
+
+#[tokio::test]
+async fn test_handle_standalone_commands_template_save_with_others() {
+    let engine = RenamingEngine;
+    let registry = TemplateRegistry::new();
+    
+    let mut flags = HashMap::new();
+    flags.insert("save".to_string(), vec!["podcast".to_string(), "%N.%E".to_string()]);
+    
+    let subcommands = vec![
+        create_subcommand("template", vec![], flags),
+        create_subcommand("list", vec!["*.txt".to_string()], HashMap::new()),
+    ];
+    
+    let result = handle_standalone_commands(&subcommands, &engine, &registry, Path::new("")).await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().message().contains("cannot be used with other subcommands"));
+}
@@ -6,7 +6,8 @@
 //! don't panic and can be called. Full output verification would require
 //! stdout capture or integration tests.
 
-use frencli::help::{print_main_help, print_subcommand_help};
+use frencli::help::{print_main_help, print_subcommand_help, main_help_text, subcommand_help_text};
+use frencli::spec::SUBCOMMANDS;
 
 #[test]
 fn test_print_main_help_no_panic() {
@@ -81,3 +82,47 @@ fn test_help_output_structure() {
     print_main_help();
 }
 
+
+#[test]
+fn test_main_help_lists_every_subcommand() {
+    let text = main_help_text();
+    for spec in SUBCOMMANDS {
+        assert!(text.contains(spec.name), "main help is missing '{}'", spec.name);
+        assert!(text.contains(spec.summary));
+    }
+}
+
+#[test]
+fn test_main_help_lists_global_options() {
+    let text = main_help_text();
+    assert!(text.contains("--config <FILE>"));
+    assert!(text.contains("--no-config"));
+    assert!(text.contains("--version"));
+}
+
+#[test]
+fn test_subcommand_help_lists_every_flag() {
+    for spec in SUBCOMMANDS {
+        let text = subcommand_help_text(spec.name).unwrap();
+        for flag in spec.flags {
+            assert!(text.contains(&format!("--{}", flag.name)),
+                "'{}' help is missing '--{}'", spec.name, flag.name);
+        }
+        assert!(text.contains("--help"));
+    }
+}
+
+#[test]
+fn test_subcommand_help_has_no_stale_flags() {
+    let validate = subcommand_help_text("validate").unwrap();
+    assert!(!validate.contains("--change"));
+    assert!(!validate.contains("--template"));
+    let list = subcommand_help_text("list").unwrap();
+    assert!(!list.contains("--apply"));
+    assert!(!list.contains("--yes"));
+}
+
+#[test]
+fn test_subcommand_help_text_unknown() {
+    assert!(subcommand_help_text("make").is_none());
+}
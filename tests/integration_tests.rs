@@ -143,15 +143,14 @@ fn extract_renames(output: &str) -> Vec<(String, String)> {
         if in_preview && (line.contains("Preview mode") || line.contains("Successfully") || line.is_empty()) {
             break;
         }
-        if in_preview {
-            if let Some(arrow_pos) = line.find("->") {
+        if in_preview
+            && let Some(arrow_pos) = line.find("->") {
                 let old = line[..arrow_pos].trim().to_string();
                 let new = line[arrow_pos + 2..].trim().to_string();
                 if !old.is_empty() && !new.is_empty() && old != "Old Name" && !old.contains("Name") {
                     renames.push((old, new));
                 }
             }
-        }
     }
     renames
 }
@@ -436,7 +435,7 @@ fn test_list_recursive() {
         .arg("list")
         .arg("*.jpg")
         .arg("--recursive")
-        .current_dir(&test_dir)
+        .current_dir(test_dir)
         .output()
         .unwrap();
     
@@ -464,7 +463,7 @@ fn test_list_recursive() {
     let mut cmd2 = Command::new(&binary);
     cmd2.arg("list")
         .arg("*.jpg")
-        .current_dir(&test_dir)
+        .current_dir(test_dir)
         .stdin(Stdio::null());
     let output2 = cmd2.output().unwrap();
     
@@ -689,7 +688,7 @@ fn test_undo_functionality() {
         .arg("undo_feat2.txt")
         .arg("apply")
         .arg("--yes")
-        .current_dir(&test_dir)
+        .current_dir(test_dir)
         .stdin(Stdio::null());
     let output1 = cmd1.output().unwrap();
     
@@ -707,7 +706,7 @@ fn test_undo_functionality() {
         .arg("undo")
         .arg("--apply")
         .arg("--yes")  // Skip confirmation
-        .current_dir(&test_dir)
+        .current_dir(test_dir)
         .stdin(Stdio::null())
         .output()
         .unwrap();
@@ -881,7 +880,7 @@ fn test_undo_with_conflicts() {
         .arg("undo_conf2.txt")
         .arg("apply")
         .arg("--yes")
-        .current_dir(&test_dir)
+        .current_dir(test_dir)
         .stdin(Stdio::null());
     let output1 = cmd1.output().unwrap();
     
@@ -899,7 +898,7 @@ fn test_undo_with_conflicts() {
         .arg("undo")
         .arg("--apply")
         .arg("--yes")  // Skip confirmation
-        .current_dir(&test_dir)
+        .current_dir(test_dir)
         .stdin(Stdio::null())
         .output()
         .unwrap();
@@ -918,7 +917,7 @@ fn test_undo_with_conflicts() {
         .arg("undo_conf2.txt")
         .arg("apply")
         .arg("--yes")
-        .current_dir(&test_dir)
+        .current_dir(test_dir)
         .output()
         .unwrap();
     
@@ -939,7 +938,7 @@ fn test_undo_with_conflicts() {
         .arg("undo")
         .arg("--apply")
         .arg("--yes")  // Skip confirmation
-        .current_dir(&test_dir)
+        .current_dir(test_dir)
         .stdin(Stdio::null())
         .output()
         .unwrap();
@@ -948,7 +947,7 @@ fn test_undo_with_conflicts() {
             "Should detect occupied source location. Output: {}", stdout);
     
     // Cleanup
-    let _ = std::fs::remove_dir_all(&test_dir);
+    let _ = std::fs::remove_dir_all(test_dir);
 }
 
 #[test]
@@ -1270,8 +1269,8 @@ fn test_empty_name_blocking() {
     let output = cmd.output().unwrap();
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("ERROR: One or more files would have an empty name"));
-    assert_ne!(output.status.code(), Some(0));
+    assert!(stderr.contains("Error: One or more files would have an empty name"), "stderr: {}", stderr);
+    assert_eq!(output.status.code(), Some(4));
 }
 
 #[test]
@@ -1338,7 +1337,7 @@ fn test_recursive_directory_support() {
         .arg("--recursive")
         .arg("rename")
         .arg("renamed_%C2.%E")
-        .current_dir(&test_dir)
+        .current_dir(test_dir)
         .output()
         .unwrap();
     
@@ -1378,7 +1377,7 @@ fn test_recursive_with_double_star_pattern() {
         .arg("**/*.txt")
         .arg("rename")
         .arg("renamed_%C2.%E")
-        .current_dir(&test_dir)
+        .current_dir(test_dir)
         .output()
         .unwrap();
     
@@ -1411,7 +1410,7 @@ fn test_non_recursive_does_not_search_subdirs() {
         .arg("*.txt")
         .arg("rename")
         .arg("renamed.%E")
-        .current_dir(&test_dir)
+        .current_dir(test_dir)
         .output()
         .unwrap();
     
@@ -1564,6 +1563,44 @@ fn test_template_listing() {
     assert!(stdout.contains("->")); // Should show pattern mapping
 }
 
+#[test]
+fn test_template_listing_category_json() {
+    let binary = get_binary_path();
+    
+    let output = Command::new(&binary)
+        .args(["template", "--list", "--category", "counter", "--json"])
+        .output()
+        .unwrap();
+    
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let items = json.as_array().unwrap();
+    assert!(!items.is_empty());
+    assert!(items.iter().all(|item| item["category"] == "counter"));
+    assert!(items.iter().any(|item| item["name"] == "counter-3" && item["description"].is_string()));
+}
+
+#[test]
+fn test_template_try_previews_templates() {
+    let binary = get_binary_path();
+    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let test_data_dir = workspace_root.join("test_data");
+    
+    let output = Command::new(&binary)
+        .args(["list", "Chapter*.md", "template", "--try", "counter-3", "uppercase"])
+        .current_dir(&test_data_dir)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Previewing 2 template(s) on 3 of 3 file(s)"));
+    assert!(stdout.contains("001.md"));
+    assert!(stdout.contains("CHAPTER 01.MD"));
+}
+
 #[test]
 fn test_template_usage() {
     let binary = get_binary_path();
@@ -1585,7 +1622,7 @@ fn test_template_usage() {
     let renames = extract_renames(&stdout);
     assert!(!renames.is_empty());
     let (_, new) = &renames[0];
-    // lowercase-name template should be %N%L.%E
+    // lowercase-name lowercases the name and keeps the (already lowercase) extension
     assert_eq!(new, "interdisplay-regular.ttf");
 }
 
@@ -1751,3 +1788,564 @@ fn test_name_substring_selection() {
     // First 3 chars of "InterDisplay-Bold" are "Int"
     assert_eq!(new, "Int.ttf");
 }
+
+#[test]
+fn test_template_check_exit_code() {
+    let binary = get_binary_path();
+    let config_dir = TempDir::new().unwrap();
+    let work_dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(config_dir.path().join("frencli")).unwrap();
+    std::fs::write(
+        config_dir.path().join("frencli").join("templates.json"),
+        r#"{"templates": {"broken": "%N_%Q"}}"#,
+    ).unwrap();
+    
+    let output = Command::new(&binary)
+        .args(["template", "--check"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(4), "validation failures exit with 4");
+    assert!(stdout.contains("unknown token '%Q'"), "got: {}", stdout);
+    
+    // The problem is also reported as a warning when templates are loaded for other commands
+    let output = Command::new(&binary)
+        .args(["template", "--list"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success());
+    assert!(stderr.contains("Warning: user template 'broken'"), "got: {}", stderr);
+}
+
+#[test]
+fn test_malformed_user_templates_do_not_block_other_commands() {
+    let binary = get_binary_path();
+    let config_dir = TempDir::new().unwrap();
+    let work_dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(config_dir.path().join("frencli")).unwrap();
+    std::fs::write(config_dir.path().join("frencli").join("templates.json"), "{ not json").unwrap();
+    std::fs::write(work_dir.path().join("a.txt"), "a").unwrap();
+    
+    let output = Command::new(&binary)
+        .args(["list", "*.txt"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "got: {}", stderr);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("a.txt"));
+    assert!(stderr.contains("templates.json") && stderr.contains("skipped"), "got: {}", stderr);
+    
+    // Completion scripts stay silent and still get the built-in names
+    let output = Command::new(&binary)
+        .args(["completions", "--template-names"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+    assert!(String::from_utf8_lossy(&output.stdout).lines().any(|name| name == "photo-date"));
+    
+    // 'template --check' reports the broken file as a problem
+    let output = Command::new(&binary)
+        .args(["template", "--check"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("templates.json"));
+}
+
+#[test]
+fn test_malformed_project_templates_in_ancestor_are_skipped() {
+    let binary = get_binary_path();
+    let config_dir = TempDir::new().unwrap();
+    let project_dir = TempDir::new().unwrap();
+    let work_dir = project_dir.path().join("photos");
+    std::fs::create_dir_all(&work_dir).unwrap();
+    std::fs::write(project_dir.path().join(".frentemplates"), r#"{"templates": {"team": 1}}"#).unwrap();
+    std::fs::write(work_dir.join("a.txt"), "a").unwrap();
+    
+    let output = Command::new(&binary)
+        .args(["list", "*.txt", "template", "--use", "lowercase"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .current_dir(&work_dir)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "got: {}", stderr);
+    assert!(stderr.contains(".frentemplates") && stderr.contains("template 'team'"), "got: {}", stderr);
+}
+
+#[test]
+fn test_completions_template_names_include_user_templates() {
+    let binary = get_binary_path();
+    let config_dir = TempDir::new().unwrap();
+    let work_dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(config_dir.path().join("frencli")).unwrap();
+    std::fs::write(
+        config_dir.path().join("frencli").join("templates.json"),
+        r#"{"templates": {"podcast": "%T%N_%C3.%E"}}"#,
+    ).unwrap();
+    
+    let output = Command::new(&binary)
+        .args(["completions", "--template-names"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    let names: Vec<&str> = stdout.lines().collect();
+    assert!(names.contains(&"podcast"));
+    assert!(names.contains(&"photo-date"));
+}
+
+#[test]
+fn test_user_templates_toml_file() {
+    let binary = get_binary_path();
+    let config_dir = TempDir::new().unwrap();
+    let work_dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(config_dir.path().join("frencli")).unwrap();
+    let toml_path = config_dir.path().join("frencli").join("templates.toml");
+    std::fs::write(&toml_path, "[templates]\npodcast = \"%T%N_%C3.%E\"\n").unwrap();
+    let run = |args: &[&str]| {
+        Command::new(&binary)
+            .args(args)
+            .env("XDG_CONFIG_HOME", config_dir.path())
+            .current_dir(work_dir.path())
+            .stdin(Stdio::null())
+            .output()
+            .unwrap()
+    };
+    
+    let output = run(&["completions", "--template-names"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).lines().any(|name| name == "podcast"));
+    
+    // Saved templates go to the TOML file when it exists
+    let output = run(&["template", "--save", "scan", "scan_%C3.%E"]);
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let content = std::fs::read_to_string(&toml_path).unwrap();
+    assert!(content.contains("scan = \"scan_%C3.%E\""), "{}", content);
+    assert!(!config_dir.path().join("frencli").join("templates.json").exists());
+}
+
+#[test]
+fn test_project_templates_toml_file() {
+    let binary = get_binary_path();
+    let config_dir = TempDir::new().unwrap();
+    let project_dir = TempDir::new().unwrap();
+    std::fs::write(project_dir.path().join(".frentemplates"), "[templates.team]\npattern = \"%N_team.%E\"\ncategory = \"team\"\n").unwrap();
+    
+    let output = Command::new(&binary)
+        .args(["template", "--list", "--json"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .current_dir(project_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.stderr.is_empty(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let templates: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let team = templates.as_array().unwrap().iter().find(|t| t["name"] == "team").expect("team template");
+    assert_eq!(team["pattern"], "%N_team.%E");
+    assert_eq!(team["source"], "project");
+}
+
+#[test]
+fn test_config_file_supplies_list_defaults() {
+    let binary = get_binary_path();
+    let config_dir = TempDir::new().unwrap();
+    let work_dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(config_dir.path().join("frencli")).unwrap();
+    std::fs::write(
+        config_dir.path().join("frencli").join("config.toml"),
+        "[list]\nexclude = [\"*.tmp\"]\n",
+    ).unwrap();
+    std::fs::write(work_dir.path().join("keep.txt"), "").unwrap();
+    std::fs::write(work_dir.path().join("skip.tmp"), "").unwrap();
+    
+    let output = Command::new(&binary)
+        .args(["list", "*"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .env_remove("FRENCLI_CONFIG")
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("keep.txt"), "got: {}", stdout);
+    assert!(!stdout.contains("skip.tmp"), "got: {}", stdout);
+    
+    // --no-config ignores the config file for one run
+    let output = Command::new(&binary)
+        .args(["--no-config", "list", "*"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .env_remove("FRENCLI_CONFIG")
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("skip.tmp"), "got: {}", stdout);
+}
+
+#[test]
+fn test_config_file_from_environment_must_exist() {
+    let binary = get_binary_path();
+    let work_dir = TempDir::new().unwrap();
+    
+    let output = Command::new(&binary)
+        .args(["list", "*"])
+        .env("FRENCLI_CONFIG", work_dir.path().join("missing.toml"))
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("Failed to read config file"), "got: {}", stderr);
+}
+
+#[test]
+fn test_alias_save_and_run() {
+    let binary = get_binary_path();
+    let config_dir = TempDir::new().unwrap();
+    let work_dir = TempDir::new().unwrap();
+    std::fs::write(work_dir.path().join("a.jpg"), "").unwrap();
+    std::fs::write(work_dir.path().join("b.png"), "").unwrap();
+    
+    let output = Command::new(&binary)
+        .args(["alias", "--save", "pics", "--", "list", "*.jpg", "rename", "%N_new.%E"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    
+    // Extra arguments are appended to the alias's 'list'
+    let output = Command::new(&binary)
+        .args(["run", "pics", "*.png"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("a_new.jpg"), "got: {}", stdout);
+    assert!(stdout.contains("b_new.png"), "got: {}", stdout);
+    
+    let output = Command::new(&binary)
+        .args(["alias", "--list"])
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .current_dir(work_dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("pics    frencli list '*.jpg' rename %N_new.%E"), "got: {}", stdout);
+}
+
+#[test]
+fn test_exit_codes_distinguish_failures() {
+    let binary = get_binary_path();
+    let work_dir = TempDir::new().unwrap();
+    std::fs::write(work_dir.path().join("a.txt"), "").unwrap();
+    let run = |args: &[&str]| {
+        Command::new(&binary)
+            .args(args)
+            .current_dir(work_dir.path())
+            .stdin(Stdio::null())
+            .output()
+            .unwrap()
+    };
+    
+    // Usage error
+    let output = run(&["list", "*.txt", "--yes"]);
+    assert_eq!(output.status.code(), Some(2));
+    
+    // Nothing matched the list patterns
+    let output = run(&["list", "*.jpg", "rename", "%N_x.%E"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Error: No files matched *.jpg."));
+    
+    // Success
+    let output = run(&["list", "*.txt", "rename", "%N_x.%E"]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_apply_dry_run_leaves_files_untouched() {
+    let binary = get_binary_path();
+    let work_dir = TempDir::new().unwrap();
+    std::fs::write(work_dir.path().join("a.txt"), "").unwrap();
+    std::fs::write(work_dir.path().join("b.txt"), "").unwrap();
+    std::fs::write(work_dir.path().join("b_x.txt"), "").unwrap();
+    
+    let output = Command::new(&binary)
+        .args(["list", "a.txt", "b.txt", "rename", "%N_x.%E", "apply", "--dry-run", "--json"])
+        .current_dir(work_dir.path())
+        .env("XDG_CONFIG_HOME", work_dir.path().join("config"))
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json_start = stdout.find("{\n  \"dry_run\"").expect("dry run JSON report");
+    let report: serde_json::Value = serde_json::from_str(&stdout[json_start..]).unwrap();
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["count"], 1);
+    assert_eq!(report["skipped"][0]["reason"], "Target file already exists");
+    assert_eq!(report["issues"].as_array().unwrap().len(), 1);
+    
+    assert!(work_dir.path().join("a.txt").exists());
+    assert!(!work_dir.path().join("a_x.txt").exists());
+    assert!(!work_dir.path().join(".fren_history.json").exists());
+}
+
+#[test]
+fn test_chdir_works_in_another_directory() {
+    let binary = get_binary_path();
+    let run_dir = TempDir::new().unwrap();
+    let work_dir = TempDir::new().unwrap();
+    std::fs::write(work_dir.path().join("a.txt"), "").unwrap();
+    let work_path = work_dir.path().to_string_lossy().to_string();
+    
+    let output = Command::new(&binary)
+        .args(["--chdir", &work_path, "list", "*.txt", "rename", "%N_x.%E", "apply", "--yes"])
+        .current_dir(run_dir.path())
+        .env("XDG_CONFIG_HOME", run_dir.path().join("config"))
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(work_dir.path().join("a_x.txt").exists());
+    
+    // History and audit log are kept in the --chdir directory, not the process's
+    assert!(work_dir.path().join(".fren_history.json").exists());
+    assert!(work_dir.path().join(".fren_audit.log").exists());
+    assert!(!run_dir.path().join(".fren_history.json").exists());
+    
+    let output = Command::new(&binary)
+        .args(["--chdir", &work_path, "undo", "--apply", "--yes"])
+        .current_dir(run_dir.path())
+        .env("XDG_CONFIG_HOME", run_dir.path().join("config"))
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(work_dir.path().join("a.txt").exists());
+}
+
+#[test]
+fn test_chdir_resolves_template_bundle_paths() {
+    let binary = get_binary_path();
+    let run_dir = TempDir::new().unwrap();
+    let work_dir = TempDir::new().unwrap();
+    let work_path = work_dir.path().to_string_lossy().to_string();
+    let run = |args: &[&str], config: &str| {
+        Command::new(&binary)
+            .args(["--chdir", &work_path])
+            .args(args)
+            .current_dir(run_dir.path())
+            .env("XDG_CONFIG_HOME", run_dir.path().join(config))
+            .stdin(Stdio::null())
+            .output()
+            .unwrap()
+    };
+    
+    let output = run(&["template", "--save", "podcast", "%N_ep.%E"], "config");
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let output = run(&["template", "--export", "bundle.json", "podcast"], "config");
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(work_dir.path().join("bundle.json").exists());
+    assert!(!run_dir.path().join("bundle.json").exists());
+    
+    // Importing reads the bundle from the --chdir directory too
+    let output = run(&["template", "--import", "bundle.json"], "other-config");
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let templates = std::fs::read_to_string(run_dir.path().join("other-config").join("frencli").join("templates.json")).unwrap();
+    assert!(templates.contains("podcast"));
+}
+
+#[test]
+fn test_chdir_missing_directory() {
+    let binary = get_binary_path();
+    let run_dir = TempDir::new().unwrap();
+    
+    let output = Command::new(&binary)
+        .args(["--chdir", "no-such-dir", "list", "*.txt"])
+        .current_dir(run_dir.path())
+        .env("XDG_CONFIG_HOME", run_dir.path().join("config"))
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Error: Cannot change to 'no-such-dir'"));
+}
+
+#[test]
+fn test_color_and_ascii_output() {
+    let binary = get_binary_path();
+    let work_dir = TempDir::new().unwrap();
+    std::fs::write(work_dir.path().join("photo.jpg"), "").unwrap();
+    let run = |args: &[&str], no_color: bool| {
+        let mut command = Command::new(&binary);
+        command.args(args)
+            .current_dir(work_dir.path())
+            .env("XDG_CONFIG_HOME", work_dir.path().join("config"))
+            .env_remove("NO_COLOR")
+            .stdin(Stdio::null());
+        if no_color {
+            command.env("NO_COLOR", "1");
+        }
+        let output = command.output().unwrap();
+        assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    
+    // Piped stdout is plain by default
+    let plain = run(&["list", "*.jpg", "rename", "%N_001.%E", "validate"], false);
+    assert!(!plain.contains('\x1b'));
+    assert!(plain.contains("✓ All files passed validation!"));
+    
+    let colored = run(&["--color", "always", "list", "*.jpg", "rename", "%N_001.%E", "validate"], false);
+    assert!(colored.contains("photo\x1b[32m_001\x1b[0m.jpg"));
+    
+    // NO_COLOR only affects --color auto
+    let no_color = run(&["--color", "auto", "list", "*.jpg", "rename", "%N_001.%E"], true);
+    assert!(!no_color.contains('\x1b'));
+    
+    let ascii = run(&["--ascii", "list", "*.jpg", "rename", "%N_001.%E", "validate"], false);
+    assert!(ascii.contains("+ All files passed validation!"));
+    assert!(ascii.contains("* Validation Summary:"));
+    assert!(!ascii.contains('✓') && !ascii.contains('📊'));
+}
+
+#[test]
+fn test_quiet_and_verbose_output() {
+    let binary = get_binary_path();
+    let work_dir = TempDir::new().unwrap();
+    let run = |args: &[&str]| {
+        let output = Command::new(&binary)
+            .args(args)
+            .current_dir(work_dir.path())
+            .env("XDG_CONFIG_HOME", work_dir.path().join("config"))
+            .stdin(Stdio::null())
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        (String::from_utf8_lossy(&output.stdout).to_string(), String::from_utf8_lossy(&output.stderr).to_string())
+    };
+    
+    std::fs::write(work_dir.path().join("A.txt"), "").unwrap();
+    let (stdout, stderr) = run(&["--quiet", "list", "*.txt", "rename", "%N_1.%E", "validate", "apply", "--yes"]);
+    assert_eq!(stdout, "");
+    assert_eq!(stderr, "");
+    assert!(work_dir.path().join("A_1.txt").exists());
+    
+    // --json output is still printed with --quiet
+    let (stdout, _) = run(&["--quiet", "list", "*.txt", "--json"]);
+    assert!(stdout.contains("A_1.txt"));
+    
+    let (stdout, stderr) = run(&["--verbose", "--no-config", "list", "*.txt", "template", "--use", "lowercase", "apply", "--yes"]);
+    assert!(stdout.contains("Successfully renamed 1 file(s)."));
+    assert!(stderr.contains("Ignoring the config file (--no-config)"));
+    assert!(stderr.contains("Template 'lowercase' resolves to: %L%N.%E"));
+    assert!(stderr.contains("Renamed ") && stderr.contains("a_1.txt"));
+    assert!(stderr.contains("Saved undo history to"));
+}
+
+#[test]
+fn test_list_filters() {
+    let binary = get_binary_path();
+    let work_dir = TempDir::new().unwrap();
+    let run = |args: &[&str]| {
+        Command::new(&binary)
+            .args(args)
+            .current_dir(work_dir.path())
+            .env("XDG_CONFIG_HOME", work_dir.path().join("config"))
+            .stdin(Stdio::null())
+            .output()
+            .unwrap()
+    };
+    
+    std::fs::write(work_dir.path().join("small.jpg"), "x").unwrap();
+    std::fs::write(work_dir.path().join("large.jpg"), "x".repeat(2000)).unwrap();
+    std::fs::create_dir(work_dir.path().join("album.jpg")).unwrap();
+    
+    let output = run(&["list", "*.jpg", "--min-size", "1K", "--newer", "1d", "--json"]);
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("large.jpg") && !stdout.contains("small.jpg"));
+    
+    let output = run(&["list", "*.jpg", "--type", "dir", "--json"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("album.jpg") && !stdout.contains("large.jpg"));
+    
+    // Nothing is older than a day, so there is nothing to rename
+    let output = run(&["list", "*.jpg", "--older", "1d", "rename", "%N_old.%E"]);
+    assert_eq!(output.status.code(), Some(3));
+    
+    let output = run(&["list", "*.jpg", "--max-size", "lots"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid size 'lots'"));
+}
+
+#[test]
+fn test_list_warns_about_pattern_like_a_subcommand() {
+    let binary = get_binary_path();
+    let work_dir = TempDir::new().unwrap();
+    std::fs::write(work_dir.path().join("a.txt"), "").unwrap();
+    let run = |args: &[&str]| {
+        Command::new(&binary)
+            .args(args)
+            .current_dir(work_dir.path())
+            .env("XDG_CONFIG_HOME", work_dir.path().join("config"))
+            .stdin(Stdio::null())
+            .output()
+            .unwrap()
+    };
+    
+    let output = run(&["list", "*.txt", "aply"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Warning: 'aply' is used as a search pattern. Did you mean 'apply'?"));
+    
+    // Globs and arguments after '--' are not mistaken for subcommands
+    for args in [&["list", "*.txt", "ap*y"][..], &["list", "*.txt", "--", "aply"][..]] {
+        let output = run(args);
+        assert!(!String::from_utf8_lossy(&output.stderr).contains("Did you mean"), "args: {:?}", args);
+    }
+    
+    // A rename pattern without '%' is kept, with the same warning
+    let output = run(&["list", "*.txt", "rename", "lint"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Warning: 'lint' is used as a rename pattern. Did you mean 'list'?"), "stderr: {}", stderr);
+    
+    // --quiet hides the warning
+    let output = run(&["--quiet", "list", "*.txt", "aply"]);
+    assert!(output.stderr.is_empty(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+}
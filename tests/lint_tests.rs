@@ -0,0 +1,133 @@
+//! Tests for the lint module.
+//! 
+//! These tests verify that rename patterns and templates are checked for
+//! unknown tokens, malformed replacements and dropped extensions.
+
+use frencli::lint::{lint_pattern, lint_template};
+use frencli::templates::{Template, TemplateRegistry, TemplateSource};
+
+#[test]
+fn test_lint_pattern_valid() {
+    assert!(lint_pattern("%N.%E").is_empty());
+    assert!(lint_pattern("%L%N_%C3.%E").is_empty());
+    assert!(lint_pattern("%P_%FD_%N.%E").is_empty());
+    assert!(lint_pattern("%F").is_empty());
+    assert!(lint_pattern("%N%R/ /_.%E").is_empty());
+    assert!(lint_pattern("%N%X|[0-9]+|#.%E").is_empty());
+}
+
+#[test]
+fn test_lint_pattern_lowercase_tokens() {
+    assert!(lint_pattern("%l%n.%e").is_empty());
+}
+
+#[test]
+fn test_lint_pattern_unknown_token() {
+    let problems = lint_pattern("%N_%Q.%E");
+    assert_eq!(problems, vec!["unknown token '%Q'".to_string()]);
+}
+
+#[test]
+fn test_lint_pattern_literal_percent() {
+    assert!(lint_pattern("%N_100%.%E").is_empty());
+}
+
+#[test]
+fn test_lint_pattern_trailing_percent() {
+    let problems = lint_pattern("%N.%E%");
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("end of the pattern"));
+}
+
+#[test]
+fn test_lint_pattern_drops_extension() {
+    let problems = lint_pattern("%N_%C3");
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("drops the file extension"));
+}
+
+#[test]
+fn test_lint_pattern_file_date_does_not_keep_extension() {
+    let problems = lint_pattern("%N_%FD");
+    assert!(problems.iter().any(|p| p.contains("drops the file extension")));
+}
+
+#[test]
+fn test_lint_pattern_replace_without_delimiter() {
+    let problems = lint_pattern("%N%Rold.%E");
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("must be followed by a delimiter"));
+}
+
+#[test]
+fn test_lint_pattern_replace_missing_closing_delimiter() {
+    let problems = lint_pattern("%N%R/old");
+    assert!(problems.iter().any(|p| p.contains("missing the '/'")));
+}
+
+#[test]
+fn test_lint_pattern_replace_empty_search() {
+    let problems = lint_pattern("%N%R//x.%E");
+    assert_eq!(problems, vec!["%R//: search text is empty".to_string()]);
+}
+
+#[test]
+fn test_lint_pattern_invalid_regex() {
+    let problems = lint_pattern("%N%X/([a-z/x.%E");
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].starts_with("invalid regular expression '([a-z' in %X"));
+}
+
+#[test]
+fn test_lint_pattern_multiple_problems() {
+    let problems = lint_pattern("%Q%N%X/(/x/");
+    assert_eq!(problems.len(), 3);
+}
+
+#[test]
+fn test_lint_template_fills_parameters() {
+    let mut template = Template::new("{prefix}_%C{width}.%E", TemplateSource::User);
+    template.defaults.insert("width".to_string(), "3".to_string());
+    assert!(lint_template(&template).is_empty());
+}
+
+#[test]
+fn test_lint_template_regex_quantifier_is_clean() {
+    let template = Template::new(r"%N%X/\d{4}/YYYY/.%E", TemplateSource::User);
+    assert!(lint_template(&template).is_empty());
+}
+
+#[test]
+fn test_lint_template_flags_non_identifier_braces() {
+    let template = Template::new("{my-tag}_%N_{prefix.%E", TemplateSource::User);
+    let problems = lint_template(&template);
+    assert_eq!(problems.len(), 2);
+    assert!(problems[0].contains("'{my-tag}' is not a parameter"));
+    assert!(problems[1].contains("no closing '}'"));
+}
+
+#[test]
+fn test_lint_template_flags_unused_defaults() {
+    let mut template = Template::new("{prefix}_%N.%E", TemplateSource::User);
+    template.defaults.insert("prefix".to_string(), "a".to_string());
+    template.defaults.insert("suffix".to_string(), "b".to_string());
+    let problems = lint_template(&template);
+    assert_eq!(problems, vec!["default for 'suffix' is never used: no pattern has a {suffix} parameter".to_string()]);
+}
+
+#[test]
+fn test_lint_template_chain_numbers_patterns() {
+    let mut template = Template::new("%L%N.%E", TemplateSource::User);
+    template.chain = vec!["%N%Z.%E".to_string()];
+    let problems = lint_template(&template);
+    assert_eq!(problems, vec!["pattern 2: unknown token '%Z'".to_string()]);
+}
+
+#[test]
+fn test_lint_template_builtins_are_clean() {
+    let registry = TemplateRegistry::new();
+    for (name, template) in registry.templates() {
+        let problems = lint_template(template);
+        assert!(problems.is_empty(), "built-in template '{}' has problems: {:?}", name, problems);
+    }
+}
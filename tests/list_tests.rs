@@ -1,10 +1,16 @@
 //! Tests for the list subcommand module.
 //! 
-//! These tests verify file finding, pattern matching, recursion, and exclusion functionality.
+//! These tests verify file finding, pattern matching, recursion, exclusion, and the
+//! size, modification time and type filters.
 //! All tests are async to match the async API of the list module.
 
-use frencli::list::{find_files, display_files};
-use std::path::PathBuf;
+use frencli::list::{
+    find_files, find_files_filtered, display_files, handle_list_command, parse_size, parse_time,
+    FileType, ListFilters,
+};
+use frencli::error::FrencliError;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 use tempfile::TempDir;
 use tokio::fs;
 mod test_utils;
@@ -288,3 +294,201 @@ async fn test_find_files_in_logs_structure() {
         assert!(file.to_string_lossy().contains("Logs"));
     }
 }
+
+#[tokio::test]
+async fn test_handle_list_command_returns_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("file1.txt");
+    fs::write(&file, "test").await.unwrap();
+    
+    let pattern = temp_dir.path().join("*.txt").to_string_lossy().to_string();
+    let files = handle_list_command(Path::new(""), vec![pattern], false, vec![], false).await.unwrap();
+    assert_eq!(files.len(), 1);
+}
+
+#[tokio::test]
+async fn test_handle_list_command_resolves_relative_patterns() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("file1.txt"), "test").await.unwrap();
+    fs::write(temp_dir.path().join("file2.md"), "test").await.unwrap();
+    
+    let files = handle_list_command(temp_dir.path(), vec!["*.txt".to_string()], false, vec![], false).await.unwrap();
+    assert_eq!(files, vec![temp_dir.path().join("file1.txt")]);
+}
+
+#[tokio::test]
+async fn test_handle_list_command_requires_pattern() {
+    let err = handle_list_command(Path::new(""), vec![], false, vec![], false).await.unwrap_err();
+    assert_eq!(err, FrencliError::Usage("No search pattern provided.".to_string()));
+}
+
+// ============================================================================
+// list filter tests
+// ============================================================================
+
+#[test]
+fn test_parse_size() {
+    assert_eq!(parse_size("500").unwrap(), 500);
+    assert_eq!(parse_size("500B").unwrap(), 500);
+    assert_eq!(parse_size("2MB").unwrap(), 2_000_000);
+    assert_eq!(parse_size("2m").unwrap(), 2_000_000);
+    assert_eq!(parse_size("1.5K").unwrap(), 1_500);
+    assert_eq!(parse_size("1KiB").unwrap(), 1024);
+    assert_eq!(parse_size("1GiB").unwrap(), 1 << 30);
+
+    for invalid in ["", "MB", "2XB", "-1K", "1.2.3"] {
+        let err = parse_size(invalid).unwrap_err();
+        assert!(err.starts_with("Invalid size"), "got: {}", err);
+    }
+}
+
+#[test]
+fn test_parse_time() {
+    let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    assert_eq!(parse_time("30m", now).unwrap(), now - Duration::from_secs(30 * 60));
+    assert_eq!(parse_time("7d", now).unwrap(), now - Duration::from_secs(7 * 24 * 60 * 60));
+    assert_eq!(parse_time("2w", now).unwrap(), now - Duration::from_secs(14 * 24 * 60 * 60));
+
+    // Dates are in local time, so only compare them with each other
+    let day = parse_time("2026-01-31", now).unwrap();
+    assert_eq!(parse_time("2026-01-31 18:00", now).unwrap(), day + Duration::from_secs(18 * 60 * 60));
+    assert_eq!(parse_time("2026-01-31T18:00:30", now).unwrap(), day + Duration::from_secs(18 * 60 * 60 + 30));
+
+    for invalid in ["", "7", "7y", "yesterday", "2026-13-01"] {
+        let err = parse_time(invalid, now).unwrap_err();
+        assert!(err.starts_with("Invalid time"), "got: {}", err);
+    }
+}
+
+#[test]
+fn test_file_type_parse() {
+    assert_eq!(FileType::parse("file").unwrap(), FileType::File);
+    assert_eq!(FileType::parse("dir").unwrap(), FileType::Dir);
+    assert_eq!(FileType::parse("symlink").unwrap(), FileType::Symlink);
+    assert_eq!(
+        FileType::parse("socket").unwrap_err(),
+        "Invalid --type value 'socket'. Use file, dir or symlink."
+    );
+}
+
+#[tokio::test]
+async fn test_find_files_filtered_by_size() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("small.txt"), "a").await.unwrap();
+    fs::write(temp_dir.path().join("medium.txt"), "a".repeat(100)).await.unwrap();
+    fs::write(temp_dir.path().join("large.txt"), "a".repeat(1000)).await.unwrap();
+    let patterns = vec![temp_dir.path().join("*.txt").to_string_lossy().to_string()];
+
+    // Both bounds are inclusive
+    let filters = ListFilters { min_size: Some(100), max_size: Some(1000), ..ListFilters::default() };
+    let mut result = find_files_filtered(&patterns, false, &[], &filters).await.unwrap();
+    result.sort();
+    assert_eq!(result, vec![temp_dir.path().join("large.txt"), temp_dir.path().join("medium.txt")]);
+
+    let filters = ListFilters { max_size: Some(99), ..ListFilters::default() };
+    let result = find_files_filtered(&patterns, false, &[], &filters).await.unwrap();
+    assert_eq!(result, vec![temp_dir.path().join("small.txt")]);
+}
+
+#[tokio::test]
+async fn test_find_files_filtered_by_modification_time() {
+    let temp_dir = TempDir::new().unwrap();
+    let old = temp_dir.path().join("old.txt");
+    let new = temp_dir.path().join("new.txt");
+    fs::write(&old, "test").await.unwrap();
+    fs::write(&new, "test").await.unwrap();
+    let now = SystemTime::now();
+    std::fs::File::options().write(true).open(&old).unwrap()
+        .set_modified(now - Duration::from_secs(10 * 24 * 60 * 60)).unwrap();
+    let patterns = vec![temp_dir.path().join("*.txt").to_string_lossy().to_string()];
+
+    let filters = ListFilters { newer: Some(parse_time("7d", now).unwrap()), ..ListFilters::default() };
+    assert_eq!(find_files_filtered(&patterns, false, &[], &filters).await.unwrap(), vec![new]);
+
+    let filters = ListFilters { older: Some(parse_time("7d", now).unwrap()), ..ListFilters::default() };
+    assert_eq!(find_files_filtered(&patterns, false, &[], &filters).await.unwrap(), vec![old]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_find_files_filtered_by_type() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    fs::write(dir.join("photo.jpg"), "test").await.unwrap();
+    fs::create_dir(dir.join("albums")).await.unwrap();
+    fs::create_dir(dir.join("albums").join("2026")).await.unwrap();
+    std::os::unix::fs::symlink(dir.join("photo.jpg"), dir.join("link.jpg")).unwrap();
+    let patterns = vec![dir.join("*").to_string_lossy().to_string()];
+
+    let only = |file_type| ListFilters { file_type: Some(file_type), ..ListFilters::default() };
+
+    // Without --type, links to files are listed with the files
+    let mut result = find_files(&patterns, false, &[]).await.unwrap();
+    result.sort();
+    assert_eq!(result, vec![dir.join("link.jpg"), dir.join("photo.jpg")]);
+
+    let result = find_files_filtered(&patterns, false, &[], &only(FileType::File)).await.unwrap();
+    assert_eq!(result, vec![dir.join("photo.jpg")]);
+
+    let result = find_files_filtered(&patterns, false, &[], &only(FileType::Symlink)).await.unwrap();
+    assert_eq!(result, vec![dir.join("link.jpg")]);
+
+    let result = find_files_filtered(&patterns, false, &[], &only(FileType::Dir)).await.unwrap();
+    assert_eq!(result, vec![dir.join("albums")]);
+
+    let mut result = find_files_filtered(&patterns, true, &[], &only(FileType::Dir)).await.unwrap();
+    result.sort();
+    assert_eq!(result, vec![dir.join("albums"), dir.join("albums").join("2026")]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_find_files_filtered_matches_like_the_engine() {
+    // A '.tmp' prefix would make every pattern name a hidden path
+    let temp_dir = tempfile::Builder::new().prefix("list").tempdir().unwrap();
+    let dir = temp_dir.path();
+    fs::create_dir_all(dir.join("a").join("b")).await.unwrap();
+    fs::create_dir(dir.join(".cache")).await.unwrap();
+    fs::write(dir.join("top.txt"), "test").await.unwrap();
+    fs::write(dir.join("a").join("mid.txt"), "test").await.unwrap();
+    fs::write(dir.join("a").join("b").join("deep.txt"), "test").await.unwrap();
+    fs::write(dir.join(".hidden.txt"), "test").await.unwrap();
+    fs::write(dir.join(".cache").join("cached.txt"), "test").await.unwrap();
+    std::os::unix::fs::symlink(dir.join("top.txt"), dir.join("link.txt")).unwrap();
+    let any_size = ListFilters { min_size: Some(0), ..ListFilters::default() };
+
+    for (pattern, recursive) in [("*.txt", false), ("*.txt", true), ("a/**/*.txt", false), ("a", true)] {
+        let patterns = vec![dir.join(pattern).to_string_lossy().to_string()];
+        let mut expected = find_files(&patterns, recursive, &[]).await.unwrap();
+        expected.sort();
+        let mut result = find_files_filtered(&patterns, recursive, &[], &any_size).await.unwrap();
+        result.sort();
+        assert_eq!(result, expected, "pattern {} (recursive: {})", pattern, recursive);
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_find_files_filtered_by_type_recursive_and_hidden() {
+    // A '.tmp' prefix would make every pattern name a hidden path
+    let temp_dir = tempfile::Builder::new().prefix("list").tempdir().unwrap();
+    let dir = temp_dir.path();
+    fs::create_dir_all(dir.join("albums").join("2026")).await.unwrap();
+    fs::create_dir(dir.join(".trash")).await.unwrap();
+    fs::write(dir.join("albums").join("2026").join("photo.jpg"), "test").await.unwrap();
+    fs::write(dir.join(".trash").join("old.jpg"), "test").await.unwrap();
+    let patterns = vec![dir.join("*").to_string_lossy().to_string()];
+    let only = |file_type| ListFilters { file_type: Some(file_type), ..ListFilters::default() };
+
+    // Every --type walks the same entries: hidden directories are skipped for all of them
+    let result = find_files_filtered(&patterns, true, &[], &only(FileType::File)).await.unwrap();
+    assert_eq!(result, vec![dir.join("albums").join("2026").join("photo.jpg")]);
+
+    let mut result = find_files_filtered(&patterns, true, &[], &only(FileType::Dir)).await.unwrap();
+    result.sort();
+    assert_eq!(result, vec![dir.join("albums"), dir.join("albums").join("2026")]);
+
+    let patterns = vec![dir.join(".*").to_string_lossy().to_string()];
+    let result = find_files_filtered(&patterns, false, &[], &only(FileType::Dir)).await.unwrap();
+    assert_eq!(result, vec![dir.join(".trash")]);
+}
@@ -0,0 +1,70 @@
+//! Tests for the manpage module.
+//! 
+//! These tests verify that man pages are generated from the command spec.
+
+use frencli::manpage::{main_manpage, subcommand_manpage, write_manpages};
+use frencli::spec::{PATTERN_MODIFIERS, PATTERN_PLACEHOLDERS, SUBCOMMANDS};
+use tempfile::TempDir;
+
+/// Undoes the roff hyphen escaping so tests can look for plain text
+fn unescape(page: &str) -> String {
+    page.replace("\\-", "-")
+}
+
+#[test]
+fn test_main_manpage_lists_subcommands_and_tokens() {
+    let page = unescape(&main_manpage());
+    assert!(page.starts_with(".TH FRENCLI 1"));
+    for spec in SUBCOMMANDS {
+        assert!(page.contains(&format!(".B {}\n", spec.name)), "missing '{}'", spec.name);
+        assert!(page.contains(&format!(".BR frencli-{} (1)", spec.name)));
+    }
+    for (token, _, _) in PATTERN_PLACEHOLDERS {
+        assert!(page.contains(&format!(".B {}\n", token)), "missing '{}'", token);
+    }
+    for (token, _) in PATTERN_MODIFIERS {
+        assert!(page.contains(&format!(".B {}\n", token)), "missing '{}'", token);
+    }
+}
+
+#[test]
+fn test_subcommand_manpage_lists_flags() {
+    for spec in SUBCOMMANDS {
+        let page = unescape(&subcommand_manpage(spec.name).unwrap());
+        assert!(page.starts_with(&format!(".TH FRENCLI-{} 1", spec.name.to_uppercase())));
+        for flag in spec.flags {
+            assert!(page.contains(&format!("--{}", flag.name)), "'{}' page is missing '--{}'", spec.name, flag.name);
+        }
+        assert!(page.contains("--help"));
+    }
+}
+
+#[test]
+fn test_subcommand_manpage_escapes_hyphens() {
+    let page = subcommand_manpage("list").unwrap();
+    assert!(page.contains(".BI \\-\\-files\\-from \" \" <FILE>"));
+    assert!(!page.contains(" --"));
+}
+
+#[test]
+fn test_rename_manpage_has_pattern_reference() {
+    assert!(subcommand_manpage("rename").unwrap().contains(".SH PATTERNS"));
+    assert!(!subcommand_manpage("apply").unwrap().contains(".SH PATTERNS"));
+}
+
+#[test]
+fn test_subcommand_manpage_unknown() {
+    let result = subcommand_manpage("aply");
+    assert_eq!(result.unwrap_err(), "Unknown subcommand 'aply'. Did you mean 'apply'?");
+}
+
+#[test]
+fn test_write_manpages() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path().join("man1");
+    
+    let written = write_manpages(&dir).unwrap();
+    assert_eq!(written, SUBCOMMANDS.len() + 1);
+    assert!(dir.join("frencli.1").exists());
+    assert!(dir.join("frencli-rename.1").exists());
+}
@@ -0,0 +1,36 @@
+//! Tests for the output module.
+//!
+//! These tests verify how `--quiet` and `--verbose` choose the verbosity and
+//! which kinds of output each verbosity shows. The verbosity is process-wide,
+//! so the test that changes it does so in a single test.
+
+use frencli::output::{self, Verbosity};
+
+#[test]
+fn test_verbosity_from_flags() {
+    assert_eq!(Verbosity::from_flags(false, false).unwrap(), Verbosity::Normal);
+    assert_eq!(Verbosity::from_flags(true, false).unwrap(), Verbosity::Quiet);
+    assert_eq!(Verbosity::from_flags(false, true).unwrap(), Verbosity::Verbose);
+    assert_eq!(
+        Verbosity::from_flags(true, true).unwrap_err(),
+        "Cannot use both '--quiet' and '--verbose'."
+    );
+}
+
+#[test]
+fn test_verbosity_levels() {
+    assert_eq!(output::verbosity(), Verbosity::Normal);
+    assert!(output::shows_info());
+    assert!(!output::is_verbose());
+
+    output::set_verbosity(Verbosity::Quiet);
+    assert_eq!(output::verbosity(), Verbosity::Quiet);
+    assert!(!output::shows_info());
+    assert!(!output::is_verbose());
+
+    output::set_verbosity(Verbosity::Verbose);
+    assert!(output::shows_info());
+    assert!(output::is_verbose());
+
+    output::set_verbosity(Verbosity::Normal);
+}
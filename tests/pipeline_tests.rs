@@ -0,0 +1,258 @@
+//! Tests for the pipeline module.
+//!
+//! These tests verify the embeddable Pipeline builder: file selection, preview,
+//! validation and apply, with structured results instead of printed output.
+//! All tests use isolated temp directories, without changing the current directory.
+
+use frencli::error::FrencliError;
+use frencli::list::ListFilters;
+use frencli::pipeline::Pipeline;
+use frencli::templates::{Template, TemplateRegistry, TemplateSource};
+use tempfile::TempDir;
+use tokio::fs;
+
+/// Creates `names` in a new temp directory and returns it with a `*.txt` pattern for it.
+async fn setup(names: &[&str]) -> (TempDir, String) {
+    let temp_dir = TempDir::new().unwrap();
+    for name in names {
+        fs::write(temp_dir.path().join(name), "content").await.unwrap();
+    }
+    let pattern = temp_dir.path().join("*.txt").to_string_lossy().to_string();
+    (temp_dir, pattern)
+}
+
+#[tokio::test]
+async fn test_pipeline_preview() {
+    let (temp_dir, pattern) = setup(&["a.txt", "b.txt"]).await;
+
+    let preview = Pipeline::new()
+        .patterns([pattern])
+        .rename("%N_old.%E")
+        .preview()
+        .await
+        .unwrap();
+
+    assert_eq!(preview.files.len(), 2);
+    let mut names: Vec<_> = preview.result.renames.iter().map(|r| r.new_name.clone()).collect();
+    names.sort();
+    assert_eq!(names, vec!["a_old.txt", "b_old.txt"]);
+    // Preview does not touch the files
+    assert!(temp_dir.path().join("a.txt").exists());
+}
+
+#[tokio::test]
+async fn test_pipeline_preview_chained_patterns() {
+    let (_temp_dir, pattern) = setup(&["Report.txt"]).await;
+
+    let preview = Pipeline::new()
+        .patterns([pattern])
+        .rename("%L%N.%E")
+        .rename("%N_final.%E")
+        .preview()
+        .await
+        .unwrap();
+
+    assert_eq!(preview.stages.len(), 2);
+    assert_eq!(preview.stages[0].new_names, vec!["report.txt"]);
+    assert_eq!(preview.result.renames[0].new_name, "report_final.txt");
+}
+
+#[tokio::test]
+async fn test_pipeline_preview_builtin_template() {
+    let (temp_dir, _pattern) = setup(&["Notes.txt"]).await;
+
+    let preview = Pipeline::new()
+        .files([temp_dir.path().join("Notes.txt")])
+        .template("lowercase")
+        .preview()
+        .await
+        .unwrap();
+
+    assert_eq!(preview.result.renames[0].new_name, "notes.txt");
+}
+
+#[tokio::test]
+async fn test_pipeline_preview_template_with_parameters() {
+    let (_temp_dir, pattern) = setup(&["episode.txt"]).await;
+    let mut registry = TemplateRegistry::new();
+    registry.insert("show".to_string(), Template::new("{show}_%N.%E", TemplateSource::User));
+
+    let preview = Pipeline::new()
+        .patterns([pattern])
+        .template("show")
+        .set("show", "podcast")
+        .templates(registry)
+        .preview()
+        .await
+        .unwrap();
+
+    assert_eq!(preview.result.renames[0].new_name, "podcast_episode.txt");
+}
+
+#[tokio::test]
+async fn test_pipeline_preview_errors() {
+    let (temp_dir, _pattern) = setup(&[]).await;
+
+    let no_files = Pipeline::new().rename("%N.%E").preview().await;
+    assert!(matches!(no_files, Err(FrencliError::Usage(_))));
+
+    let no_pattern = Pipeline::new().files([temp_dir.path().join("a.txt")]).preview().await;
+    assert!(matches!(no_pattern, Err(FrencliError::Usage(_))));
+
+    let unknown_template = Pipeline::new().files([temp_dir.path().join("a.txt")]).template("no-such-template").preview().await;
+    assert!(matches!(unknown_template, Err(FrencliError::Usage(_))));
+
+    let no_match = Pipeline::new()
+        .patterns([temp_dir.path().join("*.jpg").to_string_lossy().to_string()])
+        .rename("%N.%E")
+        .preview()
+        .await;
+    assert!(matches!(no_match, Err(FrencliError::NoFilesMatched(_))));
+}
+
+#[tokio::test]
+async fn test_pipeline_validate_reports_issues() {
+    let (temp_dir, _pattern) = setup(&["a.txt", "b.txt"]).await;
+
+    // a.txt -> b.txt, but b.txt exists
+    let validation = Pipeline::new()
+        .files([temp_dir.path().join("a.txt")])
+        .rename("b.%E")
+        .validate()
+        .await
+        .unwrap();
+
+    assert!(!validation.is_valid());
+    assert_eq!(validation.result.issues.len(), 1);
+}
+
+#[tokio::test]
+async fn test_pipeline_apply() {
+    let (temp_dir, pattern) = setup(&["a.txt", "b.txt"]).await;
+
+    let report = Pipeline::new()
+        .patterns([pattern])
+        .rename("%N_new.%E")
+        .history(false)
+        .apply()
+        .await
+        .unwrap();
+
+    assert!(report.is_complete());
+    assert_eq!(report.execution.successful.len(), 2);
+    assert!(temp_dir.path().join("a_new.txt").exists());
+    assert!(temp_dir.path().join("b_new.txt").exists());
+    assert!(!temp_dir.path().join("a.txt").exists());
+}
+
+#[tokio::test]
+async fn test_pipeline_apply_validation_failed() {
+    let (temp_dir, _pattern) = setup(&["a.txt", "a_x.txt", "c.txt"]).await;
+    let files = [temp_dir.path().join("a.txt"), temp_dir.path().join("c.txt")];
+
+    // a.txt -> a_x.txt collides with an existing file; c.txt -> c_x.txt is valid
+    let pipeline = Pipeline::new()
+        .files(files)
+        .rename("%N_x.%E")
+        .history(false);
+
+    let result = pipeline.apply().await;
+    assert!(matches!(result, Err(FrencliError::ValidationFailed(_))));
+    assert!(temp_dir.path().join("a.txt").exists());
+
+    // With skip_invalid, only the valid renames are applied
+    let report = pipeline.skip_invalid(true).apply().await.unwrap();
+    assert_eq!(report.validation.result.issues.len(), 1);
+    assert_eq!(report.execution.successful.len(), 1);
+    assert!(temp_dir.path().join("a.txt").exists());
+    assert!(temp_dir.path().join("c_x.txt").exists());
+}
+
+#[tokio::test]
+async fn test_pipeline_apply_duplicate_targets() {
+    let (temp_dir, pattern) = setup(&["a.txt", "b.txt"]).await;
+
+    // Both files would be renamed to same.txt, as with the CLI the batch is refused
+    let result = Pipeline::new()
+        .patterns([pattern])
+        .rename("same.%E")
+        .history(false)
+        .apply()
+        .await;
+    match result {
+        Err(FrencliError::ValidationFailed(message)) => assert!(message.contains("same name")),
+        other => panic!("expected ValidationFailed, got {:?}", other.map(|report| report.execution.successful.len())),
+    }
+    assert!(temp_dir.path().join("a.txt").exists());
+    assert!(temp_dir.path().join("b.txt").exists());
+    assert!(!temp_dir.path().join("same.txt").exists());
+}
+
+#[tokio::test]
+async fn test_pipeline_base_dir() {
+    let (temp_dir, _pattern) = setup(&["a.txt", "b.md"]).await;
+
+    // Relative patterns and files are resolved against the base directory
+    let preview = Pipeline::new()
+        .base_dir(temp_dir.path())
+        .patterns(["*.txt"])
+        .rename("%N_new.%E")
+        .preview()
+        .await
+        .unwrap();
+    assert_eq!(preview.files, vec![temp_dir.path().join("a.txt")]);
+
+    let preview = Pipeline::new()
+        .base_dir(temp_dir.path())
+        .files(["b.md"])
+        .rename("%N_new.%E")
+        .preview()
+        .await
+        .unwrap();
+    assert_eq!(preview.files, vec![temp_dir.path().join("b.md")]);
+}
+
+#[tokio::test]
+async fn test_pipeline_filters() {
+    let (temp_dir, pattern) = setup(&["a.txt"]).await;
+    fs::write(temp_dir.path().join("empty.txt"), "").await.unwrap();
+    let filters = ListFilters { min_size: Some(1), ..ListFilters::default() };
+
+    let preview = Pipeline::new()
+        .patterns([pattern])
+        .filters(filters.clone())
+        .rename("%N_new.%E")
+        .preview()
+        .await
+        .unwrap();
+    assert_eq!(preview.files, vec![temp_dir.path().join("a.txt")]);
+
+    // Explicit files are filtered too
+    let preview = Pipeline::new()
+        .base_dir(temp_dir.path())
+        .files(["a.txt", "empty.txt"])
+        .filters(filters)
+        .rename("%N_new.%E")
+        .preview()
+        .await
+        .unwrap();
+    assert_eq!(preview.files, vec![temp_dir.path().join("a.txt")]);
+}
+
+#[tokio::test]
+async fn test_pipeline_apply_saves_history() {
+    let (temp_dir, pattern) = setup(&["a.txt"]).await;
+
+    Pipeline::new()
+        .base_dir(temp_dir.path())
+        .patterns([pattern])
+        .rename("%N_new.%E")
+        .audit("frencli test")
+        .apply()
+        .await
+        .unwrap();
+
+    // History and audit log are written to the base directory
+    assert!(temp_dir.path().join(".fren_history.json").exists());
+    assert!(temp_dir.path().join(".fren_audit.log").exists());
+}
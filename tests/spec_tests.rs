@@ -0,0 +1,60 @@
+//! Tests for the command spec module.
+//! 
+//! These tests verify that the declarative command spec is consistent.
+
+use frencli::spec::{Arity, SUBCOMMANDS, HELP_FLAG, find_subcommand, is_subcommand, subcommand_names};
+use std::collections::HashSet;
+
+#[test]
+fn test_subcommand_names_unique() {
+    let names: HashSet<&str> = subcommand_names().collect();
+    assert_eq!(names.len(), SUBCOMMANDS.len());
+}
+
+#[test]
+fn test_flag_names_unique_per_subcommand() {
+    for spec in SUBCOMMANDS {
+        let mut seen = HashSet::new();
+        for flag in spec.flags {
+            assert!(seen.insert(flag.name), "'{}' lists '--{}' twice", spec.name, flag.name);
+            assert_ne!(flag.name, HELP_FLAG.name, "'{}' lists '--help' explicitly", spec.name);
+        }
+    }
+}
+
+#[test]
+fn test_value_names_match_arity() {
+    for spec in SUBCOMMANDS {
+        for flag in spec.flags {
+            let is_switch = flag.arity == Arity::Switch;
+            assert_eq!(is_switch, flag.value_name.is_empty(),
+                "'{} --{}': value name does not match arity", spec.name, flag.name);
+            assert!(!flag.help.is_empty(), "'{} --{}' has no help", spec.name, flag.name);
+        }
+    }
+}
+
+#[test]
+fn test_find_subcommand() {
+    assert_eq!(find_subcommand("list").map(|spec| spec.name), Some("list"));
+    assert!(find_subcommand("make").is_none());
+    assert!(is_subcommand("apply"));
+    assert!(!is_subcommand("--apply"));
+}
+
+#[test]
+fn test_positional_arguments() {
+    assert!(find_subcommand("list").unwrap().takes_args());
+    assert!(find_subcommand("rename").unwrap().takes_args());
+    assert!(!find_subcommand("apply").unwrap().takes_args());
+    assert!(!find_subcommand("validate").unwrap().takes_args());
+}
+
+#[test]
+fn test_same_flag_name_can_differ_between_subcommands() {
+    // 'undo --check' is a switch, 'template --check' takes template names
+    let undo = find_subcommand("undo").unwrap();
+    let template = find_subcommand("template").unwrap();
+    assert_eq!(undo.flag("check").unwrap().arity, Arity::Switch);
+    assert_eq!(template.flag("check").unwrap().arity, Arity::Optional);
+}
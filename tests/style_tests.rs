@@ -0,0 +1,70 @@
+//! Tests for the style module.
+//!
+//! These tests verify the `--color` decision, change highlighting, padding of
+//! styled text and ASCII symbols. The style settings are process-wide, so the
+//! tests that change them do so in a single test.
+
+use frencli::style::{self, ColorChoice, Symbol};
+
+#[test]
+fn test_color_choice_parse() {
+    assert_eq!(ColorChoice::parse("auto").unwrap(), ColorChoice::Auto);
+    assert_eq!(ColorChoice::parse("always").unwrap(), ColorChoice::Always);
+    assert_eq!(ColorChoice::parse("never").unwrap(), ColorChoice::Never);
+    assert_eq!(
+        ColorChoice::parse("yes").unwrap_err(),
+        "Invalid --color value 'yes'. Use auto, always or never."
+    );
+}
+
+#[test]
+fn test_color_choice_enabled() {
+    // auto colors only on a terminal, and never with NO_COLOR
+    assert!(ColorChoice::Auto.enabled(false, true));
+    assert!(!ColorChoice::Auto.enabled(false, false));
+    assert!(!ColorChoice::Auto.enabled(true, true));
+
+    assert!(ColorChoice::Always.enabled(true, false));
+    assert!(!ColorChoice::Never.enabled(false, true));
+}
+
+#[test]
+fn test_pad_counts_visible_characters() {
+    let styled = "\x1b[32mnew\x1b[0m";
+    assert_eq!(style::pad(styled, "new", 6), format!("{}   ", styled));
+    assert_eq!(style::pad("long name", "long name", 4), "long name");
+}
+
+#[test]
+fn test_styles_and_symbols() {
+    style::set_color(false);
+    style::set_ascii(false);
+    assert_eq!(style::error("failed"), "failed");
+    assert_eq!(style::highlight_change("photo.jpg", "photo_001.jpg"), "photo_001.jpg");
+    assert_eq!(format!("{} Valid", Symbol::Check), "✓ Valid");
+    assert_eq!(Symbol::Summary.as_str(), "📊");
+    assert_eq!(Symbol::BothWays.as_str(), "↔");
+
+    style::set_color(true);
+    assert_eq!(style::error("failed"), "\x1b[31mfailed\x1b[0m");
+    assert_eq!(style::warning("careful"), "\x1b[33mcareful\x1b[0m");
+    assert_eq!(style::success("done"), "\x1b[32mdone\x1b[0m");
+    // Only the inserted part is highlighted
+    assert_eq!(
+        style::highlight_change("photo.jpg", "photo_001.jpg"),
+        "photo\x1b[32m_001\x1b[0m.jpg"
+    );
+    assert_eq!(style::highlight_change("a.txt", "b.txt"), "\x1b[32mb\x1b[0m.txt");
+    assert_eq!(style::highlight_change("same.txt", "same.txt"), "same.txt");
+
+    style::set_ascii(true);
+    assert_eq!(format!("{} Valid", Symbol::Check), "+ Valid");
+    assert_eq!(Symbol::Cross.as_str(), "x");
+    assert_eq!(Symbol::Warning.as_str(), "!");
+    assert_eq!(Symbol::Summary.as_str(), "*");
+    assert_eq!(Symbol::Arrow.as_str(), "->");
+    assert_eq!(Symbol::BothWays.as_str(), "<->");
+
+    style::set_color(false);
+    style::set_ascii(false);
+}
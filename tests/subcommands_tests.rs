@@ -3,13 +3,13 @@
 //! These tests verify the custom subcommand parser that handles multiple
 //! subcommands in a single invocation.
 
-use frencli::subcommands::{parse_multi_subcommand, get_flag_value, has_flag, get_flag_values};
+use frencli::subcommands::{parse_global_options, parse_multi_subcommand, get_flag_value, has_flag, get_flag_values};
 use std::collections::HashMap;
 
 #[test]
 fn test_parse_single_subcommand() {
     let args = vec!["list".to_string(), "*.txt".to_string()];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
     assert_eq!(result[0].name, "list");
@@ -26,7 +26,7 @@ fn test_parse_multiple_subcommands() {
         "%N.%E".to_string(),
         "apply".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 3);
     assert_eq!(result[0].name, "list");
@@ -46,7 +46,7 @@ fn test_parse_subcommand_with_flags() {
         "--exclude".to_string(),
         "*.tmp".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
     assert_eq!(result[0].name, "list");
@@ -63,7 +63,7 @@ fn test_parse_subcommand_with_multiple_flag_values() {
         "*.tmp".to_string(),
         "*.bak".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
     assert_eq!(get_flag_values(&result[0].flags, "exclude"), vec!["*.tmp", "*.bak"]);
@@ -72,14 +72,14 @@ fn test_parse_subcommand_with_multiple_flag_values() {
 #[test]
 fn test_parse_subcommand_with_boolean_flag() {
     let args = vec![
-        "rename".to_string(),
+        "apply".to_string(),
         "--yes".to_string(),
         "--overwrite".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
-    assert_eq!(result[0].name, "rename");
+    assert_eq!(result[0].name, "apply");
     assert!(has_flag(&result[0].flags, "yes"));
     assert!(has_flag(&result[0].flags, "overwrite"));
 }
@@ -100,7 +100,7 @@ fn test_parse_complex_command() {
         "apply".to_string(),
         "--yes".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 4);
     
@@ -124,25 +124,193 @@ fn test_parse_complex_command() {
 }
 
 #[test]
-fn test_parse_unknown_args_ignored() {
+fn test_parse_unknown_subcommand_rejected() {
     let args = vec![
         "unknown".to_string(),
         "list".to_string(),
         "*.txt".to_string(),
-        "also-unknown".to_string(),
     ];
     let result = parse_multi_subcommand(args);
     
-    assert_eq!(result.len(), 1);
-    assert_eq!(result[0].name, "list");
-    // Unknown args before subcommands are ignored, but args after subcommands are collected
+    assert!(result.unwrap_err().starts_with("Unknown subcommand 'unknown'."));
+}
+
+#[test]
+fn test_parse_unknown_subcommand_suggestion() {
+    let args = vec!["lst".to_string(), "*.txt".to_string()];
+    let result = parse_multi_subcommand(args);
+    
+    assert!(result.unwrap_err().contains("Did you mean 'list'?"));
+}
+
+#[test]
+fn test_parse_list_args_collected() {
+    let args = vec![
+        "list".to_string(),
+        "*.txt".to_string(),
+        "also-unknown".to_string(),
+    ];
+    let result = parse_multi_subcommand(args).unwrap();
+    
+    // Args after 'list' are file patterns, whatever they look like
     assert_eq!(result[0].args, vec!["*.txt", "also-unknown"]);
 }
 
+#[test]
+fn test_parse_mistyped_subcommand_after_no_arg_subcommand() {
+    let args = vec![
+        "validate".to_string(),
+        "aply".to_string(),
+    ];
+    let result = parse_multi_subcommand(args);
+    
+    assert_eq!(result.unwrap_err(), "Unknown subcommand 'aply'. Did you mean 'apply'?");
+}
+
+#[test]
+fn test_parse_argument_for_no_arg_subcommand() {
+    let args = vec!["apply".to_string(), "everything".to_string()];
+    let result = parse_multi_subcommand(args);
+    
+    assert_eq!(result.unwrap_err(), "'apply' does not take arguments (got 'everything').");
+}
+
+#[test]
+fn test_parse_mistyped_subcommand_as_rename_pattern() {
+    let args = vec![
+        "rename".to_string(),
+        "%N.%E".to_string(),
+        "aply".to_string(),
+    ];
+    let result = parse_multi_subcommand(args).unwrap();
+    
+    // A rename pattern can be any word, so it is kept with a warning for the caller
+    assert_eq!(result[0].args, vec!["%N.%E", "aply"]);
+    assert_eq!(result[0].warnings.len(), 1);
+    assert!(result[0].warnings[0].contains("'aply' is used as a rename pattern. Did you mean 'apply'?"));
+}
+
+#[test]
+fn test_parse_pattern_like_subcommand_warnings() {
+    let parse = |args: &[&str]| parse_multi_subcommand(args.iter().map(|a| a.to_string()).collect()).unwrap();
+    
+    // 'lint' is close to 'list', but it is a valid rename pattern
+    let result = parse(&["list", "*.txt", "rename", "lint"]);
+    assert_eq!(result[1].args, vec!["lint"]);
+    assert!(result[1].warnings[0].contains("Did you mean 'list'?"));
+    
+    let result = parse(&["list", "*.txt", "aply"]);
+    assert_eq!(result[0].args, vec!["*.txt", "aply"]);
+    assert!(result[0].warnings[0].contains("'aply' is used as a search pattern. Did you mean 'apply'?"));
+    
+    // Globs, % tokens, unrelated words and arguments after '--' give no warning
+    for args in [
+        &["list", "ap*y"][..],
+        &["list", "--", "aply"][..],
+        &["rename", "%Naply.%E"][..],
+        &["rename", "lowercase"][..],
+        &["rename", "--", "lint"][..],
+    ] {
+        assert!(parse(args)[0].warnings.is_empty(), "args: {:?}", args);
+    }
+}
+
+#[test]
+fn test_parse_unknown_flag_suggestion() {
+    let args = vec!["apply".to_string(), "--yse".to_string()];
+    let result = parse_multi_subcommand(args);
+    
+    assert_eq!(
+        result.unwrap_err(),
+        "Unknown flag '--yse' for 'apply'. Did you mean '--yes'? Run 'frencli apply --help' to see its flags."
+    );
+}
+
+#[test]
+fn test_parse_unknown_flag_without_suggestion() {
+    let args = vec!["list".to_string(), "--frobnicate".to_string()];
+    let result = parse_multi_subcommand(args);
+    
+    assert_eq!(
+        result.unwrap_err(),
+        "Unknown flag '--frobnicate' for 'list'. Run 'frencli list --help' to see its flags."
+    );
+}
+
+#[test]
+fn test_parse_flag_of_other_subcommand_rejected() {
+    let args = vec!["list".to_string(), "*.txt".to_string(), "--yes".to_string()];
+    let result = parse_multi_subcommand(args);
+    
+    assert_eq!(
+        result.unwrap_err(),
+        "'--yes' is not a flag of 'list' (it belongs to 'apply', 'undo'). Run 'frencli list --help' to see its flags."
+    );
+}
+
+#[test]
+fn test_parse_misspelled_flag_of_other_subcommand() {
+    // '--recusive' is close to a 'list' flag, but 'apply' has nothing like it
+    let args = vec!["apply".to_string(), "--recusive".to_string()];
+    let result = parse_multi_subcommand(args);
+    
+    let err = result.unwrap_err();
+    assert!(err.starts_with("Unknown flag '--recusive' for 'apply'."), "got: {}", err);
+    assert!(!err.contains("Did you mean"));
+}
+
+#[test]
+fn test_parse_help_flag_accepted_everywhere() {
+    for name in ["list", "rename", "validate", "apply", "template", "undo", "audit", "interactive"] {
+        let args = vec![name.to_string(), "--help".to_string()];
+        let result = parse_multi_subcommand(args).unwrap();
+        assert!(has_flag(&result[0].flags, "help"), "'{} --help' not parsed", name);
+    }
+}
+
+#[test]
+fn test_parse_value_flag_without_value() {
+    let args = vec!["audit".to_string(), "--limit".to_string()];
+    let result = parse_multi_subcommand(args);
+    assert_eq!(result.unwrap_err(), "'--limit' requires a value: audit --limit <N>");
+    
+    let args = vec![
+        "template".to_string(),
+        "--use".to_string(),
+        "--stages".to_string(),
+    ];
+    let result = parse_multi_subcommand(args);
+    assert!(result.unwrap_err().starts_with("'--use' requires a value"));
+}
+
+#[test]
+fn test_parse_optional_value_flag_without_value() {
+    let args = vec!["template".to_string(), "--try".to_string()];
+    let result = parse_multi_subcommand(args).unwrap();
+    assert!(has_flag(&result[0].flags, "try"));
+    assert!(get_flag_values(&result[0].flags, "try").is_empty());
+}
+
+#[test]
+fn test_parse_repeated_flag_accumulates_values() {
+    let args = vec![
+        "template".to_string(),
+        "--use".to_string(),
+        "trip".to_string(),
+        "--set".to_string(),
+        "prefix=rome".to_string(),
+        "--set".to_string(),
+        "width=2".to_string(),
+    ];
+    let result = parse_multi_subcommand(args).unwrap();
+    
+    assert_eq!(get_flag_values(&result[0].flags, "set"), vec!["prefix=rome", "width=2"]);
+}
+
 #[test]
 fn test_parse_empty_args() {
     let args = vec![];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 0);
 }
@@ -154,7 +322,7 @@ fn test_parse_template_with_use_flag() {
         "--use".to_string(),
         "lowercase".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
     assert_eq!(result[0].name, "template");
@@ -167,7 +335,7 @@ fn test_parse_undo_with_check() {
         "undo".to_string(),
         "--check".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
     assert_eq!(result[0].name, "undo");
@@ -181,7 +349,7 @@ fn test_parse_undo_with_apply() {
         "--apply".to_string(),
         "--yes".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
     assert_eq!(result[0].name, "undo");
@@ -232,8 +400,8 @@ fn test_parse_subcommand_order_independence() {
         "*.txt".to_string(),
     ];
     
-    let result1 = parse_multi_subcommand(args1);
-    let result2 = parse_multi_subcommand(args2);
+    let result1 = parse_multi_subcommand(args1).unwrap();
+    let result2 = parse_multi_subcommand(args2).unwrap();
     
     // Both should parse correctly, order is preserved in result
     assert_eq!(result1.len(), 2);
@@ -257,7 +425,7 @@ fn test_short_flag_allowed_as_list_positional_arg() {
         "-y".to_string(),
         "-o".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
     assert_eq!(result[0].name, "list");
@@ -272,7 +440,7 @@ fn test_short_flag_allowed_as_rename_positional_arg() {
         "rename".to_string(),
         "-y".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
     assert_eq!(result[0].name, "rename");
@@ -290,7 +458,7 @@ fn test_short_flag_allowed_as_exclude_value() {
         "-y".to_string(),
         "-o".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
     assert_eq!(result[0].name, "list");
@@ -306,7 +474,7 @@ fn test_short_flag_allowed_as_template_use_value() {
         "--use".to_string(),
         "-y".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
     assert_eq!(result[0].name, "template");
@@ -323,10 +491,213 @@ fn test_short_flag_allowed_as_mixed_list_args() {
         "test.txt".to_string(),
         "-o".to_string(),
     ];
-    let result = parse_multi_subcommand(args);
+    let result = parse_multi_subcommand(args).unwrap();
     
     assert_eq!(result.len(), 1);
     assert_eq!(result[0].name, "list");
     assert_eq!(result[0].args, vec!["*.txt", "-y", "test.txt", "-o"]);
 }
 
+
+#[test]
+fn test_parse_single_value_flag_takes_one_value() {
+    let args = vec![
+        "list".to_string(),
+        "--files-from".to_string(),
+        "files.txt".to_string(),
+        "*.jpg".to_string(),
+    ];
+    let result = parse_multi_subcommand(args).unwrap();
+    
+    assert_eq!(get_flag_values(&result[0].flags, "files-from"), vec!["files.txt"]);
+    assert_eq!(result[0].args, vec!["*.jpg"]);
+}
+
+#[test]
+fn test_parse_flag_arity_depends_on_subcommand() {
+    let args = vec![
+        "template".to_string(),
+        "--check".to_string(),
+        "podcast".to_string(),
+        "trip".to_string(),
+    ];
+    let result = parse_multi_subcommand(args).unwrap();
+    assert_eq!(get_flag_values(&result[0].flags, "check"), vec!["podcast", "trip"]);
+    
+    let args = vec!["undo".to_string(), "--check".to_string()];
+    let result = parse_multi_subcommand(args).unwrap();
+    assert!(has_flag(&result[0].flags, "check"));
+}
+
+// ============================================================================
+// Tests for --flag=value and the -- separator
+// ============================================================================
+
+#[test]
+fn test_parse_flag_equals_value() {
+    let args = vec![
+        "template".to_string(),
+        "--use=trip".to_string(),
+        "--set=prefix=rome".to_string(),
+    ];
+    let result = parse_multi_subcommand(args).unwrap();
+    
+    assert_eq!(get_flag_value(&result[0].flags, "use"), Some("trip".to_string()));
+    assert_eq!(get_flag_values(&result[0].flags, "set"), vec!["prefix=rome"]);
+}
+
+#[test]
+fn test_parse_flag_equals_value_takes_exactly_one_value() {
+    let args = vec![
+        "list".to_string(),
+        "--exclude=*.tmp".to_string(),
+        "*.txt".to_string(),
+        "--exclude=--draft.txt".to_string(),
+    ];
+    let result = parse_multi_subcommand(args).unwrap();
+    
+    assert_eq!(get_flag_values(&result[0].flags, "exclude"), vec!["*.tmp", "--draft.txt"]);
+    assert_eq!(result[0].args, vec!["*.txt"]);
+}
+
+#[test]
+fn test_parse_flag_equals_value_can_be_subcommand_name() {
+    let args = vec!["audit".to_string(), "--limit=5".to_string()];
+    let result = parse_multi_subcommand(args).unwrap();
+    assert_eq!(get_flag_value(&result[0].flags, "limit"), Some("5".to_string()));
+    
+    let args = vec!["template".to_string(), "--delete=apply".to_string()];
+    let result = parse_multi_subcommand(args).unwrap();
+    assert_eq!(get_flag_value(&result[0].flags, "delete"), Some("apply".to_string()));
+}
+
+#[test]
+fn test_parse_switch_with_value_rejected() {
+    let args = vec!["apply".to_string(), "--yes=no".to_string()];
+    let result = parse_multi_subcommand(args);
+    
+    assert_eq!(result.unwrap_err(), "'--yes' does not take a value.");
+}
+
+#[test]
+fn test_parse_flag_equals_empty_value_rejected() {
+    let args = vec!["template".to_string(), "--use=".to_string()];
+    let result = parse_multi_subcommand(args);
+    
+    assert!(result.unwrap_err().starts_with("'--use' requires a value"));
+}
+
+#[test]
+fn test_parse_end_of_options_separator() {
+    let args = vec![
+        "rename".to_string(),
+        "%N.%E".to_string(),
+        "list".to_string(),
+        "*.txt".to_string(),
+        "--".to_string(),
+        "apply".to_string(),
+        "--foo.txt".to_string(),
+    ];
+    let result = parse_multi_subcommand(args).unwrap();
+    
+    // Nothing after '--' starts a new subcommand or is read as a flag
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[1].name, "list");
+    assert_eq!(result[1].args, vec!["*.txt", "apply", "--foo.txt"]);
+    assert!(result[1].flags.is_empty());
+}
+
+#[test]
+fn test_parse_end_of_options_for_subcommand_without_args() {
+    let args = vec!["apply".to_string(), "--".to_string(), "file".to_string()];
+    let result = parse_multi_subcommand(args);
+    assert_eq!(result.unwrap_err(), "'apply' does not take arguments (got 'file').");
+    
+    // A trailing '--' with nothing after it is harmless
+    let args = vec!["apply".to_string(), "--".to_string()];
+    assert!(parse_multi_subcommand(args).is_ok());
+}
+
+#[test]
+fn test_parse_end_of_options_before_subcommand() {
+    let args = vec!["--".to_string(), "list".to_string()];
+    let result = parse_multi_subcommand(args);
+    
+    assert!(result.unwrap_err().starts_with("'--' must follow a subcommand"));
+}
+
+#[test]
+fn test_parse_subcommand_name_as_argument() {
+    // 'manpage' takes a subcommand name, so 'rename' is its argument
+    let args = vec!["manpage".to_string(), "rename".to_string()];
+    let result = parse_multi_subcommand(args).unwrap();
+    
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].args, vec!["rename"]);
+    
+    // Only the first name is taken as the argument
+    let args = vec!["manpage".to_string(), "rename".to_string(), "apply".to_string()];
+    let result = parse_multi_subcommand(args).unwrap();
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[1].name, "apply");
+}
+
+#[test]
+fn test_parse_global_options() {
+    let args: Vec<String> = ["--config", "my.toml", "list", "*.txt"].iter().map(|s| s.to_string()).collect();
+    let (flags, rest) = parse_global_options(args).unwrap();
+    
+    assert_eq!(get_flag_value(&flags, "config"), Some("my.toml".to_string()));
+    assert_eq!(rest, vec!["list", "*.txt"]);
+    
+    let args: Vec<String> = ["--config=my.toml", "--help"].iter().map(|s| s.to_string()).collect();
+    let (flags, rest) = parse_global_options(args).unwrap();
+    assert_eq!(get_flag_value(&flags, "config"), Some("my.toml".to_string()));
+    assert_eq!(rest, vec!["--help"]);
+    
+    let args: Vec<String> = ["--no-config", "list", "*.txt", "--json"].iter().map(|s| s.to_string()).collect();
+    let (flags, rest) = parse_global_options(args).unwrap();
+    assert!(has_flag(&flags, "no-config"));
+    assert_eq!(rest, vec!["list", "*.txt", "--json"]);
+    
+    let args: Vec<String> = ["--chdir", "photos", "--no-config", "undo", "--check"].iter().map(|s| s.to_string()).collect();
+    let (flags, rest) = parse_global_options(args).unwrap();
+    assert_eq!(get_flag_value(&flags, "chdir"), Some("photos".to_string()));
+    assert!(has_flag(&flags, "no-config"));
+    assert_eq!(rest, vec!["undo", "--check"]);
+    
+    let args: Vec<String> = ["--color", "never", "--ascii", "list", "*.txt"].iter().map(|s| s.to_string()).collect();
+    let (flags, rest) = parse_global_options(args).unwrap();
+    assert_eq!(get_flag_value(&flags, "color"), Some("never".to_string()));
+    assert!(has_flag(&flags, "ascii"));
+    assert_eq!(rest, vec!["list", "*.txt"]);
+}
+
+#[test]
+fn test_parse_global_options_missing_value() {
+    let args: Vec<String> = ["--config", "list"].iter().map(|s| s.to_string()).collect();
+    let result = parse_global_options(args);
+    
+    assert_eq!(result.unwrap_err(), "'--config' requires a value: frencli --config <FILE> <SUBCOMMAND>...");
+    
+    let args: Vec<String> = ["--no-config=yes", "list"].iter().map(|s| s.to_string()).collect();
+    assert_eq!(parse_global_options(args).unwrap_err(), "'--no-config' does not take a value.");
+}
+
+#[test]
+fn test_parse_global_option_after_subcommand() {
+    let args: Vec<String> = ["list", "*.txt", "--config", "my.toml"].iter().map(|s| s.to_string()).collect();
+    let result = parse_multi_subcommand(args);
+    
+    assert!(result.unwrap_err().starts_with("'--config' is a global option and must come before the first subcommand"));
+}
+
+#[test]
+fn test_parse_alias_save_keeps_pipeline_as_arguments() {
+    let args: Vec<String> = ["alias", "--save", "photos", "--", "list", "*.jpg", "apply", "--yes"].iter().map(|s| s.to_string()).collect();
+    let result = parse_multi_subcommand(args).unwrap();
+    
+    assert_eq!(result.len(), 1);
+    assert_eq!(get_flag_value(&result[0].flags, "save"), Some("photos".to_string()));
+    assert_eq!(result[0].args, vec!["list", "*.jpg", "apply", "--yes"]);
+}
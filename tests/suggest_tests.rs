@@ -0,0 +1,40 @@
+//! Tests for the suggest module.
+//! 
+//! These tests verify "did you mean" suggestions for mistyped names.
+
+use frencli::suggest::{closest_match, did_you_mean};
+
+#[test]
+fn test_closest_match_single_typo() {
+    let candidates = ["list", "rename", "validate", "apply"];
+    assert_eq!(closest_match("aply", candidates), Some("apply"));
+    assert_eq!(closest_match("renmae", candidates), Some("rename"));
+}
+
+#[test]
+fn test_closest_match_is_case_insensitive() {
+    assert_eq!(closest_match("APPLY", ["apply"]), Some("apply"));
+}
+
+#[test]
+fn test_closest_match_picks_nearest() {
+    let candidates = ["photo-date", "photo-datetime", "photo-counter"];
+    assert_eq!(closest_match("photo-dat", candidates), Some("photo-date"));
+}
+
+#[test]
+fn test_closest_match_rejects_distant_names() {
+    assert_eq!(closest_match("xyz", ["list", "rename"]), None);
+    assert_eq!(closest_match("podcast", ["lowercase", "uppercase"]), None);
+}
+
+#[test]
+fn test_closest_match_no_candidates() {
+    assert_eq!(closest_match("list", std::iter::empty()), None);
+}
+
+#[test]
+fn test_did_you_mean() {
+    assert_eq!(did_you_mean("aply", ["apply"]), " Did you mean 'apply'?");
+    assert_eq!(did_you_mean("xyz", ["apply"]), "");
+}
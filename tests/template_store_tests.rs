@@ -0,0 +1,312 @@
+//! Tests for the template store module.
+//! 
+//! These tests verify parsing of user template files and template name validation.
+
+use frencli::templates::{Template, TemplateSource};
+use frencli::template_store::{
+    parse_template_file, read_template_file, validate_template_name, find_project_templates, PROJECT_TEMPLATES_FILE,
+    template_to_value, bundle_to_string, parse_bundle, read_raw_templates, write_raw_templates, TemplateFormat,
+};
+use serde_json::{Map, Value};
+use tempfile::TempDir;
+
+#[test]
+fn test_parse_template_file_string_entries() {
+    let content = r#"{"templates": {"podcast": "%T%N_%C3.%E", "scan": "scan_%C4.%E"}}"#;
+    let entries = parse_template_file(content, TemplateSource::User).unwrap();
+    
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].0, "podcast");
+    assert_eq!(entries[0].1.pattern, "%T%N_%C3.%E");
+    assert_eq!(entries[1].0, "scan");
+    assert_eq!(entries[1].1.pattern, "scan_%C4.%E");
+    assert_eq!(entries[1].1.source, TemplateSource::User);
+}
+
+#[test]
+fn test_parse_template_file_object_entries() {
+    let content = r#"{"templates": {"scan": {"pattern": "scan_%C4.%E"}}}"#;
+    let entries = parse_template_file(content, TemplateSource::Project).unwrap();
+    
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].0, "scan");
+    assert_eq!(entries[0].1.pattern, "scan_%C4.%E");
+    assert_eq!(entries[0].1.source, TemplateSource::Project);
+}
+
+#[test]
+fn test_parse_template_file_invalid_json() {
+    let result = parse_template_file("{ not json", TemplateSource::User);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().contains("invalid JSON"));
+}
+
+#[test]
+fn test_parse_template_file_missing_templates_object() {
+    let result = parse_template_file(r#"{"podcast": "%N.%E"}"#, TemplateSource::User);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().contains("\"templates\""));
+}
+
+#[test]
+fn test_parse_template_file_malformed_entries() {
+    let result = parse_template_file(r#"{"templates": {"bad": 42}}"#, TemplateSource::User);
+    let error = result.unwrap_err();
+    assert!(error.contains("template 'bad'"));
+    
+    let result = parse_template_file(r#"{"templates": {"bad": {"description": "no pattern"}}}"#, TemplateSource::User);
+    assert!(result.unwrap_err().contains("missing \"pattern\" field"));
+    
+    let result = parse_template_file(r#"{"templates": {"bad": "  "}}"#, TemplateSource::User);
+    assert!(result.unwrap_err().contains("pattern cannot be empty"));
+}
+
+#[test]
+fn test_parse_template_file_invalid_name() {
+    let result = parse_template_file(r#"{"templates": {"12": "%N.%E"}}"#, TemplateSource::User);
+    assert!(result.unwrap_err().contains("cannot be a number"));
+}
+
+#[test]
+fn test_validate_template_name() {
+    assert!(validate_template_name("podcast").is_ok());
+    assert!(validate_template_name("photo-2024").is_ok());
+    assert!(validate_template_name("").is_err());
+    assert!(validate_template_name("has space").is_err());
+    assert!(validate_template_name("42").is_err());
+}
+
+#[test]
+fn test_read_template_file_reports_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("templates.json");
+    std::fs::write(&path, r#"{"templates": {"bad": []}}"#).unwrap();
+    
+    let error = read_template_file(&path, TemplateSource::User).unwrap_err();
+    assert!(error.contains(&path.display().to_string()));
+    assert!(error.contains("template 'bad'"));
+}
+
+#[test]
+fn test_read_template_file_missing() {
+    let temp_dir = TempDir::new().unwrap();
+    let result = read_template_file(&temp_dir.path().join("missing.json"), TemplateSource::User);
+    assert!(result.unwrap_err().contains("Failed to read template file"));
+}
+
+#[test]
+fn test_template_format_from_path() {
+    assert_eq!(TemplateFormat::from_path(std::path::Path::new("templates.toml")), Some(TemplateFormat::Toml));
+    assert_eq!(TemplateFormat::from_path(std::path::Path::new("templates.json")), Some(TemplateFormat::Json));
+    assert_eq!(TemplateFormat::from_path(std::path::Path::new(PROJECT_TEMPLATES_FILE)), None);
+}
+
+#[test]
+fn test_read_project_template_file_detects_format() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join(PROJECT_TEMPLATES_FILE);
+    
+    std::fs::write(&path, r#"{"templates": {"team": "%N_json.%E"}}"#).unwrap();
+    let entries = read_template_file(&path, TemplateSource::Project).unwrap();
+    assert_eq!(entries[0].1.pattern, "%N_json.%E");
+    
+    std::fs::write(&path, "[templates]\nteam = \"%N_toml.%E\"\n").unwrap();
+    let entries = read_template_file(&path, TemplateSource::Project).unwrap();
+    assert_eq!(entries[0].1.pattern, "%N_toml.%E");
+    
+    // The error is reported for the format the file looks like
+    std::fs::write(&path, r#"{"templates": {"team": }}"#).unwrap();
+    assert!(read_template_file(&path, TemplateSource::Project).unwrap_err().contains("invalid JSON"));
+    std::fs::write(&path, "[templates]\nteam = \n").unwrap();
+    assert!(read_template_file(&path, TemplateSource::Project).unwrap_err().contains("invalid TOML: line 2"));
+}
+
+#[test]
+fn test_read_template_file_toml() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("templates.toml");
+    std::fs::write(&path, r#"
+[templates]
+podcast = "%T%N_%C3.%E"
+clean = ["%L%N.%E", "%N_%C3.%E"]
+
+[templates.trip]
+pattern = "{prefix}_%C{width}.%E"
+params = { width = 3 }
+description = "Number trip photos"
+"#).unwrap();
+    
+    let entries = read_template_file(&path, TemplateSource::User).unwrap();
+    let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["clean", "podcast", "trip"]);
+    assert_eq!(entries[0].1.chain, vec!["%N_%C3.%E"]);
+    let trip = &entries[2].1;
+    assert_eq!(trip.defaults.get("width"), Some(&"3".to_string()));
+    assert_eq!(trip.defaults.get("prefix"), None);
+    assert_eq!(trip.description.as_deref(), Some("Number trip photos"));
+    
+    std::fs::write(&path, "[templates\npodcast = 1").unwrap();
+    let error = read_template_file(&path, TemplateSource::User).unwrap_err();
+    assert!(error.contains("invalid TOML: line 1"), "{}", error);
+}
+
+#[test]
+fn test_write_raw_templates_toml_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("templates.toml");
+    let mut template = Template::new("{prefix}_%C{width}.%E", TemplateSource::User);
+    template.defaults.insert("width".to_string(), "3".to_string());
+    let mut templates = Map::new();
+    templates.insert("podcast".to_string(), Value::String("%T%N_%C3.%E".to_string()));
+    templates.insert("trip".to_string(), template_to_value(&template));
+    
+    // A parameter without a default (null in JSON) is left out of the TOML file
+    write_raw_templates(&path, templates).unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains("podcast = \"%T%N_%C3.%E\""), "{}", content);
+    assert!(!content.contains("prefix ="), "{}", content);
+    
+    let raw = read_raw_templates(&path).unwrap();
+    assert_eq!(raw.len(), 2);
+    let entries = read_template_file(&path, TemplateSource::User).unwrap();
+    assert_eq!(entries[1].1.defaults, template.defaults);
+    assert_eq!(entries[1].1.pattern, template.pattern);
+}
+
+#[test]
+fn test_find_project_templates_walks_upward() {
+    let temp_dir = TempDir::new().unwrap();
+    let nested = temp_dir.path().join("repo").join("src").join("deep");
+    std::fs::create_dir_all(&nested).unwrap();
+    let project_file = temp_dir.path().join("repo").join(PROJECT_TEMPLATES_FILE);
+    std::fs::write(&project_file, r#"{"templates": {}}"#).unwrap();
+    
+    assert_eq!(find_project_templates(&nested), Some(project_file.clone()));
+    assert_eq!(find_project_templates(&temp_dir.path().join("repo")), Some(project_file));
+}
+
+#[test]
+fn test_find_project_templates_prefers_nearest() {
+    let temp_dir = TempDir::new().unwrap();
+    let inner = temp_dir.path().join("inner");
+    std::fs::create_dir_all(&inner).unwrap();
+    std::fs::write(temp_dir.path().join(PROJECT_TEMPLATES_FILE), r#"{"templates": {}}"#).unwrap();
+    std::fs::write(inner.join(PROJECT_TEMPLATES_FILE), r#"{"templates": {}}"#).unwrap();
+    
+    assert_eq!(find_project_templates(&inner), Some(inner.join(PROJECT_TEMPLATES_FILE)));
+}
+
+#[test]
+fn test_find_project_templates_ignores_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    let bogus = temp_dir.path().join(PROJECT_TEMPLATES_FILE);
+    std::fs::create_dir_all(&bogus).unwrap();
+    
+    // A directory named .frentemplates is not a template file
+    assert_ne!(find_project_templates(temp_dir.path()), Some(bogus));
+}
+
+#[test]
+fn test_parse_template_file_params() {
+    let content = r#"{"templates": {"trip": {"pattern": "{prefix}_%C{width}.%E", "params": {"prefix": null, "width": 3}}}}"#;
+    let entries = parse_template_file(content, TemplateSource::User).unwrap();
+    let template = &entries[0].1;
+    
+    assert_eq!(template.parameters(), vec!["prefix", "width"]);
+    assert_eq!(template.defaults.get("width"), Some(&"3".to_string()));
+    assert_eq!(template.defaults.get("prefix"), None);
+}
+
+#[test]
+fn test_parse_template_file_malformed_params() {
+    let result = parse_template_file(r#"{"templates": {"t": {"pattern": "{a}.%E", "params": {"b": "x"}}}}"#, TemplateSource::User);
+    assert!(result.unwrap_err().contains("parameter 'b' is not used"));
+    
+    let result = parse_template_file(r#"{"templates": {"t": {"pattern": "{a}.%E", "params": ["a"]}}}"#, TemplateSource::User);
+    assert!(result.unwrap_err().contains("\"params\" must be an object"));
+}
+
+#[test]
+fn test_parse_template_file_metadata() {
+    let content = r#"{"templates": {"scan": {"pattern": "scan_%C4.%E", "description": "Number scans", "category": "document", "example": "a.pdf -> scan_0001.pdf"}}}"#;
+    let entries = parse_template_file(content, TemplateSource::User).unwrap();
+    let template = &entries[0].1;
+    
+    assert_eq!(template.description.as_deref(), Some("Number scans"));
+    assert_eq!(template.category.as_deref(), Some("document"));
+    assert_eq!(template.example.as_deref(), Some("a.pdf -> scan_0001.pdf"));
+    
+    let result = parse_template_file(r#"{"templates": {"scan": {"pattern": "%N.%E", "category": 3}}}"#, TemplateSource::User);
+    assert!(result.unwrap_err().contains("\"category\" must be a string"));
+}
+
+#[test]
+fn test_parse_template_file_chained_patterns() {
+    let content = r#"{"templates": {
+        "clean": ["%L%N.%E", "%N%R/ /_.%E"],
+        "numbered": {"pattern": ["%L%N.%E", "%N_%C3.%E"], "category": "cleanup"}
+    }}"#;
+    let entries = parse_template_file(content, TemplateSource::User).unwrap();
+    
+    assert_eq!(entries[0].0, "clean");
+    assert_eq!(entries[0].1.pattern, "%L%N.%E");
+    assert_eq!(entries[0].1.chain, vec!["%N%R/ /_.%E".to_string()]);
+    assert_eq!(entries[1].1.chain, vec!["%N_%C3.%E".to_string()]);
+    assert_eq!(entries[1].1.category.as_deref(), Some("cleanup"));
+}
+
+#[test]
+fn test_parse_template_file_malformed_chain() {
+    let result = parse_template_file(r#"{"templates": {"clean": []}}"#, TemplateSource::User);
+    assert!(result.unwrap_err().contains("pattern list cannot be empty"));
+    
+    let result = parse_template_file(r#"{"templates": {"clean": ["%N.%E", 3]}}"#, TemplateSource::User);
+    assert!(result.unwrap_err().contains("must be a string"));
+    
+    let result = parse_template_file(r#"{"templates": {"clean": ["%N.%E", " "]}}"#, TemplateSource::User);
+    assert!(result.unwrap_err().contains("pattern cannot be empty"));
+}
+
+#[test]
+fn test_template_to_value_round_trip() {
+    let plain = Template::new("%N_%C3.%E", TemplateSource::User);
+    assert_eq!(template_to_value(&plain), Value::String("%N_%C3.%E".to_string()));
+    
+    let mut rich = Template::new("{prefix}_%L%N.%E", TemplateSource::Project);
+    rich.chain = vec!["%N_%C{width}.%E".to_string()];
+    rich.defaults.insert("width".to_string(), "3".to_string());
+    rich.category = Some("photo".to_string());
+    
+    let mut templates = Map::new();
+    templates.insert("rich".to_string(), template_to_value(&rich));
+    let content = serde_json::json!({ "templates": templates }).to_string();
+    let entries = parse_template_file(&content, TemplateSource::Project).unwrap();
+    
+    assert_eq!(entries[0].1, rich);
+}
+
+#[test]
+fn test_bundle_round_trip() {
+    let mut templates = Map::new();
+    templates.insert("podcast".to_string(), Value::String("%T%N_%C3.%E".to_string()));
+    
+    let bundle = bundle_to_string(templates.clone()).unwrap();
+    assert!(bundle.contains("\"format\": \"frencli-templates\""));
+    assert!(bundle.contains("\"version\": 1"));
+    assert_eq!(parse_bundle(&bundle).unwrap(), templates);
+}
+
+#[test]
+fn test_parse_bundle_rejects_invalid_bundles() {
+    let error = parse_bundle(r#"{"templates": {}}"#).unwrap_err();
+    assert!(error.contains("not a template bundle"));
+    
+    let error = parse_bundle(r#"{"format": "frencli-templates", "version": 2, "templates": {}}"#).unwrap_err();
+    assert!(error.contains("bundle version 2 is not supported"));
+    
+    let error = parse_bundle(r#"{"format": "frencli-templates", "templates": {}}"#).unwrap_err();
+    assert!(error.contains("\"version\""));
+    
+    let error = parse_bundle(r#"{"format": "frencli-templates", "version": 1, "templates": {"bad": 3}}"#).unwrap_err();
+    assert!(error.contains("template 'bad'"));
+}
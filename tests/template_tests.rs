@@ -2,8 +2,15 @@
 //! 
 //! These tests verify template listing and retrieval functionality.
 
-use frencli::template::handle_template_command;
-use frencli::templates::TemplateRegistry;
+use frencli::template::{
+    handle_template_command, handle_template_list, handle_template_try, handle_template_save, handle_template_delete,
+    handle_template_rename, handle_template_export, handle_template_import, handle_template_check, ConflictPolicy,
+};
+use frencli::templates::{Template, TemplateRegistry, TemplateSource};
+use freneng::RenamingEngine;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
 
 #[test]
 fn test_handle_template_list() {
@@ -21,8 +28,8 @@ fn test_handle_template_use_by_name() {
     
     let result = handle_template_command(&registry, false, Some("lowercase".to_string()));
     assert!(result.is_ok());
-    let pattern = result.unwrap();
-    assert_eq!(pattern, Some("%L%N.%E".to_string()));
+    let patterns = result.unwrap();
+    assert_eq!(patterns, Some(vec!["%L%N.%E".to_string()]));
 }
 
 #[test]
@@ -33,8 +40,8 @@ fn test_handle_template_use_by_index() {
     // Use first template (index 1)
     let result = handle_template_command(&registry, false, Some("1".to_string()));
     assert!(result.is_ok());
-    let pattern = result.unwrap();
-    assert_eq!(pattern, Some(templates[0].1.clone()));
+    let patterns = result.unwrap().unwrap();
+    assert_eq!(patterns[0], *templates[0].1);
 }
 
 #[test]
@@ -81,17 +88,362 @@ fn test_handle_template_no_action() {
     assert!(error.contains("requires either --list or --use"));
 }
 
+#[test]
+fn test_handle_template_use_chained_template() {
+    let mut registry = TemplateRegistry::new();
+    let mut template = Template::new("{prefix}_%N.%E", TemplateSource::User);
+    template.chain = vec!["%L%N.%E".to_string()];
+    template.defaults.insert("prefix".to_string(), "Trip".to_string());
+    registry.insert("trip".to_string(), template);
+    
+    // Every stage is returned, not just the first, with defaults filled in
+    let result = handle_template_command(&registry, false, Some("trip".to_string()));
+    assert_eq!(result.unwrap(), Some(vec!["Trip_%N.%E".to_string(), "%L%N.%E".to_string()]));
+    
+    // A parameter without a default cannot be filled
+    registry.insert("show".to_string(), Template::new("{show}_%N.%E", TemplateSource::User));
+    let error = handle_template_command(&registry, false, Some("show".to_string())).unwrap_err();
+    assert!(error.contains("Template 'show'"));
+}
+
 #[test]
 fn test_handle_template_use_all_templates() {
     let registry = TemplateRegistry::new();
-    let templates = registry.list();
     
-    // Test that all templates can be retrieved by name
-    for (name, expected_pattern) in templates {
-        let result = handle_template_command(&registry, false, Some(name.clone()));
-        assert!(result.is_ok(), "Failed to get template: {}", name);
-        let pattern = result.unwrap();
-        assert_eq!(pattern, Some(expected_pattern.clone()), "Pattern mismatch for template: {}", name);
+    // Every template can be retrieved by name and by index, with all of its patterns
+    for (index, (name, template)) in registry.templates().into_iter().enumerate() {
+        let expected = template.fill(&[]).ok();
+        let result = handle_template_command(&registry, false, Some(name.clone())).ok().flatten();
+        assert_eq!(result, expected, "Pattern mismatch for template: {}", name);
+        let result = handle_template_command(&registry, false, Some((index + 1).to_string())).ok().flatten();
+        assert_eq!(result, expected, "Pattern mismatch for template index: {}", index + 1);
     }
 }
 
+#[test]
+fn test_handle_template_save_creates_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("frencli").join("templates.json");
+    
+    handle_template_save(&path, "podcast", "%T%N_%C3.%E", &[], false).unwrap();
+    
+    let mut registry = TemplateRegistry::new();
+    registry.merge_file(&path, TemplateSource::User).unwrap();
+    assert_eq!(registry.get("podcast"), Some(&"%T%N_%C3.%E".to_string()));
+}
+
+#[test]
+fn test_handle_template_save_refuses_clobber_without_force() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("templates.json");
+    
+    // Built-in template
+    let error = handle_template_save(&path, "lowercase", "%L%F", &[], false).unwrap_err();
+    assert!(error.contains("built-in template"));
+    assert!(error.contains("--force"));
+    assert!(!path.exists());
+    
+    // Existing user template
+    handle_template_save(&path, "podcast", "%N.%E", &[], false).unwrap();
+    let error = handle_template_save(&path, "podcast", "%L%N.%E", &[], false).unwrap_err();
+    assert!(error.contains("already exists"));
+    
+    // --force overrides both
+    handle_template_save(&path, "lowercase", "%L%F", &[], true).unwrap();
+    handle_template_save(&path, "podcast", "%L%N.%E", &[], true).unwrap();
+    let mut registry = TemplateRegistry::new();
+    registry.merge_file(&path, TemplateSource::User).unwrap();
+    assert_eq!(registry.get("lowercase"), Some(&"%L%F".to_string()));
+    assert_eq!(registry.get("podcast"), Some(&"%L%N.%E".to_string()));
+}
+
+#[test]
+fn test_handle_template_save_invalid_name() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("templates.json");
+    
+    assert!(handle_template_save(&path, "7", "%N.%E", &[], false).is_err());
+    assert!(handle_template_save(&path, "two words", "%N.%E", &[], false).is_err());
+}
+
+#[test]
+fn test_handle_template_delete() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("templates.json");
+    handle_template_save(&path, "podcast", "%N.%E", &[], false).unwrap();
+    
+    handle_template_delete(&path, "podcast").unwrap();
+    let mut registry = TemplateRegistry::new();
+    registry.merge_file(&path, TemplateSource::User).unwrap();
+    assert_eq!(registry.get("podcast"), None);
+    
+    // Unknown and built-in templates cannot be deleted
+    assert!(handle_template_delete(&path, "podcast").unwrap_err().contains("No user template"));
+    assert!(handle_template_delete(&path, "lowercase").unwrap_err().contains("cannot be deleted"));
+}
+
+#[test]
+fn test_handle_template_delete_repairs_malformed_entry() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("templates.json");
+    fs::write(&path, r#"{"templates": {"podcast": "%N.%E", "broken": 1}}"#).unwrap();
+    
+    let mut registry = TemplateRegistry::new();
+    assert!(registry.merge_file(&path, TemplateSource::User).is_err());
+    
+    handle_template_delete(&path, "broken").unwrap();
+    registry.merge_file(&path, TemplateSource::User).unwrap();
+    assert_eq!(registry.get("podcast"), Some(&"%N.%E".to_string()));
+    
+    // A file that is not valid JSON is still refused rather than overwritten
+    fs::write(&path, "{ not json").unwrap();
+    assert!(handle_template_delete(&path, "podcast").unwrap_err().contains("invalid JSON"));
+}
+
+#[test]
+fn test_handle_template_rename() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("templates.json");
+    handle_template_save(&path, "podcast", "%N.%E", &[], false).unwrap();
+    handle_template_save(&path, "episode", "%C3.%E", &[], false).unwrap();
+    
+    // Target name taken
+    assert!(handle_template_rename(&path, "podcast", "episode", false).unwrap_err().contains("already exists"));
+    assert!(handle_template_rename(&path, "podcast", "lowercase", false).unwrap_err().contains("built-in"));
+    // Source must be a user template
+    assert!(handle_template_rename(&path, "uppercase", "upper", false).unwrap_err().contains("cannot be renamed"));
+    
+    handle_template_rename(&path, "podcast", "show", false).unwrap();
+    let mut registry = TemplateRegistry::new();
+    registry.merge_file(&path, TemplateSource::User).unwrap();
+    assert_eq!(registry.get("podcast"), None);
+    assert_eq!(registry.get("show"), Some(&"%N.%E".to_string()));
+}
+
+#[test]
+fn test_handle_template_save_with_param_defaults() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("templates.json");
+    let defaults = vec![("width".to_string(), "3".to_string())];
+    
+    handle_template_save(&path, "trip", "{prefix}_%C{width}.%E", &defaults, false).unwrap();
+    
+    let mut registry = TemplateRegistry::new();
+    registry.merge_file(&path, TemplateSource::User).unwrap();
+    let template = registry.get_template("trip").unwrap();
+    assert_eq!(template.defaults.get("width"), Some(&"3".to_string()));
+    
+    // Defaults for parameters the pattern does not use are rejected
+    let defaults = vec![("size".to_string(), "3".to_string())];
+    let error = handle_template_save(&path, "other", "{prefix}.%E", &defaults, false).unwrap_err();
+    assert!(error.contains("'size' is not used"));
+}
+
+#[test]
+fn test_handle_template_list_by_category() {
+    let registry = TemplateRegistry::new();
+    
+    assert!(handle_template_list(&registry, Some("photo"), false).is_ok());
+    assert!(handle_template_list(&registry, Some("PHOTO"), true).is_ok());
+    assert!(handle_template_list(&registry, None, true).is_ok());
+}
+
+#[test]
+fn test_handle_template_list_unknown_category() {
+    let registry = TemplateRegistry::new();
+    
+    let error = handle_template_list(&registry, Some("music"), false).unwrap_err();
+    assert!(error.contains("No templates in category 'music'"));
+    assert!(error.contains("photo"));
+    assert!(error.contains("cleanup"));
+}
+
+fn create_sample_files(temp_dir: &TempDir) -> Vec<PathBuf> {
+    ["b.txt", "a.txt"].iter()
+        .map(|name| {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, "").unwrap();
+            path
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_handle_template_try_all_templates() {
+    let temp_dir = TempDir::new().unwrap();
+    let files = create_sample_files(&temp_dir);
+    let mut registry = TemplateRegistry::new();
+    // Templates with required parameters are listed but not previewed
+    registry.insert("trip".to_string(), Template::new("{prefix}_%N.%E", TemplateSource::User));
+    
+    let result = handle_template_try(&RenamingEngine, &registry, &files, &[], None).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_handle_template_try_selected_templates() {
+    let temp_dir = TempDir::new().unwrap();
+    let files = create_sample_files(&temp_dir);
+    let registry = TemplateRegistry::new();
+    
+    let names = vec!["lowercase".to_string(), "1".to_string()];
+    assert!(handle_template_try(&RenamingEngine, &registry, &files, &names, None).await.is_ok());
+    assert!(handle_template_try(&RenamingEngine, &registry, &files, &[], Some("counter")).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_handle_template_try_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let files = create_sample_files(&temp_dir);
+    let registry = TemplateRegistry::new();
+    
+    let names = vec!["missing".to_string()];
+    let error = handle_template_try(&RenamingEngine, &registry, &files, &names, None).await.unwrap_err();
+    assert!(error.contains("Unknown template 'missing'"));
+    
+    // The category filter applies to explicitly named templates too
+    let names = vec!["lowercase".to_string()];
+    let error = handle_template_try(&RenamingEngine, &registry, &files, &names, Some("photo")).await.unwrap_err();
+    assert!(error.contains("No templates to try in category 'photo'"));
+}
+
+/// Writes a bundle with `podcast` and `scan` templates and returns its path
+fn write_sample_bundle(temp_dir: &TempDir) -> String {
+    let path = temp_dir.path().join("bundle.json");
+    fs::write(&path, r#"{
+        "format": "frencli-templates",
+        "version": 1,
+        "templates": {
+            "podcast": "%T%N_%C3.%E",
+            "scan": {"pattern": "scan_%C4.%E", "category": "document"}
+        }
+    }"#).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_conflict_policy_parse() {
+    assert_eq!(ConflictPolicy::parse("skip"), Ok(ConflictPolicy::Skip));
+    assert_eq!(ConflictPolicy::parse("overwrite"), Ok(ConflictPolicy::Overwrite));
+    assert_eq!(ConflictPolicy::parse("rename-on-import"), Ok(ConflictPolicy::Rename));
+    assert!(ConflictPolicy::parse("merge").unwrap_err().contains("Invalid --on-conflict value 'merge'"));
+}
+
+#[test]
+fn test_handle_template_export_and_import() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut registry = TemplateRegistry::new();
+    registry.insert("podcast".to_string(), Template::new("%T%N_%C3.%E", TemplateSource::User));
+    let bundle_path = temp_dir.path().join("out.json");
+    let bundle = bundle_path.to_string_lossy().to_string();
+    
+    // Without names, only user and project templates are exported
+    assert_eq!(handle_template_export(&registry, &bundle, &[]), Ok(1));
+    assert_eq!(handle_template_export(&registry, &bundle, &["podcast".to_string(), "lowercase".to_string()]), Ok(2));
+    
+    // 'lowercase' is a built-in, so it conflicts unless a policy is given
+    let store_path = temp_dir.path().join("templates.json");
+    assert!(handle_template_import(&TemplateRegistry::new(), &store_path, &bundle, None).is_err());
+    assert_eq!(handle_template_import(&TemplateRegistry::new(), &store_path, &bundle, Some(ConflictPolicy::Overwrite)), Ok(2));
+    
+    let mut imported = TemplateRegistry::new();
+    imported.merge_file(&store_path, TemplateSource::User).unwrap();
+    assert_eq!(imported.get("podcast"), Some(&"%T%N_%C3.%E".to_string()));
+    assert_eq!(imported.get_template("lowercase").unwrap().source, TemplateSource::User);
+}
+
+#[test]
+fn test_handle_template_export_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let registry = TemplateRegistry::new();
+    let bundle = temp_dir.path().join("out.json").to_string_lossy().to_string();
+    
+    assert!(handle_template_export(&registry, &bundle, &[]).unwrap_err().contains("No user or project templates"));
+    assert!(handle_template_export(&registry, &bundle, &["nope".to_string()]).unwrap_err().contains("Unknown template 'nope'"));
+}
+
+#[test]
+fn test_handle_template_import_conflicts_require_policy() {
+    let temp_dir = TempDir::new().unwrap();
+    let bundle = write_sample_bundle(&temp_dir);
+    let store_path = temp_dir.path().join("templates.json");
+    handle_template_save(&store_path, "podcast", "%N.%E", &[], false).unwrap();
+    let mut registry = TemplateRegistry::new();
+    registry.merge_file(&store_path, TemplateSource::User).unwrap();
+    
+    let error = handle_template_import(&registry, &store_path, &bundle, None).unwrap_err();
+    assert!(error.contains("already exist: podcast"));
+    assert!(error.contains("--on-conflict"));
+    
+    // Nothing was written
+    let mut after = TemplateRegistry::new();
+    after.merge_file(&store_path, TemplateSource::User).unwrap();
+    assert!(after.get("scan").is_none());
+}
+
+#[test]
+fn test_handle_template_import_policies() {
+    let temp_dir = TempDir::new().unwrap();
+    let bundle = write_sample_bundle(&temp_dir);
+    let store_path = temp_dir.path().join("templates.json");
+    handle_template_save(&store_path, "podcast", "%N.%E", &[], false).unwrap();
+    let mut registry = TemplateRegistry::new();
+    registry.merge_file(&store_path, TemplateSource::User).unwrap();
+    let load = || {
+        let mut loaded = TemplateRegistry::new();
+        loaded.merge_file(&store_path, TemplateSource::User).unwrap();
+        loaded
+    };
+    
+    assert_eq!(handle_template_import(&registry, &store_path, &bundle, Some(ConflictPolicy::Skip)), Ok(1));
+    assert_eq!(load().get("podcast"), Some(&"%N.%E".to_string()));
+    assert!(load().get("scan").is_some());
+    
+    let registry = load();
+    assert_eq!(handle_template_import(&registry, &store_path, &bundle, Some(ConflictPolicy::Rename)), Ok(2));
+    assert_eq!(load().get("podcast-2"), Some(&"%T%N_%C3.%E".to_string()));
+    assert_eq!(load().get_template("scan-2").unwrap().category.as_deref(), Some("document"));
+    
+    let registry = load();
+    assert_eq!(handle_template_import(&registry, &store_path, &bundle, Some(ConflictPolicy::Overwrite)), Ok(2));
+    assert_eq!(load().get("podcast"), Some(&"%T%N_%C3.%E".to_string()));
+}
+
+#[test]
+fn test_handle_template_import_invalid_bundle() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("bundle.json");
+    fs::write(&path, r#"{"templates": {"podcast": "%N.%E"}}"#).unwrap();
+    let store_path = temp_dir.path().join("templates.json");
+    
+    let error = handle_template_import(&TemplateRegistry::new(), &store_path, &path.to_string_lossy(), None).unwrap_err();
+    assert!(error.contains("Invalid template bundle"));
+    assert!(!store_path.exists());
+}
+
+#[test]
+fn test_handle_template_check_builtins() {
+    let registry = TemplateRegistry::new();
+    assert!(handle_template_check(&registry, &[]).is_ok());
+}
+
+#[test]
+fn test_handle_template_check_reports_problems() {
+    let mut registry = TemplateRegistry::new();
+    registry.insert("broken".to_string(), Template::new("%N_%Q", TemplateSource::User));
+    let total = registry.templates().len();
+    
+    let result = handle_template_check(&registry, &[]);
+    assert_eq!(result.unwrap_err(), format!("1 of {} template(s) have problems.", total));
+    
+    // Checking only other templates succeeds
+    assert!(handle_template_check(&registry, &["lowercase".to_string()]).is_ok());
+    assert!(handle_template_check(&registry, &["broken".to_string()]).is_err());
+}
+
+#[test]
+fn test_handle_template_check_unknown_name() {
+    let registry = TemplateRegistry::new();
+    let result = handle_template_check(&registry, &["no-such-template".to_string()]);
+    assert!(result.is_err());
+}
@@ -2,7 +2,8 @@
 //! 
 //! These tests verify template registration, retrieval, and listing functionality.
 
-use frencli::templates::TemplateRegistry;
+use frencli::templates::{Template, TemplateRegistry, TemplateSource, parse_param_assignments};
+use tempfile::TempDir;
 
 #[test]
 fn test_template_registry_new() {
@@ -94,3 +95,216 @@ fn test_template_registry_case_sensitive() {
     assert_eq!(registry.get("LOWERCASE"), None);
 }
 
+
+#[test]
+fn test_template_registry_builtins_have_builtin_source() {
+    let registry = TemplateRegistry::new();
+    
+    for (name, template) in registry.templates() {
+        assert_eq!(template.source, TemplateSource::Builtin, "Template '{}' should be built-in", name);
+    }
+}
+
+#[test]
+fn test_template_registry_merge_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("templates.json");
+    std::fs::write(&path, r#"{"templates": {"podcast": "%T%N_%C3.%E", "lowercase": "%L%F"}}"#).unwrap();
+    
+    let mut registry = TemplateRegistry::new();
+    let builtin_count = registry.list().len();
+    let loaded = registry.merge_file(&path, TemplateSource::User).unwrap();
+    
+    assert_eq!(loaded, 2);
+    // One new template, one override of a built-in
+    assert_eq!(registry.list().len(), builtin_count + 1);
+    assert_eq!(registry.get("podcast"), Some(&"%T%N_%C3.%E".to_string()));
+    assert_eq!(registry.get("lowercase"), Some(&"%L%F".to_string()));
+    assert_eq!(registry.get_template("lowercase").unwrap().source, TemplateSource::User);
+    assert_eq!(registry.get_template("uppercase").unwrap().source, TemplateSource::Builtin);
+}
+
+#[test]
+fn test_template_registry_merge_file_malformed() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("templates.json");
+    std::fs::write(&path, r#"{"templates": {"podcast": 1}}"#).unwrap();
+    
+    let mut registry = TemplateRegistry::new();
+    let result = registry.merge_file(&path, TemplateSource::User);
+    assert!(result.is_err());
+    // Registry is left untouched on error
+    assert_eq!(registry.get("podcast"), None);
+}
+
+#[test]
+fn test_template_registry_project_layer_overrides_user() {
+    let temp_dir = TempDir::new().unwrap();
+    let user_path = temp_dir.path().join("templates.json");
+    let project_path = temp_dir.path().join(".frentemplates");
+    std::fs::write(&user_path, r#"{"templates": {"team": "%N_user.%E"}}"#).unwrap();
+    std::fs::write(&project_path, r#"{"templates": {"team": "%N_project.%E"}}"#).unwrap();
+    
+    let mut registry = TemplateRegistry::new();
+    registry.merge_file(&user_path, TemplateSource::User).unwrap();
+    registry.merge_file(&project_path, TemplateSource::Project).unwrap();
+    
+    let template = registry.get_template("team").unwrap();
+    assert_eq!(template.pattern, "%N_project.%E");
+    assert_eq!(template.source, TemplateSource::Project);
+}
+
+#[test]
+fn test_template_source_labels() {
+    assert_eq!(TemplateSource::Builtin.label(), "builtin");
+    assert_eq!(TemplateSource::User.label(), "user");
+    assert_eq!(TemplateSource::Project.label(), "project");
+}
+
+#[test]
+fn test_template_parameters() {
+    let template = Template::new("{prefix}_%C{width}_{prefix}.%E", TemplateSource::User);
+    assert_eq!(template.parameters(), vec!["prefix", "width"]);
+    
+    let plain = Template::new("%N.%E", TemplateSource::Builtin);
+    assert!(plain.parameters().is_empty());
+}
+
+#[test]
+fn test_template_fill() {
+    let mut template = Template::new("{prefix}_%C{width}.%E", TemplateSource::User);
+    template.defaults.insert("width".to_string(), "3".to_string());
+    
+    let values = vec![("prefix".to_string(), "trip".to_string())];
+    assert_eq!(template.fill(&values), Ok(vec!["trip_%C3.%E".to_string()]));
+    
+    // Last value wins for repeated keys
+    let values = vec![
+        ("prefix".to_string(), "a".to_string()),
+        ("prefix".to_string(), "b".to_string()),
+    ];
+    assert_eq!(template.fill(&values), Ok(vec!["b_%C3.%E".to_string()]));
+}
+
+#[test]
+fn test_template_fill_escaped_braces() {
+    let template = Template::new("{{%N}}_{tag}.%E", TemplateSource::User);
+    let values = vec![("tag".to_string(), "x".to_string())];
+    
+    assert_eq!(template.parameters(), vec!["tag"]);
+    assert_eq!(template.fill(&values), Ok(vec!["{%N}_x.%E".to_string()]));
+}
+
+#[test]
+fn test_template_fill_errors() {
+    let template = Template::new("{prefix}_%N.%E", TemplateSource::User);
+    
+    assert!(template.fill(&[]).unwrap_err().contains("missing value for parameter(s): prefix"));
+    let values = vec![("other".to_string(), "x".to_string())];
+    assert!(template.fill(&values).unwrap_err().contains("unknown parameter 'other'"));
+}
+
+#[test]
+fn test_template_regex_quantifier_is_not_a_parameter() {
+    let template = Template::new(r"%N%X/\d{4}/YYYY/.%E", TemplateSource::User);
+    
+    assert!(template.parameters().is_empty());
+    assert_eq!(template.fill(&[]), Ok(vec![r"%N%X/\d{4}/YYYY/.%E".to_string()]));
+}
+
+#[test]
+fn test_template_non_identifier_braces_are_literal() {
+    let template = Template::new("{}_{my-tag}_{2x}_{ok}_{open.%E", TemplateSource::User);
+    let values = vec![("ok".to_string(), "1".to_string())];
+    
+    assert_eq!(template.parameters(), vec!["ok"]);
+    assert_eq!(template.fill(&values), Ok(vec!["{}_{my-tag}_{2x}_1_{open.%E".to_string()]));
+}
+
+#[test]
+fn test_parse_param_assignments() {
+    let values = vec!["prefix=trip".to_string(), "sep==".to_string()];
+    assert_eq!(parse_param_assignments(&values), Ok(vec![
+        ("prefix".to_string(), "trip".to_string()),
+        ("sep".to_string(), "=".to_string()),
+    ]));
+    
+    assert!(parse_param_assignments(&["novalue".to_string()]).is_err());
+    assert!(parse_param_assignments(&["=x".to_string()]).is_err());
+}
+
+#[test]
+fn test_builtin_templates_have_metadata() {
+    let registry = TemplateRegistry::new();
+    
+    for (name, template) in registry.templates() {
+        assert!(template.description.is_some(), "{} has no description", name);
+        assert!(template.category.is_some(), "{} has no category", name);
+        assert!(template.example.as_ref().is_some_and(|e| e.contains(" -> ")), "{} has no example", name);
+    }
+    
+    // Every template can be told apart in 'template --list'
+    let mut descriptions: Vec<_> = registry.templates().into_iter().map(|(_, t)| t.description.clone()).collect();
+    descriptions.sort();
+    descriptions.dedup();
+    assert_eq!(descriptions.len(), registry.templates().len());
+}
+
+#[test]
+fn test_template_categories() {
+    let mut registry = TemplateRegistry::new();
+    let mut template = Template::new("%N.%E", TemplateSource::User);
+    template.category = Some("Music".to_string());
+    registry.insert("song".to_string(), template);
+    
+    let categories = registry.categories();
+    assert!(categories.contains(&"photo".to_string()));
+    assert!(categories.contains(&"music".to_string()));
+    assert!(registry.get_template("song").unwrap().in_category("MUSIC"));
+    assert!(!registry.get_template("lowercase").unwrap().in_category("photo"));
+}
+
+#[test]
+fn test_find_by_name_or_index() {
+    let registry = TemplateRegistry::new();
+    let templates = registry.templates();
+    
+    let (name, template) = registry.find("lowercase").unwrap();
+    assert_eq!(name, "lowercase");
+    assert_eq!(template.pattern, "%L%N.%E");
+    
+    let (name, _) = registry.find("1").unwrap();
+    assert_eq!(name, templates[0].0);
+    
+    assert!(registry.find("0").unwrap_err().contains("out of range"));
+    assert!(registry.find("999").unwrap_err().contains("out of range"));
+    assert!(registry.find("nope").unwrap_err().contains("Unknown template 'nope'"));
+}
+
+#[test]
+fn test_chained_template_patterns_and_fill() {
+    let mut template = Template::new("%L%N.%E", TemplateSource::User);
+    template.chain = vec!["{prefix}_%N.%E".to_string(), "%N_%C3.%E".to_string()];
+    
+    assert_eq!(template.patterns(), vec!["%L%N.%E", "{prefix}_%N.%E", "%N_%C3.%E"]);
+    assert_eq!(template.display_pattern(), "%L%N.%E => {prefix}_%N.%E => %N_%C3.%E");
+    assert_eq!(template.parameters(), vec!["prefix"]);
+    
+    let values = vec![("prefix".to_string(), "trip".to_string())];
+    assert_eq!(template.fill(&values), Ok(vec![
+        "%L%N.%E".to_string(),
+        "trip_%N.%E".to_string(),
+        "%N_%C3.%E".to_string(),
+    ]));
+    assert!(template.fill(&[]).unwrap_err().contains("prefix"));
+}
+
+#[test]
+fn test_template_registry_find_suggests_close_name() {
+    let registry = TemplateRegistry::new();
+    let err = registry.find("photo-dat").unwrap_err();
+    assert!(err.contains("Did you mean 'photo-date'?"), "got: {}", err);
+    
+    let err = registry.find("nothing-like-it").unwrap_err();
+    assert!(!err.contains("Did you mean"), "got: {}", err);
+}
@@ -98,28 +98,3 @@ pub async fn setup_test_data_async() -> Option<(TempDir, PathBuf)> {
     
     Some((temp_dir, test_data_dst))
 }
-
-/// RAII guard that restores the current directory when dropped.
-/// 
-/// Use this only when absolutely necessary (e.g., for functions that read from current directory).
-/// Prefer using absolute paths and the working_directory parameter where possible.
-#[allow(dead_code)] // Used by audit_tests.rs, undo_tests.rs, rename_tests.rs
-pub struct DirGuard {
-    original_dir: PathBuf,
-}
-
-impl DirGuard {
-    #[allow(dead_code)] // Used by audit_tests.rs, undo_tests.rs, rename_tests.rs
-    pub fn new(target_dir: &Path) -> Result<Self, std::io::Error> {
-        let original_dir = std::env::current_dir()?;
-        std::env::set_current_dir(target_dir)?;
-        Ok(DirGuard { original_dir })
-    }
-}
-
-impl Drop for DirGuard {
-    fn drop(&mut self) {
-        let _ = std::env::set_current_dir(&self.original_dir);
-    }
-}
-
@@ -3,6 +3,7 @@
 //! These tests verify validation command functionality.
 
 use frencli::validate::handle_validate_command;
+use frencli::error::FrencliError;
 use freneng::{RenamingEngine, EnginePreviewResult, FileRename};
 use tempfile::TempDir;
 use tokio::fs;
@@ -24,10 +25,9 @@ async fn test_handle_validate_with_valid_renames() {
         has_empty_names: false,
     };
     
-    // Should not exit (validation passes)
-    // Note: This function exits on error, so we can't easily assert success
-    // But we can verify it doesn't panic
-    handle_validate_command(&engine, &preview, false).await;
+    let result = handle_validate_command(&engine, &preview, false).await.unwrap();
+    assert_eq!(result.valid.len(), 1);
+    assert!(result.issues.is_empty());
 }
 
 #[tokio::test]
@@ -36,8 +36,8 @@ async fn test_handle_validate_with_empty_names() {
     let file = temp_dir.path().join("test.txt");
     fs::write(&file, "content").await.unwrap();
     
-    let _engine = RenamingEngine;
-    let _preview = EnginePreviewResult {
+    let engine = RenamingEngine;
+    let preview = EnginePreviewResult {
         renames: vec![FileRename {
             old_path: file.clone(),
             new_path: temp_dir.path().join(""),
@@ -47,9 +47,10 @@ async fn test_handle_validate_with_empty_names() {
         has_empty_names: true,
     };
     
-    // With skip_invalid=false, this should exit with code 1
-    // We can't easily test exit codes, but we verify the function exists
-    // In practice, this would exit, so the test verifies compilation
+    // With skip_invalid=false, empty names fail validation
+    let err = handle_validate_command(&engine, &preview, false).await.unwrap_err();
+    assert!(matches!(err, FrencliError::ValidationFailed(_)), "got: {:?}", err);
+    assert!(err.message().contains("1 empty filename(s)"), "got: {}", err);
 }
 
 #[tokio::test]
@@ -70,7 +71,7 @@ async fn test_handle_validate_with_warnings() {
     };
     
     // Should display warnings but continue
-    handle_validate_command(&engine, &preview, false).await;
+    assert!(handle_validate_command(&engine, &preview, false).await.is_ok());
 }
 
 #[tokio::test]
@@ -91,7 +92,8 @@ async fn test_handle_validate_with_skip_invalid() {
     };
     
     // With skip_invalid=true, should continue despite empty names
-    handle_validate_command(&engine, &preview, true).await;
+    let result = handle_validate_command(&engine, &preview, true).await.unwrap();
+    assert_eq!(result.issues.len(), 1);
 }
 
 #[tokio::test]
@@ -112,7 +114,36 @@ async fn test_handle_validate_with_overwrite() {
     };
     
     // Should validate (overwrite parameter removed - validation always checks for existing files)
-    handle_validate_command(&engine, &preview, false).await;
+    assert!(handle_validate_command(&engine, &preview, false).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_handle_validate_target_exists_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("test.txt");
+    let existing = temp_dir.path().join("existing.txt");
+    fs::write(&file, "content").await.unwrap();
+    fs::write(&existing, "content").await.unwrap();
+    
+    let engine = RenamingEngine;
+    let preview = EnginePreviewResult {
+        renames: vec![FileRename {
+            old_path: file.clone(),
+            new_path: existing.clone(),
+            new_name: "existing.txt".to_string(),
+        }],
+        warnings: vec![],
+        has_empty_names: false,
+    };
+    
+    let err = handle_validate_command(&engine, &preview, false).await.unwrap_err();
+    assert!(matches!(err, FrencliError::ValidationFailed(_)), "got: {:?}", err);
+    assert!(err.message().contains("1 issue(s)"), "got: {}", err);
+    
+    // With skip_invalid=true the issues are returned instead
+    let result = handle_validate_command(&engine, &preview, true).await.unwrap();
+    assert_eq!(result.issues.len(), 1);
+    assert!(result.valid.is_empty());
 }
 
 #[tokio::test]
@@ -145,8 +176,9 @@ async fn test_handle_validate_multiple_files() {
         has_empty_names: false,
     };
     
-    // Use skip_invalid=true to prevent process exit if there are any validation issues
-    // (e.g., race conditions with temp directory permissions)
-    handle_validate_command(&engine, &preview, true).await;
+    // Use skip_invalid=true so validation issues (e.g., race conditions with
+    // temp directory permissions) do not fail the test
+    let result = handle_validate_command(&engine, &preview, true).await.unwrap();
+    assert_eq!(result.valid.len() + result.issues.len(), 2);
 }
 
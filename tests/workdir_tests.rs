@@ -0,0 +1,96 @@
+//! Tests for the workdir module.
+//!
+//! These tests verify path resolution against the base directory, and that the
+//! undo history and audit log are read and written there. All tests use
+//! isolated temp directories, without changing the current directory.
+
+use frencli::workdir::{
+    check_base_dir, clear_history, load_history, log_audit, read_audit_log, resolve, resolve_pattern,
+    save_history, HISTORY_FILE,
+};
+use freneng::RenameExecutionResult;
+use freneng::history::RenameAction;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn rename_action(dir: &Path, old: &str, new: &str) -> RenameAction {
+    RenameAction {
+        old_path: dir.join(old),
+        new_path: dir.join(new),
+    }
+}
+
+#[test]
+fn test_resolve() {
+    assert_eq!(resolve(Path::new("photos"), "a.jpg"), PathBuf::from("photos/a.jpg"));
+    assert_eq!(resolve(Path::new(""), "a.jpg"), PathBuf::from("a.jpg"));
+    assert_eq!(resolve(Path::new("photos"), "/tmp/a.jpg"), PathBuf::from("/tmp/a.jpg"));
+    assert_eq!(resolve_pattern(Path::new("photos"), "*.jpg"), "photos/*.jpg");
+}
+
+#[test]
+fn test_check_base_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("file.txt");
+    std::fs::write(&file, "").unwrap();
+
+    assert_eq!(check_base_dir(&temp_dir.path().to_string_lossy()).unwrap(), temp_dir.path());
+
+    let err = check_base_dir(&file.to_string_lossy()).unwrap_err();
+    assert!(err.ends_with("not a directory."), "got: {}", err);
+
+    let missing = temp_dir.path().join("missing");
+    assert!(check_base_dir(&missing.to_string_lossy()).is_err());
+}
+
+#[tokio::test]
+async fn test_history_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    assert!(load_history(dir).await.unwrap().is_none());
+
+    save_history(dir, vec![rename_action(dir, "a.txt", "b.txt")]).await.unwrap();
+    assert!(dir.join(HISTORY_FILE).exists());
+
+    let history = load_history(dir).await.unwrap().unwrap();
+    assert_eq!(history.actions.len(), 1);
+    assert_eq!(history.actions[0].new_path, dir.join("b.txt"));
+
+    // An empty batch keeps the previous history
+    save_history(dir, Vec::new()).await.unwrap();
+    assert!(load_history(dir).await.unwrap().is_some());
+
+    clear_history(dir).await.unwrap();
+    assert!(load_history(dir).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_load_history_malformed() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join(HISTORY_FILE), "not json").unwrap();
+
+    let err = load_history(temp_dir.path()).await.unwrap_err();
+    assert!(err.starts_with("Invalid history file"), "got: {}", err);
+}
+
+#[tokio::test]
+async fn test_audit_log_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    assert!(read_audit_log(dir).await.unwrap().is_empty());
+
+    for i in 0..2 {
+        let execution = RenameExecutionResult {
+            successful: vec![rename_action(dir, "a.txt", &format!("a{}.txt", i))],
+            skipped: vec![],
+            errors: vec![],
+        };
+        log_audit(dir, &format!("frencli run {}", i), None, &execution).await.unwrap();
+    }
+
+    // Most recent entry first
+    let entries = read_audit_log(dir).await.unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].command, "frencli run 1");
+    assert_eq!(entries[1].command, "frencli run 0");
+}